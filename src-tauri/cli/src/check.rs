@@ -0,0 +1,162 @@
+use anyhow::Result;
+use chronograph_core::baseline::MetricChange;
+use chronograph_core::chronograph_engine::ChronoGraphEngine;
+use chronograph_core::dependency_analyzer::{AnalysisIssue, IssueLevel};
+use chronograph_core::regressions::RegressionEvent;
+use clap::Args;
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Record the latest commit as the baseline instead of checking
+    #[arg(long, conflicts_with = "ratchet")]
+    record_baseline: bool,
+    /// Tighten the baseline when the latest commit improves on it
+    #[arg(long)]
+    ratchet: bool,
+    /// Baseline file to read and write instead of the repository's committed
+    /// .chronograph/baseline.json; required to record a baseline of a remote repository
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+    /// Fail on rule violations with warning severity too
+    #[arg(long)]
+    fail_on_warnings: bool,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// Everything that fails the check at the latest commit. With a baseline only
+/// violations, cycles and metric changes not accepted by it are listed.
+#[derive(Serialize)]
+struct CheckReport {
+    commit_hash: String,
+    baseline_commit: Option<String>,
+    violations: Vec<AnalysisIssue>,
+    new_cycles: Vec<Vec<String>>,
+    metric_regressions: Vec<MetricChange>,
+    /// Threshold regressions relative to the previous analyzed commit
+    regressions: Vec<RegressionEvent>,
+}
+
+impl CheckReport {
+    fn passed(&self) -> bool {
+        self.violations.is_empty()
+            && self.new_cycles.is_empty()
+            && self.metric_regressions.is_empty()
+            && self.regressions.is_empty()
+    }
+
+    fn print(&self) {
+        match self.baseline_commit {
+            Some(ref baseline) => println!("Checked {} against baseline {}", self.commit_hash, baseline),
+            None => println!("Checked {}", self.commit_hash),
+        }
+        for issue in &self.violations {
+            let location = match (&issue.file_path, issue.line_number) {
+                (Some(path), Some(line)) => format!("{}:{}", path.display(), line),
+                (Some(path), None) => path.display().to_string(),
+                _ => String::from("-"),
+            };
+            println!("  {:?} {}: {}", issue.level, location, issue.message);
+        }
+        for cycle in &self.new_cycles {
+            println!("  New cycle: {}", cycle.join(" -> "));
+        }
+        for change in &self.metric_regressions {
+            println!("  {} {} rose above baseline: {} -> {}", change.subject, change.metric, change.baseline, change.current);
+        }
+        for regression in &self.regressions {
+            println!("  Regression: {}", regression.message);
+        }
+        println!("{}", if self.passed() { "Check passed" } else { "Check failed" });
+    }
+}
+
+impl CheckArgs {
+    /// Whether the check writes a baseline file
+    pub fn writes_baseline(&self) -> bool {
+        self.record_baseline || self.ratchet
+    }
+
+    /// Tell where the baseline went. The repository's own baseline file is read
+    /// from the committed head, so it only takes effect once committed.
+    fn report_written(&self, engine: &ChronoGraphEngine) -> Result<()> {
+        let path = engine.baseline_path()?;
+        let mut message = format!("Baseline written to {}", path.display());
+        if self.baseline.is_none() {
+            message.push_str("; commit it so the next check uses it");
+        }
+        // Keep JSON output parseable
+        if self.json {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+        Ok(())
+    }
+
+    fn fails(&self, issue: &AnalysisIssue) -> bool {
+        match issue.level {
+            IssueLevel::Error => true,
+            IssueLevel::Warning => self.fail_on_warnings,
+            IssueLevel::Info => false,
+        }
+    }
+
+    /// Run the check and print its report; returns whether it passed
+    pub fn run(&self, engine: &mut ChronoGraphEngine) -> Result<bool> {
+        if self.record_baseline {
+            let baseline = engine.record_baseline(None)?;
+            if self.json {
+                println!("{}", serde_json::to_string_pretty(&baseline)?);
+            } else {
+                println!(
+                    "Recorded baseline at {}: {} violations, {} cycles",
+                    baseline.commit_hash, baseline.violations.len(), baseline.cycles.len()
+                );
+            }
+            self.report_written(engine)?;
+            return Ok(true);
+        }
+
+        let latest = engine.get_snapshots().last()
+            .ok_or_else(|| anyhow::anyhow!("No analysis available"))?;
+        let commit_hash = latest.commit_info.hash.clone();
+        let regressions = latest.regressions.clone();
+
+        let report = match engine.get_baseline() {
+            Some(_) => {
+                let comparison = engine.compare_with_baseline(None)?;
+                if self.ratchet && comparison.improved() {
+                    engine.tighten_baseline(None)?;
+                    self.report_written(engine)?;
+                }
+                CheckReport {
+                    commit_hash,
+                    baseline_commit: Some(comparison.baseline_commit),
+                    violations: comparison.new_violations.into_iter().filter(|i| self.fails(i)).collect(),
+                    new_cycles: comparison.new_cycles,
+                    metric_regressions: comparison.regressions,
+                    regressions,
+                }
+            }
+            None => CheckReport {
+                violations: engine.rule_violations(&commit_hash)?.into_iter().filter(|i| self.fails(i)).collect(),
+                commit_hash,
+                baseline_commit: None,
+                new_cycles: Vec::new(),
+                metric_regressions: Vec::new(),
+                regressions,
+            },
+        };
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            report.print();
+        }
+        Ok(report.passed())
+    }
+}
@@ -0,0 +1,305 @@
+//! Headless ChronoGraph for scripts and CI: analyze a repository's history, export
+//! the results, diff two commits, gate a pipeline on architecture checks or serve
+//! the engine over a local HTTP API.
+//!
+//! Exit codes: 0 on success, 1 when `check` finds failures, 2 on errors.
+
+mod check;
+mod server;
+
+use anyhow::{Context, Result};
+use chronograph_core::analysis_export::ExportOptions;
+use chronograph_core::architecture_rules::RuleSet;
+use chronograph_core::chronograph_engine::{ChronoGraphConfig, ChronoGraphEngine};
+use chronograph_core::lakos_analyzer::LakosAnalyzer;
+use chronograph_core::quality_score::QualityScoreModel;
+use clap::{Args, Parser, Subcommand};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// The analysis ran but checks failed
+const EXIT_CHECK_FAILED: u8 = 1;
+/// The analysis could not run
+const EXIT_ERROR: u8 = 2;
+
+#[derive(Parser)]
+#[command(version, about = "Temporal dependency analysis of Dart/Flutter repositories")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Log more on stderr: -v for diagnostics, -vv for everything
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Only log errors on stderr, no progress or warnings
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+}
+
+impl Cli {
+    /// Log to stderr at the level of the verbosity flags; RUST_LOG overrides it
+    fn init_logger(&self) {
+        let level = match (self.quiet, self.verbose) {
+            (true, _) => log::LevelFilter::Error,
+            (false, 0) => log::LevelFilter::Info,
+            (false, 1) => log::LevelFilter::Debug,
+            (false, _) => log::LevelFilter::Trace,
+        };
+        env_logger::Builder::new()
+            .filter_level(level)
+            .parse_default_env()
+            .format(|buf, record| match record.level() {
+                log::Level::Info => writeln!(buf, "{}", record.args()),
+                level => writeln!(buf, "{}: {}", level, record.args()),
+            })
+            .init();
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Analyze a repository's history and print summary statistics as JSON
+    Analyze {
+        #[command(flatten)]
+        analysis: AnalysisArgs,
+    },
+    /// Analyze a repository and export the results
+    Export {
+        #[command(flatten)]
+        analysis: AnalysisArgs,
+        /// Export format: json, graphml, gexf, dot, mermaid, plantuml, structurizr,
+        /// html, sarif, csv, parquet or arrow
+        #[arg(short, long, default_value = "json")]
+        format: String,
+        /// Export one analyzed commit instead of the whole timeline
+        #[arg(long)]
+        commit: Option<String>,
+        /// Cluster DOT nodes by folder
+        #[arg(long)]
+        cluster: bool,
+        /// Folder depth diagrams collapse files to, below --subtree if given
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Only draw this folder in diagrams
+        #[arg(long)]
+        subtree: Option<String>,
+        /// Length in days of the periods the html report summarizes changes over
+        #[arg(long)]
+        period_days: Option<u32>,
+        /// Commit the html report draws a diagram of; repeatable
+        #[arg(long = "diagram-commit")]
+        diagram_commits: Vec<String>,
+        /// Only this table for csv, parquet and arrow: edges, nodes, commits or issues
+        #[arg(long)]
+        table: Option<String>,
+        /// Write to a file instead of stdout; a directory for parquet, arrow and
+        /// csv exports of all tables
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Diff the dependency graphs of two analyzed commits
+    Diff {
+        #[command(flatten)]
+        analysis: AnalysisArgs,
+        /// Older commit (full hash or unique prefix)
+        from: String,
+        /// Newer commit (full hash or unique prefix)
+        to: String,
+    },
+    /// Check architecture rules, regression thresholds and the baseline at the latest commit
+    Check {
+        #[command(flatten)]
+        analysis: AnalysisArgs,
+        #[command(flatten)]
+        check: check::CheckArgs,
+    },
+    /// Serve the engine as an HTTP/JSON API on localhost
+    Serve {
+        #[arg(long, default_value_t = 7421)]
+        port: u16,
+    },
+}
+
+#[derive(Args)]
+struct AnalysisArgs {
+    /// Local repository path or remote URL
+    repo: String,
+    /// Branch or tag to analyze instead of the default branch
+    #[arg(long = "ref")]
+    git_ref: Option<String>,
+    /// Project subfolder to analyze, e.g. packages/app
+    #[arg(long)]
+    subfolder: Option<String>,
+    /// Analyze every Nth commit
+    #[arg(long)]
+    sampling: Option<usize>,
+    /// Maximum number of commits to analyze, 0 for no limit
+    #[arg(long)]
+    max_commits: Option<usize>,
+    #[arg(long, default_value = "lakos")]
+    analyzer: String,
+    /// Architecture rules file (defaults to the repository's .chronograph/rules.json)
+    #[arg(long)]
+    rules: Option<PathBuf>,
+    /// Regression thresholds file (defaults to the repository's .chronograph/thresholds.json)
+    #[arg(long)]
+    thresholds: Option<PathBuf>,
+    /// Quality score model file: a preset name plus overrides (defaults to the
+    /// repository's .chronograph/quality.json)
+    #[arg(long)]
+    quality_model: Option<PathBuf>,
+    /// Directory for repository clones
+    #[arg(long)]
+    work_dir: Option<PathBuf>,
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid JSON in {}", path.display()))
+}
+
+impl AnalysisArgs {
+    fn config(&self) -> Result<ChronoGraphConfig> {
+        let mut config = ChronoGraphConfig {
+            github_url: self.repo.clone(),
+            analyzer_name: self.analyzer.clone(),
+            is_local_repository: Path::new(&self.repo).exists(),
+            git_ref: self.git_ref.clone(),
+            subfolder: self.subfolder.as_ref().map(|s| s.replace('\\', "/")),
+            // Keep clones so follow-up runs only fetch new commits
+            cleanup_after_analysis: false,
+            ..Default::default()
+        };
+        if let Some(sampling) = self.sampling {
+            config.commit_sampling = sampling.max(1);
+        }
+        if let Some(max_commits) = self.max_commits {
+            config.max_commits = (max_commits > 0).then_some(max_commits);
+        }
+        if let Some(ref work_dir) = self.work_dir {
+            config.local_base_dir = work_dir.clone();
+        }
+        if let Some(ref path) = self.rules {
+            let rules: RuleSet = serde_json::from_value(read_json(path)?)
+                .with_context(|| format!("Invalid architecture rules in {}", path.display()))?;
+            rules.validate().with_context(|| format!("Invalid architecture rules in {}", path.display()))?;
+            config.architecture_rules = Some(rules);
+        }
+        if let Some(ref path) = self.thresholds {
+            config.regression_thresholds = Some(serde_json::from_value(read_json(path)?)
+                .with_context(|| format!("Invalid regression thresholds in {}", path.display()))?);
+        }
+        if let Some(ref path) = self.quality_model {
+            config.quality_model = Some(QualityScoreModel::from_config(&read_json(path)?)
+                .with_context(|| format!("Invalid quality model in {}", path.display()))?);
+        }
+        Ok(config)
+    }
+
+    fn run(&self) -> Result<ChronoGraphEngine> {
+        self.run_with(self.config()?)
+    }
+
+    fn run_with(&self, config: ChronoGraphConfig) -> Result<ChronoGraphEngine> {
+        if config.analyzer_name == "lakos" && !LakosAnalyzer::is_available() {
+            anyhow::bail!("Lakos analyzer is not installed. Please run: dart pub global activate lakos");
+        }
+
+        let mut engine = ChronoGraphEngine::new(config);
+        engine.analyze_repository(|progress| {
+            log::info!("[{:>3.0}%] {}", progress.percentage, progress.message);
+        })?;
+        Ok(engine)
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<u8> {
+    match cli.command {
+        Command::Analyze { analysis } => {
+            let engine = analysis.run()?;
+            print_json(&engine.get_statistics())?;
+        }
+        Command::Export {
+            analysis, format, commit, cluster, depth, subtree, period_days, diagram_commits, table, mut output,
+        } => {
+            let writes_tables = matches!(format.as_str(), "parquet" | "arrow") || (format == "csv" && table.is_none());
+            if writes_tables && output.is_none() {
+                anyhow::bail!("{} export writes one file per table; pass an output directory with -o", format);
+            }
+            let engine = analysis.run()?;
+            let options = ExportOptions {
+                commit_hash: commit,
+                cluster_by_folder: cluster,
+                table,
+                output_dir: if writes_tables { output.take() } else { None },
+                folder_depth: depth,
+                subtree,
+                period_days,
+                diagram_commits,
+            };
+            let exported = chronograph_core::analysis_export::export(&engine, &format, &options)?;
+            match output {
+                Some(path) => std::fs::write(&path, exported)
+                    .with_context(|| format!("Failed to write {}", path.display()))?,
+                None => println!("{}", exported),
+            }
+        }
+        Command::Diff { analysis, from, to } => {
+            let engine = analysis.run()?;
+            let diff = engine.diff_snapshots(&from, &to).context(
+                "Both commits must be analyzed snapshots; lower --sampling to include more commits",
+            )?;
+            print_json(&diff)?;
+        }
+        Command::Check { analysis, check } => {
+            let mut config = analysis.config()?;
+            config.baseline_path = check.baseline.clone();
+            if check.writes_baseline() {
+                config.baseline_target()?;
+            }
+            let mut engine = analysis.run_with(config)?;
+            if !check.run(&mut engine)? {
+                return Ok(EXIT_CHECK_FAILED);
+            }
+        }
+        Command::Serve { port } => server::serve(port)?,
+    }
+    Ok(0)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    cli.init_logger();
+    match run(cli) {
+        Ok(code) => ExitCode::from(code),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::parse_from(["chronograph-cli", "diff", "../app", "abc", "def", "--ref", "release", "-vv"]);
+        assert_eq!(cli.verbose, 2);
+        let Command::Diff { analysis, from, to } = cli.command else {
+            panic!("expected diff");
+        };
+        assert_eq!((from.as_str(), to.as_str()), ("abc", "def"));
+        assert_eq!(analysis.git_ref.as_deref(), Some("release"));
+    }
+}
@@ -0,0 +1,261 @@
+//! Local HTTP/JSON API over the engine for dashboards, review bots and editor
+//! plugins. Handlers go through the same `chronograph_core::api` operations and
+//! engine methods as the desktop commands, so results are identical.
+
+use anyhow::{Context, Result};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chronograph_core::api::{self, ChronoGraphState, ProgressState};
+use chronograph_core::chronograph_engine::{AnalysisProgress, ChronoGraphEngine, CommitSnapshot};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A background analysis started through the API
+#[derive(Debug, Clone, Serialize)]
+struct AnalysisJob {
+    id: u64,
+    github_url: String,
+    status: JobStatus,
+    progress: Option<AnalysisProgress>,
+    snapshot_count: Option<usize>,
+    error: Option<String>,
+}
+
+#[derive(Default, Clone)]
+struct ServerState {
+    engine: ChronoGraphState,
+    progress: ProgressState,
+    jobs: Arc<Mutex<Vec<AnalysisJob>>>,
+}
+
+/// Error body: `{"error": "..."}`
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        ApiError(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+type ApiResult<T> = std::result::Result<Json<T>, ApiError>;
+
+fn not_found(message: impl Into<String>) -> ApiError {
+    ApiError(StatusCode::NOT_FOUND, message.into())
+}
+
+/// Run a query against the analyzed engine
+fn with_engine<T>(state: &ServerState, query: impl FnOnce(&ChronoGraphEngine) -> Result<T>) -> ApiResult<T> {
+    let state_guard = state.engine.lock().map_err(|e| e.to_string())?;
+    match state_guard.as_ref() {
+        Some(engine) => query(engine).map(Json).map_err(|e| ApiError::from(e.to_string())),
+        None => Err(ApiError(StatusCode::CONFLICT, "No analysis available".to_string())),
+    }
+}
+
+/// Same arguments as the `initialize_analysis` command
+#[derive(Deserialize)]
+struct StartAnalysisRequest {
+    github_url: String,
+    config_options: Option<serde_json::Value>,
+}
+
+async fn start_analysis(
+    State(state): State<ServerState>,
+    Json(request): Json<StartAnalysisRequest>,
+) -> std::result::Result<(StatusCode, Json<AnalysisJob>), ApiError> {
+    // Probes for the analyzer, so it runs before taking the jobs lock
+    let config = api::config_from_options(&request.github_url, request.config_options.as_ref())?;
+    let job = {
+        let mut jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+        if jobs.iter().any(|job| job.status == JobStatus::Running) {
+            return Err(ApiError(StatusCode::CONFLICT, "An analysis is already running".to_string()));
+        }
+
+        *state.engine.lock().map_err(|e| e.to_string())? = Some(ChronoGraphEngine::new(config));
+        *state.progress.lock().map_err(|e| e.to_string())? = None;
+
+        let job = AnalysisJob {
+            id: jobs.len() as u64 + 1,
+            github_url: request.github_url,
+            status: JobStatus::Running,
+            progress: None,
+            snapshot_count: None,
+            error: None,
+        };
+        jobs.push(job.clone());
+        job
+    };
+
+    let analysis_state = state.clone();
+    tokio::spawn(run_job(state, job.id, move || {
+        api::run_analysis(&analysis_state.engine, &analysis_state.progress)
+    }));
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Run a job's analysis on a blocking thread and record its outcome. A task that
+/// dies without returning still fails the job, so it never stays running.
+async fn run_job(
+    state: ServerState,
+    id: u64,
+    analysis: impl FnOnce() -> std::result::Result<Vec<CommitSnapshot>, String> + Send + 'static,
+) {
+    let result = tokio::task::spawn_blocking(analysis)
+        .await
+        .unwrap_or_else(|e| Err(format!("Analysis task failed: {}", e)));
+    if let Ok(mut jobs) = state.jobs.lock() {
+        if let Some(job) = jobs.iter_mut().find(|job| job.id == id) {
+            match result {
+                Ok(snapshots) => {
+                    job.status = JobStatus::Completed;
+                    job.snapshot_count = Some(snapshots.len());
+                }
+                Err(e) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(e);
+                }
+            }
+        }
+    }
+}
+
+async fn get_job(State(state): State<ServerState>, Path(id): Path<u64>) -> ApiResult<AnalysisJob> {
+    let jobs = state.jobs.lock().map_err(|e| e.to_string())?;
+    let mut job = jobs.iter().find(|job| job.id == id).cloned()
+        .ok_or_else(|| not_found(format!("No job {}", id)))?;
+    if job.status == JobStatus::Running {
+        job.progress = state.progress.lock().map_err(|e| e.to_string())?.clone();
+    }
+    Ok(Json(job))
+}
+
+async fn list_jobs(State(state): State<ServerState>) -> ApiResult<Vec<AnalysisJob>> {
+    Ok(Json(state.jobs.lock().map_err(|e| e.to_string())?.clone()))
+}
+
+async fn get_snapshots(State(state): State<ServerState>) -> ApiResult<Vec<CommitSnapshot>> {
+    with_engine(&state, |engine| Ok(engine.get_snapshots().to_vec()))
+}
+
+async fn get_commit_dependencies(
+    State(state): State<ServerState>,
+    Path(commit_hash): Path<String>,
+) -> ApiResult<Vec<chronograph_core::dependency_analyzer::RawDependency>> {
+    let dependencies = with_engine(&state, |engine| Ok(api::commit_dependencies(engine, &commit_hash)))?;
+    dependencies.0.map(Json).ok_or_else(|| not_found(format!("No analyzed snapshot for commit {}", commit_hash)))
+}
+
+/// Same arguments as the `diff_snapshots` command
+#[derive(Deserialize)]
+struct DiffQuery {
+    from_commit: String,
+    to_commit: String,
+}
+
+async fn diff_snapshots(
+    State(state): State<ServerState>,
+    Query(query): Query<DiffQuery>,
+) -> ApiResult<chronograph_core::snapshot_diff::SnapshotDiff> {
+    with_engine(&state, |engine| engine.diff_snapshots(&query.from_commit, &query.to_commit))
+}
+
+/// Same arguments as the `get_impact` command
+#[derive(Deserialize)]
+struct ImpactRequest {
+    paths: Vec<String>,
+    commit_hash: Option<String>,
+}
+
+async fn get_impact(
+    State(state): State<ServerState>,
+    Json(request): Json<ImpactRequest>,
+) -> ApiResult<chronograph_core::impact::ImpactReport> {
+    with_engine(&state, |engine| engine.impact(request.commit_hash.as_deref(), &request.paths))
+}
+
+fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/api/analysis", post(start_analysis))
+        .route("/api/jobs", get(list_jobs))
+        .route("/api/jobs/{id}", get(get_job))
+        .route("/api/snapshots", get(get_snapshots))
+        .route("/api/snapshots/{commit_hash}/dependencies", get(get_commit_dependencies))
+        .route("/api/diff", get(diff_snapshots))
+        .route("/api/impact", post(get_impact))
+        .with_state(state)
+}
+
+/// Serve the API on localhost until the process is stopped
+pub fn serve(port: u16) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .with_context(|| format!("Failed to listen on 127.0.0.1:{}", port))?;
+        log::info!("ChronoGraph API listening on http://{}", listener.local_addr()?);
+        axum::serve(listener, router(ServerState::default())).await?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_queries_require_an_analysis() {
+        let state = ServerState::default();
+
+        let response = router(state.clone())
+            .oneshot(Request::get("/api/snapshots").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = router(state)
+            .oneshot(Request::get("/api/jobs/7").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_fails() {
+        let state = ServerState::default();
+        state.jobs.lock().unwrap().push(AnalysisJob {
+            id: 1,
+            github_url: "https://example.com/app.git".to_string(),
+            status: JobStatus::Running,
+            progress: None,
+            snapshot_count: None,
+            error: None,
+        });
+
+        run_job(state.clone(), 1, || panic!("analyzer crashed")).await;
+
+        let job = state.jobs.lock().unwrap()[0].clone();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.error.unwrap().contains("panic"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::dependency_analyzer::{AnalysisResult, AnalysisConfig};
+use crate::git_navigator::FileChange;
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -157,6 +158,21 @@ impl AnalysisCache {
             [],
         )?;
 
+        // Files changed per first-parent commit, scoped to the analyzed subfolder
+        // ('' for the repository root)
+        self.connection.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS commit_changes (
+                repo_url TEXT NOT NULL,
+                commit_hash TEXT NOT NULL,
+                subfolder TEXT NOT NULL,
+                changes BLOB NOT NULL,
+                PRIMARY KEY (repo_url, commit_hash, subfolder)
+            )
+            "#,
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -202,7 +218,7 @@ impl AnalysisCache {
                     }
                     Err(e) => {
                         // File corrupted, remove from cache
-                        eprintln!("Warning: Corrupted cache file {}, removing entry: {}",
+                        log::warn!("Corrupted cache file {}, removing entry: {}",
                                  file_path.display(), e);
                         self.remove_entry(&cache_key)?;
                     }
@@ -254,6 +270,40 @@ impl AnalysisCache {
         Ok(())
     }
 
+    /// Get the files a commit changed within `subfolder` from cache
+    pub fn get_commit_changes(
+        &mut self,
+        repo_url: &str,
+        commit_hash: &str,
+        subfolder: Option<&str>,
+    ) -> Result<Option<Vec<FileChange>>> {
+        let data: Option<Vec<u8>> = self.connection.query_row(
+            "SELECT changes FROM commit_changes WHERE repo_url = ? AND commit_hash = ? AND subfolder = ?",
+            params![repo_url, commit_hash, subfolder.unwrap_or("")],
+            |row| row.get(0),
+        ).optional()?;
+
+        data.map(|data| bincode::deserialize(&data).context("Failed to deserialize commit changes"))
+            .transpose()
+    }
+
+    /// Store the files a commit changed within `subfolder` in cache
+    pub fn put_commit_changes(
+        &mut self,
+        repo_url: &str,
+        commit_hash: &str,
+        subfolder: Option<&str>,
+        changes: &[FileChange],
+    ) -> Result<()> {
+        let data = bincode::serialize(changes)
+            .context("Failed to serialize commit changes")?;
+        self.connection.execute(
+            "INSERT OR REPLACE INTO commit_changes (repo_url, commit_hash, subfolder, changes) VALUES (?, ?, ?, ?)",
+            params![repo_url, commit_hash, subfolder.unwrap_or(""), data],
+        )?;
+        Ok(())
+    }
+
     /// Remove all cache entries for a repository
     pub fn remove_repository(&mut self, repo_url: &str) -> Result<Vec<PathBuf>> {
         let mut removed_files = Vec::new();
@@ -281,6 +331,10 @@ impl AnalysisCache {
             "DELETE FROM analysis_cache WHERE repo_url = ?",
             params![repo_url],
         )?;
+        self.connection.execute(
+            "DELETE FROM commit_changes WHERE repo_url = ?",
+            params![repo_url],
+        )?;
 
         Ok(removed_files)
     }
@@ -366,6 +420,7 @@ impl AnalysisCache {
 
         // Clear database
         self.connection.execute("DELETE FROM analysis_cache", [])?;
+        self.connection.execute("DELETE FROM commit_changes", [])?;
 
         Ok(total_entries)
     }
@@ -457,6 +512,7 @@ mod tests {
             global_metrics: None,
             node_metrics: None,
             architecture_quality_score: None,
+            quality_breakdown: None,
             analyzer_name: "test".to_string(),
             analyzer_version: "1.0.0".to_string(),
             analysis_timestamp: 1234567890,
@@ -0,0 +1,149 @@
+use crate::chronograph_engine::{ChronoGraphEngine, CommitSnapshot};
+use crate::diagram_export;
+use crate::git_navigator::RepoCloneInfo;
+use crate::graph_export;
+use crate::hotspots::HotspotReport;
+use crate::html_report::{self, ReportOptions};
+use crate::modularity::ModularityReport;
+use crate::sarif_export;
+use crate::table_export::{self, Table};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Version of the `json` export layout. Version 1 was a bare array of
+/// snapshots; version 2 wraps them in `AnalysisExport`.
+pub const EXPORT_FORMAT_VERSION: u32 = 2;
+
+/// Everything an export contains, gathered once from the engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisExport {
+    pub format_version: u32,
+    pub exported_at: i64,
+    pub repository: Option<RepoCloneInfo>,
+    pub subfolder: Option<String>,
+    pub snapshots: Vec<CommitSnapshot>,
+    /// Hotspot ranking per snapshot, oldest first
+    pub hotspots: Vec<HotspotReport>,
+    /// Centrality, communities and folder mismatch per snapshot, oldest first
+    pub modularity: Vec<ModularityReport>,
+}
+
+impl AnalysisExport {
+    pub fn from_engine(engine: &ChronoGraphEngine) -> Self {
+        Self {
+            format_version: EXPORT_FORMAT_VERSION,
+            exported_at: chrono::Utc::now().timestamp(),
+            repository: engine.get_repo_info().cloned(),
+            subfolder: engine.get_config().subfolder.clone(),
+            snapshots: engine.get_snapshots().to_vec(),
+            hotspots: engine.hotspot_timeline(None),
+            modularity: engine.modularity_timeline(),
+        }
+    }
+}
+
+/// Options for `export`; formats ignore the ones that do not apply to them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Export one analyzed commit instead of the whole timeline. Single-graph
+    /// formats (GraphML, DOT, diagrams, SARIF) use the latest commit when unset.
+    pub commit_hash: Option<String>,
+    /// Group DOT nodes into one cluster per folder
+    pub cluster_by_folder: bool,
+    /// Only this table for tabular formats: edges, nodes, commits or issues
+    pub table: Option<String>,
+    /// Directory tabular formats write one file per table into
+    pub output_dir: Option<PathBuf>,
+    /// Folder depth diagrams collapse files to, below the subtree if set (default 2)
+    pub folder_depth: Option<usize>,
+    /// Only draw this folder in diagrams
+    pub subtree: Option<String>,
+    /// Length in days of the periods the HTML report summarizes changes over
+    pub period_days: Option<u32>,
+    /// Commits the HTML report draws; the first and latest when empty
+    pub diagram_commits: Vec<String>,
+}
+
+/// Snapshots selected by the options, oldest first
+fn selected_snapshots<'a>(engine: &'a ChronoGraphEngine, options: &ExportOptions) -> Result<&'a [CommitSnapshot]> {
+    let snapshots = match options.commit_hash {
+        Some(ref hash) => std::slice::from_ref(
+            engine.find_snapshot(hash).ok_or_else(|| anyhow!("No analyzed snapshot for commit {}", hash))?,
+        ),
+        None => engine.get_snapshots(),
+    };
+    if snapshots.is_empty() {
+        return Err(anyhow!("No analysis available"));
+    }
+    Ok(snapshots)
+}
+
+/// The one snapshot single-graph formats draw
+fn selected_snapshot<'a>(engine: &'a ChronoGraphEngine, options: &ExportOptions) -> Result<&'a CommitSnapshot> {
+    let snapshots = selected_snapshots(engine, options)?;
+    Ok(&snapshots[snapshots.len() - 1])
+}
+
+/// Tables as CSV text, or as files in the output directory. Returns the CSV, or
+/// the written paths as a JSON array.
+fn export_tables(engine: &ChronoGraphEngine, format: &str, options: &ExportOptions) -> Result<String> {
+    let snapshots = selected_snapshots(engine, options)?;
+    let tables = match options.table {
+        Some(ref name) => vec![Table::parse(name)?],
+        None => Table::ALL.to_vec(),
+    };
+    match options.output_dir {
+        Some(ref dir) => {
+            let written = table_export::write_tables(snapshots, format, &tables, dir)?;
+            Ok(serde_json::to_string_pretty(&written)?)
+        }
+        None if format == "csv" && tables.len() == 1 => table_export::to_csv(tables[0], snapshots),
+        None => Err(anyhow!("Exporting {} tables needs an output directory", format)),
+    }
+}
+
+/// Serialize an export in the requested format: json, graphml, gexf, dot, the
+/// mermaid, plantuml and structurizr diagrams, an html report, a sarif log, or
+/// the csv, parquet and arrow tables
+pub fn export(engine: &ChronoGraphEngine, format: &str, options: &ExportOptions) -> Result<String> {
+    match format {
+        "json" => Ok(serde_json::to_string_pretty(&AnalysisExport::from_engine(engine))?),
+        "graphml" => Ok(graph_export::graphml(selected_snapshot(engine, options)?)),
+        "gexf" => {
+            let description = engine.get_repo_info()
+                .map(|info| info.original_url.clone())
+                .unwrap_or_else(|| engine.get_config().github_url.clone());
+            Ok(graph_export::gexf(selected_snapshots(engine, options)?, &description))
+        }
+        "dot" => Ok(graph_export::dot(selected_snapshot(engine, options)?, options.cluster_by_folder)),
+        "mermaid" | "plantuml" | "structurizr" => {
+            let snapshot = selected_snapshot(engine, options)?;
+            let depth = options.folder_depth.unwrap_or(2);
+            let subtree = options.subtree.as_deref();
+            Ok(match format {
+                "mermaid" => diagram_export::mermaid(snapshot, depth, subtree),
+                "plantuml" => diagram_export::plantuml(snapshot, depth, subtree),
+                _ => diagram_export::structurizr(snapshot, depth, subtree),
+            })
+        }
+        "sarif" => sarif_export::sarif(
+            selected_snapshot(engine, options)?,
+            engine.get_repo_info(),
+            engine.get_config().subfolder.as_deref(),
+        ),
+        "html" => {
+            let mut report = ReportOptions { diagram_commits: options.diagram_commits.clone(), ..Default::default() };
+            if let Some(days) = options.period_days {
+                report.period_days = days;
+            }
+            if let Some(depth) = options.folder_depth {
+                report.folder_depth = depth;
+            }
+            html_report::report(engine, &report)
+        }
+        "csv" | "parquet" | "arrow" => export_tables(engine, format, options),
+        _ => Err(anyhow!("Unsupported export format: {}", format)),
+    }
+}
@@ -0,0 +1,122 @@
+//! Operations shared by the front ends over the engine (Tauri commands and the HTTP
+//! server), so both accept the same options and return the same results.
+
+use crate::architecture_rules::RuleSet;
+use crate::chronograph_engine::{AnalysisProgress, ChronoGraphConfig, ChronoGraphEngine, CommitSnapshot};
+use crate::dependency_analyzer::RawDependency;
+use crate::lakos_analyzer::LakosAnalyzer;
+use crate::quality_score::QualityScoreModel;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Shared state for ChronoGraph engine
+pub type ChronoGraphState = Arc<Mutex<Option<ChronoGraphEngine>>>;
+
+/// Progress callback state for analysis updates
+pub type ProgressState = Arc<Mutex<Option<AnalysisProgress>>>;
+
+/// Build an engine configuration from the options accepted by `initialize_analysis`
+pub fn config_from_options(
+    github_url: &str,
+    options: Option<&serde_json::Value>,
+) -> Result<ChronoGraphConfig, String> {
+    let mut config = ChronoGraphConfig {
+        github_url: github_url.to_string(),
+        ..Default::default()
+    };
+
+    if let Some(options) = options {
+        if let Some(sampling) = options.get("commit_sampling").and_then(|v| v.as_u64()) {
+            config.commit_sampling = sampling as usize;
+        }
+        if let Some(max_commits) = options.get("max_commits").and_then(|v| v.as_u64()) {
+            config.max_commits = Some(max_commits as usize);
+        }
+        if let Some(analyzer) = options.get("analyzer").and_then(|v| v.as_str()) {
+            config.analyzer_name = analyzer.to_string();
+        }
+        if let Some(subfolder) = options.get("subfolder").and_then(|v| v.as_str()) {
+            // Normalize path separators - convert backslashes to forward slashes
+            let normalized_subfolder = subfolder.replace('\\', "/");
+            config.subfolder = Some(normalized_subfolder);
+        }
+        if let Some(is_local) = options.get("is_local_path").and_then(|v| v.as_bool()) {
+            config.is_local_repository = is_local;
+        }
+        if let Some(git_ref) = options.get("git_ref").and_then(|v| v.as_str()) {
+            config.git_ref = Some(git_ref.to_string());
+        }
+        if let Some(thresholds) = options.get("regression_thresholds") {
+            config.regression_thresholds = Some(serde_json::from_value(thresholds.clone())
+                .map_err(|e| format!("Invalid regression thresholds: {}", e))?);
+        }
+        if let Some(model) = options.get("quality_model") {
+            config.quality_model = Some(QualityScoreModel::from_config(model)
+                .map_err(|e| format!("Invalid quality model: {}", e))?);
+        }
+        if let Some(rules) = options.get("architecture_rules") {
+            let rules: RuleSet = serde_json::from_value(rules.clone())
+                .map_err(|e| format!("Invalid architecture rules: {}", e))?;
+            rules.validate().map_err(|e| format!("Invalid architecture rules: {}", e))?;
+            config.architecture_rules = Some(rules);
+        }
+        if let Some(ratchet) = options.get("ratchet_baseline").and_then(|v| v.as_bool()) {
+            config.ratchet_baseline = ratchet;
+        }
+        if let Some(path) = options.get("baseline_path").and_then(|v| v.as_str()) {
+            config.baseline_path = Some(PathBuf::from(path));
+        }
+    }
+
+    // Check if Lakos is available
+    if config.analyzer_name == "lakos" && !LakosAnalyzer::is_available() {
+        return Err("Lakos analyzer is not installed. Please run: dart pub global activate lakos".to_string());
+    }
+
+    Ok(config)
+}
+
+/// Run the analysis of the initialized engine, publishing progress as it goes.
+/// The engine is taken out of the state while it runs and always put back, also
+/// when the analysis panics, which is reported as an error.
+/// Blocks; callers on an async runtime should run it on a blocking thread.
+pub fn run_analysis(state: &ChronoGraphState, progress_state: &ProgressState) -> Result<Vec<CommitSnapshot>, String> {
+    let mut engine = {
+        let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+        state_guard.take().ok_or("No analysis initialized")?
+    }; // MutexGuard is dropped here
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        engine.analyze_repository(|progress| {
+            if let Ok(mut progress_guard) = progress_state.lock() {
+                *progress_guard = Some(progress);
+            }
+        })
+    }));
+
+    // Store engine back for future queries, even if analysis failed
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+    *state_guard = Some(engine);
+    match result {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(payload) => Err(format!("Analysis panicked: {}", panic_message(payload.as_ref()))),
+    }
+}
+
+/// Message of a panic payload, which is a string for `panic!` with a message
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+/// Dependency graph of an analyzed commit, or None if it was not analyzed
+pub fn commit_dependencies(engine: &ChronoGraphEngine, commit_hash: &str) -> Option<Vec<RawDependency>> {
+    engine
+        .find_snapshot(commit_hash)
+        .map(|snapshot| snapshot.analysis_result.dependencies.clone())
+}
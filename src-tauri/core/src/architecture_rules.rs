@@ -0,0 +1,372 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::{AnalysisIssue, IssueLevel, RawDependency};
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::DependencyGraph;
+use anyhow::Result;
+use glob::{MatchOptions, Pattern, PatternError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Rules file read from the head of the analyzed repository when the project
+/// configuration does not provide rules
+pub const RULES_FILE: &str = ".chronograph/rules.json";
+
+fn default_severity() -> IssueLevel {
+    IssueLevel::Error
+}
+
+/// A declarative constraint on the dependency graph. Patterns are globs over
+/// project-relative paths (`lib/ui/**`), plain folders (`lib/data`) or names of
+/// layers defined in the rule set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArchitectureRule {
+    /// Files matching `from` must not import files matching `to`
+    Forbidden {
+        #[serde(default)]
+        name: Option<String>,
+        from: String,
+        to: String,
+        #[serde(default = "default_severity")]
+        severity: IssueLevel,
+    },
+    /// Files matching `from` may only import each other and files matching `to`
+    AllowedOnly {
+        #[serde(default)]
+        name: Option<String>,
+        from: String,
+        to: Vec<String>,
+        #[serde(default = "default_severity")]
+        severity: IssueLevel,
+    },
+    /// No dependency cycles within a scope. A wildcard scope such as
+    /// `lib/features/*` checks every matching folder separately.
+    NoCycles {
+        #[serde(default)]
+        name: Option<String>,
+        scope: String,
+        #[serde(default = "default_severity")]
+        severity: IssueLevel,
+    },
+}
+
+impl ArchitectureRule {
+    /// Display name: the configured name or a description of the constraint
+    pub fn name(&self) -> String {
+        match self {
+            ArchitectureRule::Forbidden { name: Some(name), .. }
+            | ArchitectureRule::AllowedOnly { name: Some(name), .. }
+            | ArchitectureRule::NoCycles { name: Some(name), .. } => name.clone(),
+            ArchitectureRule::Forbidden { from, to, .. } => format!("{} must not import {}", from, to),
+            ArchitectureRule::AllowedOnly { from, to, .. } => {
+                format!("{} may depend only on {}", from, to.join(", "))
+            }
+            ArchitectureRule::NoCycles { scope, .. } => format!("no cycles in {}", scope),
+        }
+    }
+
+    fn severity(&self) -> &IssueLevel {
+        match self {
+            ArchitectureRule::Forbidden { severity, .. }
+            | ArchitectureRule::AllowedOnly { severity, .. }
+            | ArchitectureRule::NoCycles { severity, .. } => severity,
+        }
+    }
+
+    /// Every path pattern or layer name the rule refers to
+    fn patterns(&self) -> Vec<&str> {
+        match self {
+            ArchitectureRule::Forbidden { from, to, .. } => vec![from, to],
+            ArchitectureRule::AllowedOnly { from, to, .. } => {
+                std::iter::once(from.as_str()).chain(to.iter().map(String::as_str)).collect()
+            }
+            ArchitectureRule::NoCycles { scope, .. } => vec![scope],
+        }
+    }
+}
+
+/// Named layers plus the rules over them, as stored in the project rules file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleSet {
+    /// Layer name -> path pattern, e.g. `"domain": "lib/domain/**"`
+    #[serde(default)]
+    pub layers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub rules: Vec<ArchitectureRule>,
+}
+
+/// A compiled path pattern
+struct Matcher {
+    pattern: Option<Pattern>,
+    /// Folder prefix for patterns without wildcards
+    folder: Option<String>,
+}
+
+impl Matcher {
+    fn new(pattern: &str) -> Result<Self, PatternError> {
+        let pattern = pattern.trim_matches('/');
+        // `folder/**` is the whole folder, so treat it as one scope
+        let pattern = pattern.strip_suffix("/**").filter(|p| !p.contains(['*', '?', '['])).unwrap_or(pattern);
+        if pattern.contains(['*', '?', '[']) {
+            Ok(Self { pattern: Some(Pattern::new(pattern)?), folder: None })
+        } else {
+            Ok(Self { pattern: None, folder: Some(pattern.to_string()) })
+        }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        match (&self.pattern, &self.folder) {
+            (Some(pattern), _) => pattern.matches_with(path, MatchOptions {
+                require_literal_separator: true,
+                ..MatchOptions::new()
+            }),
+            (None, Some(folder)) => {
+                folder.is_empty() || path == folder || path.starts_with(&format!("{}/", folder))
+            }
+            _ => false,
+        }
+    }
+
+    /// The outermost ancestor folder of `path` matched by the pattern, used to
+    /// split wildcard scopes into independent groups
+    fn scope_root(&self, path: &str) -> Option<String> {
+        let components: Vec<&str> = path.split('/').collect();
+        (1..components.len())
+            .map(|depth| components[..depth].join("/"))
+            .find(|folder| self.matches(folder))
+            .or_else(|| self.matches(path).then(String::new))
+    }
+}
+
+impl RuleSet {
+    fn resolve<'a>(&'a self, pattern: &'a str) -> &'a str {
+        self.layers.get(pattern).map(String::as_str).unwrap_or(pattern)
+    }
+
+    /// Matcher for a pattern or layer name. Rule sets are validated when loaded,
+    /// so an invalid pattern here matches nothing.
+    fn matcher(&self, pattern: &str) -> Matcher {
+        Matcher::new(self.resolve(pattern)).unwrap_or(Matcher { pattern: None, folder: None })
+    }
+
+    /// Check that every layer and rule pattern is a valid glob
+    pub fn validate(&self) -> Result<()> {
+        for (layer, pattern) in &self.layers {
+            Matcher::new(pattern)
+                .map_err(|e| anyhow::anyhow!("Invalid pattern '{}' of layer '{}': {}", pattern, layer, e))?;
+        }
+        for rule in &self.rules {
+            for pattern in rule.patterns() {
+                Matcher::new(self.resolve(pattern)).map_err(|e| {
+                    anyhow::anyhow!("Invalid pattern '{}' in rule '{}': {}", pattern, rule.name(), e)
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check every rule against a snapshot's dependencies
+    pub fn evaluate(&self, dependencies: &[RawDependency]) -> Vec<AnalysisIssue> {
+        let edges: Vec<(String, String, &RawDependency)> = dependencies
+            .iter()
+            .map(|d| (normalize_path(&d.source_file), normalize_path(&d.target_file), d))
+            .filter(|(source, target, _)| source != target)
+            .collect();
+        let mut issues = Vec::new();
+
+        for rule in &self.rules {
+            let violation = |dep: &RawDependency, message: String| AnalysisIssue {
+                level: rule.severity().clone(),
+                message,
+                file_path: Some(dep.source_file.clone()),
+                line_number: dep.line_number,
+                import_statement: dep.import_statement.clone(),
+                target_path: Some(dep.target_file.clone()),
+                rule: Some(rule.name()),
+            };
+
+            match rule {
+                ArchitectureRule::Forbidden { from, to, .. } => {
+                    let (from, to) = (self.matcher(from), self.matcher(to));
+                    for (source, target, dep) in &edges {
+                        if from.matches(source) && to.matches(target) {
+                            issues.push(violation(dep, format!("{} imports {} ({})", source, target, rule.name())));
+                        }
+                    }
+                }
+                ArchitectureRule::AllowedOnly { from, to, .. } => {
+                    let from = self.matcher(from);
+                    let allowed: Vec<Matcher> = to.iter().map(|t| self.matcher(t)).collect();
+                    for (source, target, dep) in &edges {
+                        if from.matches(source)
+                            && !from.matches(target)
+                            && !allowed.iter().any(|a| a.matches(target))
+                        {
+                            issues.push(violation(dep, format!("{} imports {} ({})", source, target, rule.name())));
+                        }
+                    }
+                }
+                ArchitectureRule::NoCycles { scope, .. } => {
+                    let scope = self.matcher(scope);
+                    let mut groups: HashMap<String, Vec<&RawDependency>> = HashMap::new();
+                    for (source, target, dep) in &edges {
+                        let root = scope.scope_root(source);
+                        if root.is_some() && root == scope.scope_root(target) {
+                            groups.entry(root.unwrap_or_default()).or_default().push(dep);
+                        }
+                    }
+
+                    let mut roots: Vec<&String> = groups.keys().collect();
+                    roots.sort();
+                    for root in roots {
+                        let group: Vec<RawDependency> = groups[root].iter().map(|d| (*d).clone()).collect();
+                        for cycle in DependencyGraph::from_dependencies(&group).cycles() {
+                            let members: HashSet<&String> = cycle.iter().collect();
+                            for dep in &groups[root] {
+                                let source = normalize_path(&dep.source_file);
+                                let target = normalize_path(&dep.target_file);
+                                if members.contains(&source) && members.contains(&target) {
+                                    issues.push(violation(dep, format!(
+                                        "{} imports {}, part of a cycle of {} files ({})",
+                                        source, target, cycle.len(), rule.name()
+                                    )));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+/// Rule violations of one snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleViolationCount {
+    pub commit_hash: String,
+    pub author: String,
+    pub timestamp: i64,
+    pub violations: usize,
+    /// Violations per rule name
+    pub by_rule: BTreeMap<String, usize>,
+}
+
+/// Issues a snapshot carries because of architecture rules
+pub fn rule_issues(snapshot: &CommitSnapshot) -> impl Iterator<Item = &AnalysisIssue> {
+    snapshot.analysis_result.issues.iter().filter(|i| i.rule.is_some())
+}
+
+/// Replace the rule issues of a snapshot with a fresh evaluation
+pub fn apply_rules(snapshot: &mut CommitSnapshot, rules: &RuleSet) {
+    let issues = rules.evaluate(&snapshot.analysis_result.dependencies);
+    let result = &mut snapshot.analysis_result;
+    result.issues.retain(|i| i.rule.is_none());
+    result.issues.extend(issues);
+}
+
+/// Violation counts per snapshot, oldest first
+pub fn violation_timeline(snapshots: &[CommitSnapshot]) -> Vec<RuleViolationCount> {
+    snapshots
+        .iter()
+        .map(|snapshot| {
+            let mut by_rule: BTreeMap<String, usize> = BTreeMap::new();
+            for issue in rule_issues(snapshot) {
+                *by_rule.entry(issue.rule.clone().unwrap_or_default()).or_insert(0) += 1;
+            }
+            RuleViolationCount {
+                commit_hash: snapshot.commit_info.hash.clone(),
+                author: snapshot.commit_info.author_name.clone(),
+                timestamp: snapshot.commit_info.timestamp,
+                violations: by_rule.values().sum(),
+                by_rule,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+    use std::path::PathBuf;
+
+    fn rules() -> RuleSet {
+        serde_json::from_value(serde_json::json!({
+            "layers": { "domain": "lib/domain/**", "core": "lib/core" },
+            "rules": [
+                { "type": "forbidden", "from": "lib/ui/**", "to": "lib/data/**" },
+                { "type": "allowed_only", "from": "domain", "to": ["core"], "severity": "Warning" },
+                { "type": "no_cycles", "name": "features are acyclic", "scope": "lib/features/*" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rules_report_violating_imports() {
+        let mut ui_import = dep("lib/ui/page.dart", "lib/data/db.dart");
+        ui_import.line_number = Some(3);
+        ui_import.import_statement = Some("import '../data/db.dart';".to_string());
+
+        let issues = rules().evaluate(&[
+            ui_import,
+            dep("lib/ui/page.dart", "lib/domain/user.dart"),
+            dep("lib/domain/user.dart", "lib/core/id.dart"),
+            dep("lib/domain/user.dart", "lib/data/db.dart"),
+            dep("lib/features/a/x.dart", "lib/features/a/y.dart"),
+            dep("lib/features/a/y.dart", "lib/features/a/x.dart"),
+            dep("lib/features/b/x.dart", "lib/features/c/x.dart"),
+            dep("lib/features/c/x.dart", "lib/features/b/x.dart"),
+        ]);
+
+        let names: Vec<&str> = issues.iter().filter_map(|i| i.rule.as_deref()).collect();
+        assert_eq!(names, vec![
+            "lib/ui/** must not import lib/data/**",
+            "domain may depend only on core",
+            "features are acyclic",
+            "features are acyclic",
+        ]);
+        assert_eq!(issues[0].line_number, Some(3));
+        assert_eq!(issues[0].import_statement.as_deref(), Some("import '../data/db.dart';"));
+        assert!(matches!(issues[1].level, IssueLevel::Warning));
+        assert_eq!(issues[2].file_path, Some(PathBuf::from("lib/features/a/x.dart")));
+    }
+
+    #[test]
+    fn test_invalid_patterns_are_rejected() {
+        assert!(rules().validate().is_ok());
+
+        let mut invalid = rules();
+        invalid.rules.push(ArchitectureRule::Forbidden {
+            name: None,
+            from: "lib/[ui/**".to_string(),
+            to: "domain".to_string(),
+            severity: default_severity(),
+        });
+        let error = invalid.validate().unwrap_err().to_string();
+        assert!(error.contains("'lib/[ui/**'"), "{}", error);
+
+        let mut invalid = rules();
+        invalid.layers.insert("data".to_string(), "lib/***/data".to_string());
+        assert!(invalid.validate().unwrap_err().to_string().contains("layer 'data'"));
+    }
+
+    #[test]
+    fn test_violation_timeline() {
+        let rules = rules();
+        let mut snapshots = vec![
+            snapshot("c1", 100, vec![dep("lib/ui/page.dart", "lib/domain/user.dart")]),
+            snapshot("c2", 200, vec![dep("lib/ui/page.dart", "lib/data/db.dart")]),
+        ];
+        for snapshot in &mut snapshots {
+            apply_rules(snapshot, &rules);
+            apply_rules(snapshot, &rules);
+        }
+
+        let timeline = violation_timeline(&snapshots);
+        assert_eq!(timeline.iter().map(|c| c.violations).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(rule_issues(&snapshots[1]).count(), 1);
+    }
+}
@@ -0,0 +1,327 @@
+use crate::architecture_rules;
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::AnalysisIssue;
+use crate::graph_algorithms::{dependency_cycles, global_metrics_or_derived, node_metrics_or_derived};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Baseline location relative to the repository root
+pub const BASELINE_FILE: &str = ".chronograph/baseline.json";
+
+const BASELINE_VERSION: u32 = 2;
+
+/// Metric changes smaller than this are treated as noise
+const EPSILON: f64 = 1e-9;
+
+/// An accepted rule violation, identified by rule and offending import. The
+/// message is left out because it can change without the violation changing,
+/// e.g. the cycle size in a no_cycles message.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineViolation {
+    pub rule: String,
+    pub source: String,
+    pub target: String,
+}
+
+impl BaselineViolation {
+    fn from_issue(issue: &AnalysisIssue) -> Self {
+        let normalize = |path: &Option<std::path::PathBuf>| {
+            path.as_deref().map(crate::dependency_view::normalize_path).unwrap_or_default()
+        };
+        Self {
+            rule: issue.rule.clone().unwrap_or_default(),
+            source: normalize(&issue.file_path),
+            target: normalize(&issue.target_path),
+        }
+    }
+}
+
+/// Accepted per-file values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeBaseline {
+    pub component_dependency: u32,
+}
+
+/// Accepted state of a legacy codebase; later analyses only report what got worse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub commit_hash: String,
+    pub recorded_at: i64,
+    pub violations: BTreeSet<BaselineViolation>,
+    /// Sorted member lists of accepted cycles
+    pub cycles: BTreeSet<Vec<String>>,
+    pub normalized_ccd: f64,
+    pub architecture_quality_score: Option<f64>,
+    pub nodes: BTreeMap<String, NodeBaseline>,
+}
+
+fn violations(snapshot: &CommitSnapshot) -> BTreeSet<BaselineViolation> {
+    architecture_rules::rule_issues(snapshot).map(BaselineViolation::from_issue).collect()
+}
+
+fn cycles(snapshot: &CommitSnapshot) -> BTreeSet<Vec<String>> {
+    dependency_cycles(&snapshot.analysis_result).into_iter().collect()
+}
+
+impl Baseline {
+    /// Record the current state of a snapshot as accepted
+    pub fn record(snapshot: &CommitSnapshot) -> Self {
+        let global = global_metrics_or_derived(&snapshot.analysis_result);
+        Self {
+            version: BASELINE_VERSION,
+            commit_hash: snapshot.commit_info.hash.clone(),
+            recorded_at: chrono::Utc::now().timestamp(),
+            violations: violations(snapshot),
+            cycles: cycles(snapshot),
+            normalized_ccd: global.normalized_ccd,
+            architecture_quality_score: snapshot.analysis_result.architecture_quality_score,
+            nodes: node_metrics_or_derived(&snapshot.analysis_result)
+                .into_iter()
+                .map(|(path, m)| {
+                    (path, NodeBaseline { component_dependency: m.component_dependency })
+                })
+                .collect(),
+        }
+    }
+
+    /// A cycle is accepted if it lies within a baseline cycle
+    fn accepts_cycle(&self, cycle: &[String]) -> bool {
+        self.cycles.iter().any(|accepted| cycle.iter().all(|m| accepted.binary_search(m).is_ok()))
+    }
+
+    /// Report what got worse (and better) in a snapshot relative to the baseline
+    pub fn compare(&self, snapshot: &CommitSnapshot) -> BaselineComparison {
+        let current_violations = violations(snapshot);
+        let new_violations = architecture_rules::rule_issues(snapshot)
+            .filter(|issue| !self.violations.contains(&BaselineViolation::from_issue(issue)))
+            .cloned()
+            .collect();
+        let fixed_violations = self.violations.difference(&current_violations).count();
+
+        let current_cycles = cycles(snapshot);
+        let new_cycles = current_cycles.iter().filter(|c| !self.accepts_cycle(c)).cloned().collect();
+        let resolved_cycles = self
+            .cycles
+            .iter()
+            .filter(|accepted| !current_cycles.iter().any(|c| accepted.iter().all(|m| c.contains(m))))
+            .cloned()
+            .collect();
+
+        let mut regressions = Vec::new();
+        let mut improvements = Vec::new();
+        let mut compare = |subject: &str, metric: &str, baseline: f64, current: f64, higher_is_worse: bool| {
+            let change = MetricChange {
+                subject: subject.to_string(),
+                metric: metric.to_string(),
+                baseline,
+                current,
+            };
+            let worse = if higher_is_worse { current - baseline } else { baseline - current };
+            if worse > EPSILON {
+                regressions.push(change);
+            } else if worse < -EPSILON {
+                improvements.push(change);
+            }
+        };
+
+        let global = global_metrics_or_derived(&snapshot.analysis_result);
+        compare("global", "normalized_ccd", self.normalized_ccd, global.normalized_ccd, true);
+        if let (Some(baseline), Some(current)) =
+            (self.architecture_quality_score, snapshot.analysis_result.architecture_quality_score)
+        {
+            compare("global", "architecture_quality_score", baseline, current, false);
+        }
+
+        let mut nodes: Vec<_> = node_metrics_or_derived(&snapshot.analysis_result).into_iter().collect();
+        nodes.sort_by(|a, b| a.0.cmp(&b.0));
+        for (path, metrics) in nodes {
+            if let Some(accepted) = self.nodes.get(&path) {
+                compare(
+                    &path,
+                    "component_dependency",
+                    accepted.component_dependency as f64,
+                    metrics.component_dependency as f64,
+                    true,
+                );
+            }
+        }
+
+        BaselineComparison {
+            baseline_commit: self.commit_hash.clone(),
+            commit_hash: snapshot.commit_info.hash.clone(),
+            new_violations,
+            fixed_violations,
+            new_cycles,
+            resolved_cycles,
+            regressions,
+            improvements,
+        }
+    }
+
+    /// Ratchet the baseline: drop fixed violations and resolved cycles and adopt
+    /// every metric that improved. Nothing that got worse is accepted.
+    pub fn tighten(&self, snapshot: &CommitSnapshot) -> Self {
+        let current_violations = violations(snapshot);
+        let current_cycles = cycles(snapshot);
+        let global = global_metrics_or_derived(&snapshot.analysis_result);
+        let current_nodes = node_metrics_or_derived(&snapshot.analysis_result);
+
+        Self {
+            version: BASELINE_VERSION,
+            commit_hash: snapshot.commit_info.hash.clone(),
+            recorded_at: chrono::Utc::now().timestamp(),
+            violations: self.violations.intersection(&current_violations).cloned().collect(),
+            cycles: current_cycles.into_iter().filter(|c| self.accepts_cycle(c)).collect(),
+            normalized_ccd: self.normalized_ccd.min(global.normalized_ccd),
+            architecture_quality_score: match (
+                self.architecture_quality_score,
+                snapshot.analysis_result.architecture_quality_score,
+            ) {
+                (Some(baseline), Some(current)) => Some(baseline.max(current)),
+                (baseline, _) => baseline,
+            },
+            nodes: self
+                .nodes
+                .iter()
+                .filter_map(|(path, accepted)| {
+                    let current = current_nodes.get(path)?;
+                    Some((
+                        path.clone(),
+                        NodeBaseline {
+                            component_dependency: accepted.component_dependency.min(current.component_dependency),
+                        },
+                    ))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A metric that differs from its baseline value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricChange {
+    /// File path or "global"
+    pub subject: String,
+    pub metric: String,
+    pub baseline: f64,
+    pub current: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineComparison {
+    pub baseline_commit: String,
+    pub commit_hash: String,
+    /// Rule violations not in the baseline
+    pub new_violations: Vec<AnalysisIssue>,
+    pub fixed_violations: usize,
+    pub new_cycles: Vec<Vec<String>>,
+    pub resolved_cycles: Vec<Vec<String>>,
+    pub regressions: Vec<MetricChange>,
+    pub improvements: Vec<MetricChange>,
+}
+
+impl BaselineComparison {
+    /// True when nothing got worse than the baseline
+    pub fn passes(&self) -> bool {
+        self.new_violations.is_empty() && self.new_cycles.is_empty() && self.regressions.is_empty()
+    }
+
+    /// True when tightening the baseline would change it
+    pub fn improved(&self) -> bool {
+        self.fixed_violations > 0 || !self.resolved_cycles.is_empty() || !self.improvements.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::architecture_rules::{apply_rules, RuleSet};
+    use crate::test_fixtures::{dep, snapshot};
+
+    fn analyzed(hash: &str, deps: Vec<crate::dependency_analyzer::RawDependency>) -> CommitSnapshot {
+        let rules: RuleSet = serde_json::from_value(serde_json::json!({
+            "rules": [
+                { "type": "forbidden", "from": "lib/ui/**", "to": "lib/data/**" },
+                { "type": "no_cycles", "scope": "lib/core" }
+            ]
+        }))
+        .unwrap();
+        let mut snapshot = snapshot(hash, 100, deps);
+        apply_rules(&mut snapshot, &rules);
+        snapshot
+    }
+
+    #[test]
+    fn test_only_new_problems_are_reported() {
+        let legacy = analyzed("c1", vec![
+            dep("lib/ui/a.dart", "lib/data/db.dart"),
+            dep("lib/x.dart", "lib/y.dart"),
+            dep("lib/y.dart", "lib/x.dart"),
+        ]);
+        let baseline = Baseline::record(&legacy);
+        assert!(baseline.compare(&legacy).passes());
+
+        let worse = analyzed("c2", vec![
+            dep("lib/ui/a.dart", "lib/data/db.dart"),
+            dep("lib/ui/b.dart", "lib/data/db.dart"),
+            dep("lib/x.dart", "lib/y.dart"),
+            dep("lib/y.dart", "lib/x.dart"),
+            dep("lib/p.dart", "lib/q.dart"),
+            dep("lib/q.dart", "lib/p.dart"),
+        ]);
+        let comparison = baseline.compare(&worse);
+        assert_eq!(comparison.new_violations.len(), 1);
+        assert_eq!(comparison.new_violations[0].file_path.as_deref(), Some(std::path::Path::new("lib/ui/b.dart")));
+        assert_eq!(comparison.new_cycles, vec![vec!["lib/p.dart".to_string(), "lib/q.dart".to_string()]]);
+        assert!(!comparison.passes());
+    }
+
+    #[test]
+    fn test_tighten_drops_fixed_problems() {
+        let legacy = analyzed("c1", vec![
+            dep("lib/ui/a.dart", "lib/data/db.dart"),
+            dep("lib/x.dart", "lib/y.dart"),
+            dep("lib/y.dart", "lib/x.dart"),
+        ]);
+        let baseline = Baseline::record(&legacy);
+
+        let better = analyzed("c2", vec![dep("lib/ui/a.dart", "lib/core.dart"), dep("lib/x.dart", "lib/y.dart")]);
+        let comparison = baseline.compare(&better);
+        assert_eq!(comparison.fixed_violations, 1);
+        assert_eq!(comparison.resolved_cycles.len(), 1);
+        assert!(comparison.improved());
+
+        let tightened = baseline.tighten(&better);
+        assert!(tightened.violations.is_empty());
+        assert!(tightened.cycles.is_empty());
+        assert_eq!(tightened.commit_hash, "c2");
+
+        // Reintroducing the cycle is now a regression
+        assert_eq!(tightened.compare(&legacy).new_cycles.len(), 1);
+    }
+
+    #[test]
+    fn test_shrinking_baselined_cycle_passes() {
+        let legacy = analyzed("c1", vec![
+            dep("lib/core/a.dart", "lib/core/b.dart"),
+            dep("lib/core/b.dart", "lib/core/a.dart"),
+            dep("lib/core/b.dart", "lib/core/c.dart"),
+            dep("lib/core/c.dart", "lib/core/b.dart"),
+        ]);
+        let baseline = Baseline::record(&legacy);
+        assert_eq!(baseline.violations.len(), 4);
+
+        // The cycle loses c.dart, so the remaining violations report a smaller cycle
+        let shrunk = analyzed("c2", vec![
+            dep("lib/core/a.dart", "lib/core/b.dart"),
+            dep("lib/core/b.dart", "lib/core/a.dart"),
+            dep("lib/core/b.dart", "lib/core/c.dart"),
+        ]);
+        let comparison = baseline.compare(&shrunk);
+        assert!(comparison.new_violations.is_empty());
+        assert!(comparison.new_cycles.is_empty());
+        assert_eq!(comparison.fixed_violations, 2);
+        assert!(comparison.passes());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::dependency_analyzer::AnalysisResult;
+use crate::dependency_view::normalize_path;
+use crate::git_navigator::CommitInfo;
+use crate::graph_algorithms::DependencyGraph;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// What to look for while bisecting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BisectTarget {
+    /// A direct dependency from `source` to `target`
+    Edge { source: String, target: String },
+    /// A cycle containing at least all of `members`
+    Cycle { members: Vec<String> },
+}
+
+impl BisectTarget {
+    /// Whether the target exists in an analysis result
+    pub fn is_present(&self, result: &AnalysisResult) -> bool {
+        match self {
+            BisectTarget::Edge { source, target } => {
+                let source = normalize_path(Path::new(source));
+                let target = normalize_path(Path::new(target));
+                result.dependencies.iter().any(|d| {
+                    normalize_path(&d.source_file) == source && normalize_path(&d.target_file) == target
+                })
+            }
+            BisectTarget::Cycle { members } => {
+                let members: BTreeSet<String> = members.iter().map(|m| normalize_path(Path::new(m))).collect();
+                DependencyGraph::from_dependencies(&result.dependencies)
+                    .cycles()
+                    .into_iter()
+                    .any(|cycle| members.iter().all(|m| cycle.contains(m)))
+            }
+        }
+    }
+}
+
+/// One commit checked during the search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BisectStep {
+    pub commit_hash: String,
+    pub present: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BisectResult {
+    pub target: BisectTarget,
+    /// True when the target appeared between the commits, false when it disappeared
+    pub introduced: bool,
+    /// First commit in the sequence whose state matches the later snapshot
+    pub commit: CommitInfo,
+    /// Last commit still matching the earlier snapshot
+    pub previous_commit: CommitInfo,
+    /// Commits that had to be checked out and analyzed (or read from cache)
+    pub steps: Vec<BisectStep>,
+}
+
+/// Binary-search `commits` (oldest first, both ends already known to differ in
+/// whether the target is present) for the first commit matching the last one.
+/// Assumes a single transition between the ends.
+pub fn bisect_commits(
+    target: &BisectTarget,
+    commits: &[CommitInfo],
+    present_at_start: bool,
+    present_at_end: bool,
+    mut probe: impl FnMut(&CommitInfo) -> Result<bool>,
+) -> Result<BisectResult> {
+    if commits.len() < 2 {
+        return Err(anyhow!("Bisect needs two distinct commits"));
+    }
+    if present_at_start == present_at_end {
+        return Err(anyhow!(
+            "Target is {} in both commits; nothing to bisect",
+            if present_at_end { "present" } else { "absent" }
+        ));
+    }
+
+    let mut low = 0;
+    let mut high = commits.len() - 1;
+    let mut steps = Vec::new();
+
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        let present = probe(&commits[mid])?;
+        steps.push(BisectStep {
+            commit_hash: commits[mid].hash.clone(),
+            present,
+        });
+        if present == present_at_end {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    Ok(BisectResult {
+        target: target.clone(),
+        introduced: present_at_end,
+        commit: commits[high].clone(),
+        previous_commit: commits[low].clone(),
+        steps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{analysis_result, commit, dep};
+
+    #[test]
+    fn test_bisect_finds_introducing_commit() {
+        let commits: Vec<CommitInfo> = (0..9).map(|i| commit(&format!("c{}", i), "alice", i)).collect();
+        let target = BisectTarget::Edge {
+            source: "a.dart".to_string(),
+            target: "b.dart".to_string(),
+        };
+        let with_edge = analysis_result(vec![dep("a.dart", "b.dart")]);
+        let without_edge = analysis_result(vec![dep("a.dart", "c.dart")]);
+
+        let mut probed = Vec::new();
+        let result = bisect_commits(&target, &commits, false, true, |c| {
+            probed.push(c.hash.clone());
+            let result = if c.timestamp >= 6 { &with_edge } else { &without_edge };
+            Ok(target.is_present(result))
+        })
+        .unwrap();
+
+        assert!(result.introduced);
+        assert_eq!(result.commit.hash, "c6");
+        assert_eq!(result.previous_commit.hash, "c5");
+        assert_eq!(result.steps.len(), probed.len());
+        assert!(probed.len() <= 3);
+    }
+
+    #[test]
+    fn test_cycle_target_and_invalid_range() {
+        let target = BisectTarget::Cycle {
+            members: vec!["a.dart".to_string(), "b.dart".to_string()],
+        };
+        let cyclic = analysis_result(vec![dep("a.dart", "b.dart"), dep("b.dart", "c.dart"), dep("c.dart", "a.dart")]);
+        assert!(target.is_present(&cyclic));
+        assert!(!target.is_present(&analysis_result(vec![dep("a.dart", "b.dart")])));
+
+        let commits = vec![commit("c0", "alice", 0), commit("c1", "alice", 1)];
+        assert!(bisect_commits(&target, &commits, true, true, |_| Ok(true)).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_view::normalize_path;
+use crate::file_history::project_relative;
+use crate::git_navigator::{CommitChanges, FileChangeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Thresholds for mining logical coupling from co-change history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CouplingConfig {
+    /// Minimum number of commits changing both files
+    pub min_support: usize,
+    /// Minimum share of one file's commits that also change the other
+    pub min_confidence: f64,
+    /// Commits touching more files of the project than this (mass renames,
+    /// formatting) are ignored, whether or not the files are analyzed
+    pub max_files_per_commit: usize,
+    /// Only consider files known to the analyzer in the latest snapshot
+    pub analyzed_files_only: bool,
+}
+
+impl Default for CouplingConfig {
+    fn default() -> Self {
+        Self {
+            min_support: 3,
+            min_confidence: 0.5,
+            max_files_per_commit: 30,
+            analyzed_files_only: true,
+        }
+    }
+}
+
+/// Two files that frequently change in the same commits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogicalCoupling {
+    pub file_a: String,
+    pub file_b: String,
+    /// Commits changing both files
+    pub support: usize,
+    /// P(b changes | a changes)
+    pub confidence_a_to_b: f64,
+    /// P(a changes | b changes)
+    pub confidence_b_to_a: f64,
+    /// Whether the static graph has an import in either direction
+    pub has_static_dependency: bool,
+}
+
+impl LogicalCoupling {
+    pub fn confidence(&self) -> f64 {
+        self.confidence_a_to_b.max(self.confidence_b_to_a)
+    }
+}
+
+/// A static import whose endpoints never change together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadCoupling {
+    pub source_file: String,
+    pub target_file: String,
+    pub source_changes: usize,
+    pub target_changes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CouplingReport {
+    pub config: CouplingConfig,
+    pub commits_considered: usize,
+    /// Commits skipped for exceeding `max_files_per_commit`
+    pub commits_skipped: usize,
+    /// Pairs above the thresholds, strongest first
+    pub couplings: Vec<LogicalCoupling>,
+    /// Co-changing pairs without an import between them
+    pub hidden_couplings: Vec<LogicalCoupling>,
+    /// Imports whose files changed but never in the same commit
+    pub dead_couplings: Vec<DeadCoupling>,
+}
+
+/// Mine co-change coupling from a first-parent history (oldest first) and compare
+/// it with the static graph of `snapshot`. Files are tracked through renames
+/// under their latest name.
+pub fn analyze_change_coupling(
+    history: &[CommitChanges],
+    subfolder: Option<&str>,
+    snapshot: Option<&CommitSnapshot>,
+    config: &CouplingConfig,
+) -> CouplingReport {
+    let latest_names = latest_names(history, subfolder);
+    let resolve = |path: String| latest_names.get(&path).cloned().unwrap_or(path);
+
+    let known_files: Option<HashSet<String>> = match (config.analyzed_files_only, snapshot) {
+        (true, Some(snapshot)) => Some(
+            snapshot
+                .analysis_result
+                .analyzed_files
+                .iter()
+                .map(|p| normalize_path(p))
+                .collect(),
+        ),
+        _ => None,
+    };
+
+    let mut change_counts: HashMap<String, usize> = HashMap::new();
+    let mut pair_counts: HashMap<(String, String), usize> = HashMap::new();
+    let mut commits_considered = 0;
+    let mut commits_skipped = 0;
+
+    for entry in history {
+        // Commit size counts every file touched in the project, analyzed or not
+        let touched: Vec<(FileChangeKind, String)> = entry
+            .changes
+            .iter()
+            .filter_map(|c| project_relative(&c.path, subfolder).map(|path| (c.kind, path)))
+            .collect();
+        if touched.len() > config.max_files_per_commit {
+            commits_skipped += 1;
+            continue;
+        }
+
+        let mut files: Vec<String> = touched
+            .into_iter()
+            .filter(|(kind, _)| *kind != FileChangeKind::Deleted)
+            .map(|(_, path)| resolve(path))
+            .filter(|f| known_files.as_ref().is_none_or(|known| known.contains(f)))
+            .collect();
+        files.sort();
+        files.dedup();
+
+        if files.is_empty() {
+            continue;
+        }
+        commits_considered += 1;
+
+        for (i, file) in files.iter().enumerate() {
+            *change_counts.entry(file.clone()).or_default() += 1;
+            for other in &files[i + 1..] {
+                *pair_counts.entry((file.clone(), other.clone())).or_default() += 1;
+            }
+        }
+    }
+
+    let static_edges: BTreeSet<(String, String)> = snapshot
+        .map(|s| {
+            s.analysis_result
+                .dependencies
+                .iter()
+                .map(|d| (normalize_path(&d.source_file), normalize_path(&d.target_file)))
+                .collect()
+        })
+        .unwrap_or_default();
+    let has_edge = |a: &str, b: &str| {
+        static_edges.contains(&(a.to_string(), b.to_string()))
+            || static_edges.contains(&(b.to_string(), a.to_string()))
+    };
+
+    let mut couplings: Vec<LogicalCoupling> = pair_counts
+        .iter()
+        .filter(|(_, &support)| support >= config.min_support)
+        .map(|((a, b), &support)| LogicalCoupling {
+            file_a: a.clone(),
+            file_b: b.clone(),
+            support,
+            confidence_a_to_b: support as f64 / change_counts[a] as f64,
+            confidence_b_to_a: support as f64 / change_counts[b] as f64,
+            has_static_dependency: has_edge(a, b),
+        })
+        .filter(|c| c.confidence() >= config.min_confidence)
+        .collect();
+    couplings.sort_by(|x, y| {
+        y.confidence()
+            .partial_cmp(&x.confidence())
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| y.support.cmp(&x.support))
+            .then_with(|| (&x.file_a, &x.file_b).cmp(&(&y.file_a, &y.file_b)))
+    });
+
+    let hidden_couplings = couplings
+        .iter()
+        .filter(|c| !c.has_static_dependency)
+        .cloned()
+        .collect();
+
+    let dead_couplings = static_edges
+        .iter()
+        .filter(|(source, target)| source != target)
+        .filter_map(|(source, target)| {
+            let source_changes = *change_counts.get(source)?;
+            let target_changes = *change_counts.get(target)?;
+            let key = if source < target {
+                (source.clone(), target.clone())
+            } else {
+                (target.clone(), source.clone())
+            };
+            if pair_counts.contains_key(&key) {
+                return None;
+            }
+            Some(DeadCoupling {
+                source_file: source.clone(),
+                target_file: target.clone(),
+                source_changes,
+                target_changes,
+            })
+        })
+        .collect();
+
+    CouplingReport {
+        config: config.clone(),
+        commits_considered,
+        commits_skipped,
+        couplings,
+        hidden_couplings,
+        dead_couplings,
+    }
+}
+
+/// Map every project-relative path that was later renamed to its final name
+fn latest_names(history: &[CommitChanges], subfolder: Option<&str>) -> HashMap<String, String> {
+    let mut renamed_to: HashMap<String, String> = HashMap::new();
+    for change in history.iter().flat_map(|entry| &entry.changes) {
+        if change.kind != FileChangeKind::Renamed {
+            continue;
+        }
+        let old_path = change.old_path.as_deref().and_then(|p| project_relative(p, subfolder));
+        if let (Some(old_path), Some(new_path)) = (old_path, project_relative(&change.path, subfolder)) {
+            renamed_to.insert(old_path, new_path);
+        }
+    }
+
+    renamed_to
+        .keys()
+        .map(|path| {
+            let mut current = path;
+            let mut seen: HashSet<&String> = HashSet::from([path]);
+            while let Some(next) = renamed_to.get(current) {
+                if !seen.insert(next) {
+                    break;
+                }
+                current = next;
+            }
+            (path.clone(), current.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{commit_changes, dep, modified, renamed, snapshot};
+
+    #[test]
+    fn test_hidden_and_dead_coupling() {
+        let touched = |hash: &str, files: &[&str]| {
+            commit_changes(hash, "alice", 0, files.iter().map(|f| modified(f, 1)).collect())
+        };
+        let history = vec![
+            // a.dart was called old_a.dart in the first commit
+            touched("c1", &["app/old_a.dart", "app/b.dart", "app/c.dart"]),
+            commit_changes("r1", "alice", 0, vec![renamed("app/old_a.dart", "app/a.dart")]),
+            touched("c2", &["app/a.dart", "app/b.dart"]),
+            touched("c3", &["app/a.dart", "app/b.dart", "README.md"]),
+            touched("c4", &["app/c.dart"]),
+            touched("c5", &["app/d.dart"]),
+            touched("c6", &["app/a.dart", "app/b.dart", "app/c.dart", "app/d.dart"]),
+            // Only two of the files are analyzed, but the commit is still too large
+            touched("c7", &["app/a.dart", "app/b.dart", "app/gen/a.g.dart", "app/gen/b.g.dart"]),
+        ];
+
+        let latest = snapshot("c6", 0, vec![dep("a.dart", "c.dart"), dep("c.dart", "d.dart"), dep("b.dart", "d.dart")]);
+        let config = CouplingConfig {
+            max_files_per_commit: 3,
+            ..CouplingConfig::default()
+        };
+        let report = analyze_change_coupling(&history, Some("app"), Some(&latest), &config);
+
+        assert_eq!(report.commits_skipped, 2);
+        assert_eq!(report.couplings.len(), 1);
+        let coupling = &report.couplings[0];
+        assert_eq!((coupling.file_a.as_str(), coupling.file_b.as_str()), ("a.dart", "b.dart"));
+        assert_eq!(coupling.support, 3);
+        assert_eq!(coupling.confidence(), 1.0);
+        assert_eq!(report.hidden_couplings.len(), 1);
+
+        let dead: Vec<(&str, &str)> = report
+            .dead_couplings
+            .iter()
+            .map(|d| (d.source_file.as_str(), d.target_file.as_str()))
+            .collect();
+        assert_eq!(dead, vec![("b.dart", "d.dart"), ("c.dart", "d.dart")]);
+    }
+}
@@ -0,0 +1,1547 @@
+use crate::git_navigator::{GitTemporalNavigator, CommitChanges, CommitInfo, RepoCloneInfo};
+use crate::dependency_analyzer::{AnalyzerRegistry, DependencyAnalyzer, AnalysisConfig, AnalysisIssue, AnalysisResult};
+use crate::lakos_analyzer::LakosAnalyzer;
+use crate::analysis_cache::{AnalysisCache, AnalysisCacheKey, CacheStatistics};
+use crate::snapshot_diff::{self, SnapshotDiff};
+use crate::dependency_timeline::DependencyTimeline;
+use crate::file_history::FileHistory;
+use crate::change_coupling::{self, CouplingConfig, CouplingReport};
+use crate::hotspots::{self, HotspotReport};
+use crate::impact::{self, ImpactReport};
+use crate::cycle_tracker::{self, TrackedCycle};
+use crate::bisect::{self, BisectResult, BisectTarget};
+use crate::metric_series::{self, MetricTimeSeries};
+use crate::regressions::{self, RegressionEvent, RegressionThresholds};
+use crate::quality_score::{self, QualityScoreBreakdown, QualityScoreModel};
+use crate::package_metrics::{self, PackageMetrics};
+use crate::modularity::{self, ModularityReport};
+use crate::ownership::{self, CodeOwners, OwnershipReport};
+use crate::architecture_rules::{self, RuleSet, RuleViolationCount};
+use crate::baseline::{self, Baseline, BaselineComparison};
+use crate::node_identity::{self, NodeIdentityMap, NodeMetricPoint};
+use crate::models::{FileChangeSet, TemporalMetadata};
+use std::path::PathBuf;
+use anyhow::{Result, Context};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Numbers the scratch clones of bisect jobs
+static BISECT_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// Complete snapshot of dependencies at a specific commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitSnapshot {
+    pub commit_info: CommitInfo,
+    pub analysis_result: AnalysisResult,
+    pub project_path: PathBuf,
+    /// Regressions introduced since the previous snapshot
+    #[serde(default)]
+    pub regressions: Vec<RegressionEvent>,
+}
+
+/// Structural metrics and git lifecycle of one graph node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDetails {
+    pub path: String,
+    pub stable_id: Option<String>,
+    pub commit_hash: String,
+    pub metrics: Option<crate::dependency_analyzer::NodeMetrics>,
+    pub temporal_data: Option<TemporalMetadata>,
+}
+
+/// Progress information for long-running analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisProgress {
+    pub phase: AnalysisPhase,
+    pub current_commit: usize,
+    pub total_commits: usize,
+    pub current_commit_hash: String,
+    pub message: String,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AnalysisPhase {
+    Cloning,
+    BuildingCommitSequence,
+    AnalyzingCommits,
+    Completed,
+    Failed(String),
+}
+
+/// Configuration for ChronoGraph analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChronoGraphConfig {
+    pub github_url: String,
+    pub local_base_dir: PathBuf,
+    pub analyzer_name: String,
+    pub analysis_config: AnalysisConfig,
+    /// Sample every N commits (1 = every commit, 2 = every other commit, etc.)
+    pub commit_sampling: usize,
+    /// Maximum number of commits to analyze (for performance)
+    pub max_commits: Option<usize>,
+    /// Whether to cleanup local repo after analysis
+    pub cleanup_after_analysis: bool,
+    /// Optional subfolder to analyze (e.g., "samples/web/gallery")
+    pub subfolder: Option<String>,
+    /// Whether the github_url is actually a local path
+    pub is_local_repository: bool,
+    /// Branch or tag to analyze instead of the default branch
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// Limits for architecture regression alerts; when unset they are read from
+    /// the repository's thresholds file, or the defaults are used
+    #[serde(default)]
+    pub regression_thresholds: Option<RegressionThresholds>,
+    /// Weights and thresholds of the architecture quality score; when unset it is
+    /// read from the repository's quality model file, or the default preset is used
+    #[serde(default)]
+    pub quality_model: Option<QualityScoreModel>,
+    /// Architecture rules; when unset they are read from the repository's rules file
+    #[serde(default)]
+    pub architecture_rules: Option<RuleSet>,
+    /// Tighten the baseline automatically when the latest snapshot improves on it
+    #[serde(default)]
+    pub ratchet_baseline: bool,
+    /// Baseline file to read and write instead of the repository's baseline file
+    #[serde(default)]
+    pub baseline_path: Option<PathBuf>,
+}
+
+impl Default for ChronoGraphConfig {
+    fn default() -> Self {
+        // Use platform-specific temporary directory
+        let temp_dir = std::env::temp_dir().join("chronograph");
+        
+        Self {
+            github_url: String::new(),
+            local_base_dir: temp_dir,
+            analyzer_name: "lakos".to_string(),
+            analysis_config: AnalysisConfig::default(),
+            commit_sampling: 5, // Every 5th commit for performance
+            max_commits: Some(100), // Limit for initial testing
+            cleanup_after_analysis: true,
+            subfolder: None,
+            is_local_repository: false,
+            git_ref: None,
+            regression_thresholds: None,
+            quality_model: None,
+            architecture_rules: None,
+            ratchet_baseline: false,
+            baseline_path: None,
+        }
+    }
+}
+
+impl ChronoGraphConfig {
+    /// Where the baseline is written: the configured path, else the repository's
+    /// baseline file in the working tree of a local repository, to be committed.
+    /// Remote repositories are analyzed from a clone and need a configured path.
+    pub fn baseline_target(&self) -> Result<PathBuf> {
+        match self.baseline_path {
+            Some(ref path) => Ok(path.clone()),
+            None if self.is_local_repository => Ok(PathBuf::from(&self.github_url).join(baseline::BASELINE_FILE)),
+            None => anyhow::bail!(
+                "A remote repository has no working tree for {}; configure a baseline path",
+                baseline::BASELINE_FILE
+            ),
+        }
+    }
+
+    /// Reject settings that cannot work before any analysis starts
+    pub fn validate(&self) -> Result<()> {
+        if let Some(ref rules) = self.architecture_rules {
+            rules.validate().context("Invalid architecture rules")?;
+        }
+        if self.ratchet_baseline {
+            self.baseline_target().context("Cannot ratchet the baseline")?;
+        }
+        Ok(())
+    }
+}
+
+/// Main ChronoGraph analysis engine
+pub struct ChronoGraphEngine {
+    config: ChronoGraphConfig,
+    git_navigator: Option<GitTemporalNavigator>,
+    analyzer_registry: AnalyzerRegistry,
+    snapshots: Vec<CommitSnapshot>,
+    dependency_timeline: DependencyTimeline,
+    /// Full first-parent history of the analyzed branch, oldest first
+    change_history: Vec<CommitChanges>,
+    file_history: FileHistory,
+    /// Project-relative changes between consecutive snapshots
+    snapshot_changes: Vec<FileChangeSet>,
+    node_identities: NodeIdentityMap,
+    cycle_history: Vec<TrackedCycle>,
+    /// CODEOWNERS at the head of the analyzed branch, if the repository has one
+    codeowners: Option<CodeOwners>,
+    /// Rules in effect, from the configuration or the repository
+    rules: RuleSet,
+    /// Regression thresholds in effect, from the configuration or the repository
+    regression_thresholds: RegressionThresholds,
+    /// Quality model in effect, from the configuration or the repository
+    quality_model: QualityScoreModel,
+    /// Accepted state that later analyses are compared against
+    baseline: Option<Baseline>,
+    cache: Option<AnalysisCache>,
+}
+
+impl ChronoGraphEngine {
+    pub fn new(config: ChronoGraphConfig) -> Self {
+        let registry = Self::default_registry();
+
+        // Initialize cache
+        let cache = Self::initialize_cache(&config).ok();
+        if cache.is_none() {
+            log::warn!("Failed to initialize analysis cache, running without cache");
+        }
+
+        Self {
+            config,
+            git_navigator: None,
+            analyzer_registry: registry,
+            snapshots: Vec::new(),
+            dependency_timeline: DependencyTimeline::default(),
+            change_history: Vec::new(),
+            file_history: FileHistory::default(),
+            snapshot_changes: Vec::new(),
+            node_identities: NodeIdentityMap::default(),
+            cycle_history: Vec::new(),
+            codeowners: None,
+            rules: RuleSet::default(),
+            regression_thresholds: RegressionThresholds::default(),
+            quality_model: QualityScoreModel::default(),
+            baseline: None,
+            cache,
+        }
+    }
+
+    fn default_registry() -> AnalyzerRegistry {
+        let mut registry = AnalyzerRegistry::new();
+
+        // Register Lakos analyzer by default
+        registry.register(Box::new(LakosAnalyzer::new()));
+        registry
+    }
+
+    /// Initialize the analysis cache
+    fn initialize_cache(config: &ChronoGraphConfig) -> Result<AnalysisCache> {
+        // Get user cache directory or fallback to temp
+        let cache_dir = if let Some(cache_dir) = dirs::cache_dir() {
+            cache_dir.join("chronograph")
+        } else {
+            config.local_base_dir.join(".cache")
+        };
+
+        AnalysisCache::new(cache_dir)
+            .context("Failed to initialize analysis cache")
+    }
+    
+    /// Start the complete analysis process
+    pub fn analyze_repository<F>(&mut self, progress_callback: F) -> Result<Vec<CommitSnapshot>>
+    where
+        F: Fn(AnalysisProgress),
+    {
+        self.config.validate()?;
+
+        progress_callback(AnalysisProgress {
+            phase: AnalysisPhase::Cloning,
+            current_commit: 0,
+            total_commits: 0,
+            current_commit_hash: String::new(),
+            message: format!("Cloning repository: {}", self.config.github_url),
+            percentage: 0.0,
+        });
+        
+        // Step 1: Clone repository and build commit sequence
+        let mut git_navigator = self.clone_and_setup()
+            .context("Failed to clone repository")?;
+        self.quality_model = match self.config.quality_model {
+            Some(ref model) => model.clone(),
+            None => Self::load_repository_quality_model(&git_navigator),
+        };
+        self.rules = match self.config.architecture_rules {
+            Some(ref rules) => rules.clone(),
+            None => {
+                let rules: RuleSet = Self::load_repository_file(&git_navigator, architecture_rules::RULES_FILE);
+                rules.validate().with_context(|| format!("Invalid {}", architecture_rules::RULES_FILE))?;
+                rules
+            }
+        };
+            
+        let merge_sequence = git_navigator.get_merge_sequence().to_vec();
+        let total_commits = merge_sequence.len();
+        
+        progress_callback(AnalysisProgress {
+            phase: AnalysisPhase::BuildingCommitSequence,
+            current_commit: 0,
+            total_commits,
+            current_commit_hash: String::new(),
+            message: format!("Found {} commits in merge sequence", total_commits),
+            percentage: 10.0,
+        });
+        
+        // Step 1.5: Validate subfolder exists (if specified)
+        if let Some(ref subfolder) = self.config.subfolder {
+            self.validate_subfolder_exists(&git_navigator, subfolder)
+                .context("Subfolder validation failed")?;
+        }
+        
+        // Step 2: Sample commits if needed
+        let commits_to_analyze = self.sample_commits(&merge_sequence);
+        let analysis_count = commits_to_analyze.len();
+        
+        progress_callback(AnalysisProgress {
+            phase: AnalysisPhase::AnalyzingCommits,
+            current_commit: 0,
+            total_commits: analysis_count,
+            current_commit_hash: String::new(),
+            message: format!("Analyzing {} commits", analysis_count),
+            percentage: 15.0,
+        });
+        
+        // Step 3: Analyze each commit
+        let mut snapshots = Vec::new();
+        
+        for (index, commit_info) in commits_to_analyze.iter().enumerate() {
+            progress_callback(AnalysisProgress {
+                phase: AnalysisPhase::AnalyzingCommits,
+                current_commit: index + 1,
+                total_commits: analysis_count,
+                current_commit_hash: commit_info.hash.clone(),
+                message: format!("Analyzing commit {}: {}", 
+                               &commit_info.hash[..8], 
+                               commit_info.message.split('\n').next().unwrap_or("")),
+                percentage: 15.0 + (index as f64 / analysis_count as f64) * 80.0,
+            });
+            
+            match self.analyze_commit(&mut git_navigator, commit_info) {
+                Ok(snapshot) => {
+                    snapshots.push(snapshot);
+                }
+                Err(e) => {
+                    let error_string = e.to_string();
+                    let error_msg = format!("{}", error_string);
+                    log::warn!("Error analyzing commit {}: {}", &commit_info.hash[..8], error_msg);
+
+                    // Check if this is a missing project files error
+                    let is_missing_project_files = error_string.contains("Cannot analyze project") ||
+                                                   error_string.contains("Required project files not found");
+                    
+                    // Check if this is truly a critical infrastructure error
+                    let is_infrastructure_error = error_string.contains("Failed to checkout commit") ||
+                                                 error_string.contains("Directory listing failed");
+
+                    // Only fail immediately for infrastructure errors (git/filesystem problems)
+                    // For missing project files, we'll check at the end if we got ANY successful analyses
+                    if is_infrastructure_error {
+                        // Send failed progress update before returning
+                        progress_callback(AnalysisProgress {
+                            phase: AnalysisPhase::Failed(error_msg.clone()),
+                            current_commit: index + 1,
+                            total_commits: analysis_count,
+                            current_commit_hash: commit_info.hash.clone(),
+                            message: error_msg.clone(),
+                            percentage: 15.0 + (index as f64 / analysis_count as f64) * 80.0,
+                        });
+                        return Err(anyhow::anyhow!("{}", error_msg));
+                    }
+
+                    // For missing project files and other errors, continue with warning
+                    if is_missing_project_files {
+                        log::info!("Skipping commit {} (project files not found yet) and continuing...", &commit_info.hash[..8]);
+                    } else {
+                        log::info!("Skipping commit {} and continuing with next commit...", &commit_info.hash[..8]);
+                    }
+                }
+            }
+        }
+        
+        // Check if we got at least some successful analyses
+        if snapshots.is_empty() {
+            let error_msg = format!(
+                "Failed to analyze any commits. This could mean:\n\
+                 1. The repository doesn't contain a Flutter/Dart project (no pubspec.yaml found)\n\
+                 2. The project is in a subfolder - please specify the subfolder path in settings\n\
+                 3. The project was added in later commits - try analyzing more commits"
+            );
+            
+            progress_callback(AnalysisProgress {
+                phase: AnalysisPhase::Failed(error_msg.clone()),
+                current_commit: analysis_count,
+                total_commits: analysis_count,
+                current_commit_hash: String::new(),
+                message: error_msg.clone(),
+                percentage: 100.0,
+            });
+            
+            return Err(anyhow::anyhow!("{}", error_msg));
+        }
+        
+        // Store results
+        self.snapshots = snapshots.clone();
+        self.dependency_timeline = DependencyTimeline::build(&self.snapshots);
+        self.change_history = git_navigator
+            .first_parent_history(self.config.subfolder.as_deref(), self.cache.as_mut())
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to read file history: {}", e);
+                Vec::new()
+            });
+        self.file_history = FileHistory::build(&self.change_history, self.config.subfolder.as_deref());
+        self.snapshot_changes = self.collect_snapshot_changes(&git_navigator);
+        self.node_identities = NodeIdentityMap::build(&self.snapshots, &self.snapshot_changes);
+        self.cycle_history = cycle_tracker::track_cycles(&self.snapshots, &self.node_identities);
+        self.regression_thresholds = match self.config.regression_thresholds {
+            Some(ref thresholds) => thresholds.clone(),
+            None => Self::load_repository_file(&git_navigator, regressions::THRESHOLDS_FILE),
+        };
+        self.detect_regressions();
+        self.codeowners = ownership::CODEOWNERS_LOCATIONS
+            .iter()
+            .find_map(|location| git_navigator.read_head_file(location).ok().flatten())
+            .map(|content| CodeOwners::parse(&content));
+        self.evaluate_rules();
+        self.baseline = self.load_baseline(&git_navigator);
+        if self.config.ratchet_baseline {
+            self.ratchet_baseline();
+        }
+        self.git_navigator = Some(git_navigator);
+        
+        let success_rate = (snapshots.len() as f64 / analysis_count as f64 * 100.0) as usize;
+        let message = if snapshots.len() < analysis_count {
+            format!("Analysis completed. {} of {} commits analyzed successfully ({}% success rate). {} commits skipped due to missing project files.", 
+                   snapshots.len(), analysis_count, success_rate, analysis_count - snapshots.len())
+        } else {
+            format!("Analysis completed. {} snapshots generated.", snapshots.len())
+        };
+        
+        progress_callback(AnalysisProgress {
+            phase: AnalysisPhase::Completed,
+            current_commit: analysis_count,
+            total_commits: analysis_count,
+            current_commit_hash: String::new(),
+            message,
+            percentage: 100.0,
+        });
+        
+        Ok(self.snapshots.clone())
+    }
+    
+    /// Clone repository and set up git navigator
+    fn clone_and_setup(&mut self) -> Result<GitTemporalNavigator> {
+        // Ensure local directory exists
+        std::fs::create_dir_all(&self.config.local_base_dir)?;
+
+        // Clean up old timestamped repositories before cloning
+        let _ = GitTemporalNavigator::cleanup_old_repos(&self.config.local_base_dir);
+
+        let mut git_navigator = if self.config.is_local_repository {
+            // For local repositories, clone to a temp location to avoid modifying user's working directory
+            GitTemporalNavigator::clone_local_repository(
+                &self.config.github_url,
+                &self.config.local_base_dir
+            )?
+        } else {
+            // For remote repositories, clone normally
+            GitTemporalNavigator::clone_repository(
+                &self.config.github_url,
+                &self.config.local_base_dir
+            )?
+        };
+
+        if let Some(ref git_ref) = self.config.git_ref {
+            git_navigator.use_ref(git_ref)?;
+        }
+
+        // If we have a subfolder, rebuild merge sequence with filtering (normalize path separators)
+        if let Some(ref subfolder) = self.config.subfolder {
+            let normalized_subfolder = subfolder.replace('\\', "/");
+            log::debug!("Rebuilding merge sequence with subfolder filter: {} -> {}", subfolder, normalized_subfolder);
+            git_navigator.build_merge_sequence_with_subfolder(Some(&normalized_subfolder))?;
+        }
+
+        Ok(git_navigator)
+    }
+    
+    /// Validate that the specified subfolder exists in the latest commit
+    fn validate_subfolder_exists(&self, git_navigator: &GitTemporalNavigator, subfolder: &str) -> Result<()> {
+        // Normalize path separators - convert backslashes to forward slashes
+        let normalized_subfolder = subfolder.replace('\\', "/");
+        let base_path = git_navigator.local_path();
+        let subfolder_path = base_path.join(&normalized_subfolder);
+        
+        log::debug!("Validating subfolder: '{}' -> normalized: '{}' at path: {}", 
+                 subfolder, normalized_subfolder, subfolder_path.display());
+        
+        if !subfolder_path.exists() {
+            // Check if it's a case sensitivity issue or suggest alternatives
+            let mut suggestions = Vec::new();
+            
+            // Try to find similar folders
+            if let Some(parent) = subfolder_path.parent() {
+                if parent.exists() {
+                    if let Ok(entries) = std::fs::read_dir(parent) {
+                        for entry in entries.flatten() {
+                            if entry.path().is_dir() {
+                                if let Some(dir_name) = entry.file_name().to_str() {
+                                    let similarity_score = self.string_similarity(&normalized_subfolder, dir_name);
+                                    if similarity_score > 0.6 {
+                                        suggestions.push(dir_name.to_string());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            
+            let mut error_msg = if subfolder != normalized_subfolder {
+                format!(
+                    "Subfolder '{}' (normalized to '{}') does not exist in the repository.\n\nRepository path: {}", 
+                    subfolder, normalized_subfolder, base_path.display()
+                )
+            } else {
+                format!(
+                    "Subfolder '{}' does not exist in the repository.\n\nRepository path: {}", 
+                    subfolder, base_path.display()
+                )
+            };
+            
+            if !suggestions.is_empty() {
+                error_msg.push_str("\n\nDid you mean one of these?");
+                for suggestion in suggestions.iter().take(5) {
+                    error_msg.push_str(&format!("\n  - {}", suggestion));
+                }
+            } else {
+                // List available top-level directories
+                if let Ok(entries) = std::fs::read_dir(base_path) {
+                    let mut dirs: Vec<String> = entries
+                        .flatten()
+                        .filter(|e| e.path().is_dir())
+                        .filter_map(|e| e.file_name().to_str().map(String::from))
+                        .collect();
+                    
+                    if !dirs.is_empty() {
+                        dirs.sort();
+                        error_msg.push_str("\n\nAvailable directories:");
+                        for dir in dirs.iter().take(10) {
+                            error_msg.push_str(&format!("\n  - {}", dir));
+                        }
+                        if dirs.len() > 10 {
+                            error_msg.push_str(&format!("\n  ... and {} more", dirs.len() - 10));
+                        }
+                    }
+                }
+            }
+            
+            anyhow::bail!(error_msg);
+        }
+        
+        // Check if it's actually a Flutter/Dart project
+        let pubspec_path = subfolder_path.join("pubspec.yaml");
+        if !pubspec_path.exists() {
+            log::warn!(
+                "'{}' doesn't contain pubspec.yaml. This might not be a Flutter/Dart project, but analysis will continue.",
+                subfolder
+            );
+        }
+        
+        log::info!("Subfolder '{}' validated successfully", subfolder);
+        Ok(())
+    }
+    
+    /// Simple string similarity calculation (Jaro-Winkler-like)
+    fn string_similarity(&self, s1: &str, s2: &str) -> f64 {
+        let s1_lower = s1.to_lowercase();
+        let s2_lower = s2.to_lowercase();
+        
+        if s1_lower == s2_lower {
+            return 1.0;
+        }
+        
+        // Simple similarity: count common characters divided by max length
+        let max_len = s1_lower.len().max(s2_lower.len()) as f64;
+        let mut common_chars = 0;
+        
+        for c1 in s1_lower.chars() {
+            if s2_lower.contains(c1) {
+                common_chars += 1;
+            }
+        }
+        
+        common_chars as f64 / max_len
+    }
+    
+    /// Sample commits based on configuration
+    fn sample_commits(&self, merge_sequence: &[CommitInfo]) -> Vec<CommitInfo> {
+        let mut sampled = Vec::new();
+        
+        for (index, commit) in merge_sequence.iter().enumerate() {
+            // Always include first and last commits
+            if index == 0 || index == merge_sequence.len() - 1 {
+                sampled.push(commit.clone());
+                continue;
+            }
+            
+            // Sample based on sampling rate
+            if index % self.config.commit_sampling == 0 {
+                sampled.push(commit.clone());
+            }
+        }
+        
+        // Apply max commits limit
+        if let Some(max_commits) = self.config.max_commits {
+            if sampled.len() > max_commits {
+                // Take evenly distributed samples
+                let step = sampled.len() / max_commits;
+                let mut limited = Vec::new();
+                
+                for i in 0..max_commits {
+                    if i * step < sampled.len() {
+                        limited.push(sampled[i * step].clone());
+                    }
+                }
+                
+                // Always include the last commit
+                if let Some(last) = sampled.last() {
+                    if !limited.iter().any(|c| c.hash == last.hash) {
+                        limited.push(last.clone());
+                    }
+                }
+                
+                sampled = limited;
+            }
+        }
+        
+        sampled
+    }
+    
+    /// Analyze dependencies at a specific commit
+    fn analyze_commit(
+        &mut self,
+        git_navigator: &mut GitTemporalNavigator,
+        commit_info: &CommitInfo
+    ) -> Result<CommitSnapshot> {
+        analyze_commit(
+            &self.config,
+            &self.analyzer_registry,
+            &self.quality_model,
+            self.cache.as_mut(),
+            git_navigator,
+            commit_info,
+        )
+    }
+    
+    /// Find the exact commit between two analyzed snapshots where a dependency or
+    /// cycle appeared (or disappeared), analyzing unsampled commits as needed.
+    /// Analyses go through the cache, so repeated bisects are cheap.
+    pub fn bisect(&self, target: &BisectTarget, from_hash: &str, to_hash: &str) -> Result<BisectResult> {
+        self.prepare_bisect(target.clone(), from_hash, to_hash)?.run()
+    }
+
+    /// Set up a bisect that runs on its own clone of the repository and cache
+    /// connection, so the engine stays available while commits are checked out and
+    /// analyzed, and concurrent bisects, analyses and cleanups do not interfere.
+    /// The clone is removed when the job is dropped.
+    pub fn prepare_bisect(&self, target: BisectTarget, from_hash: &str, to_hash: &str) -> Result<BisectJob> {
+        let navigator = self.git_navigator.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No repository available for bisecting"))?;
+        let from = self.find_snapshot(from_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", from_hash))?;
+        let to = self.find_snapshot(to_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", to_hash))?;
+
+        let sequence = navigator.get_merge_sequence();
+        let position = |hash: &str| sequence.iter().position(|c| c.hash == hash);
+        let (start, end) = match (position(&from.commit_info.hash), position(&to.commit_info.hash)) {
+            (Some(start), Some(end)) if start < end => (start, end),
+            (Some(_), Some(_)) => anyhow::bail!("Commit {} is not older than {}", from_hash, to_hash),
+            _ => anyhow::bail!("Snapshots are not part of the current merge sequence"),
+        };
+
+        let scratch_path = self.config.local_base_dir.join("bisect").join(format!(
+            "{}-{}",
+            std::process::id(),
+            BISECT_JOBS.fetch_add(1, Ordering::Relaxed)
+        ));
+        Ok(BisectJob {
+            config: self.config.clone(),
+            analyzer_registry: Self::default_registry(),
+            quality_model: self.quality_model.clone(),
+            cache: Self::initialize_cache(&self.config).ok(),
+            git_navigator: Some(navigator.clone_into(&scratch_path)?),
+            present_at_start: target.is_present(&from.analysis_result),
+            present_at_end: target.is_present(&to.analysis_result),
+            target,
+            candidates: sequence[start..=end].to_vec(),
+        })
+    }
+
+    /// Get analysis results
+    pub fn get_snapshots(&self) -> &[CommitSnapshot] {
+        &self.snapshots
+    }
+    
+    /// Lifecycle of every dependency across the analyzed snapshots
+    pub fn get_dependency_timeline(&self) -> &DependencyTimeline {
+        &self.dependency_timeline
+    }
+
+    /// Per-file change history over the full first-parent history
+    pub fn get_file_history(&self) -> &FileHistory {
+        &self.file_history
+    }
+
+    /// Metrics and lifecycle of a file or folder as of an analyzed commit (latest by default)
+    pub fn node_details(&self, node_path: &str, commit_hash: Option<&str>) -> Result<NodeDetails> {
+        let snapshot = match commit_hash {
+            Some(hash) => self.find_snapshot(hash)
+                .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", hash))?,
+            None => self.snapshots.last()
+                .ok_or_else(|| anyhow::anyhow!("No analysis available"))?,
+        };
+
+        let path = crate::dependency_view::normalize_path(node_path.as_ref());
+        let metrics = crate::graph_algorithms::node_metrics_or_derived(&snapshot.analysis_result)
+            .remove(&path);
+        let temporal_data = self.file_history.node_metadata(&path, Some(snapshot.commit_info.timestamp));
+
+        let stable_id = self.node_identities
+            .stable_id(&snapshot.commit_info.hash, &path)
+            .map(str::to_string);
+
+        Ok(NodeDetails {
+            path,
+            stable_id,
+            commit_hash: snapshot.commit_info.hash.clone(),
+            metrics,
+            temporal_data,
+        })
+    }
+
+    /// Logical coupling mined from co-changes, compared against the latest static graph
+    pub fn change_coupling(&self, config: &CouplingConfig) -> CouplingReport {
+        change_coupling::analyze_change_coupling(
+            &self.change_history,
+            self.config.subfolder.as_deref(),
+            self.snapshots.last(),
+            config,
+        )
+    }
+
+    /// Hotspot ranking of one analyzed commit (latest by default)
+    pub fn hotspots(&self, commit_hash: Option<&str>, limit: Option<usize>) -> Result<HotspotReport> {
+        let snapshot = match commit_hash {
+            Some(hash) => self.find_snapshot(hash)
+                .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", hash))?,
+            None => self.snapshots.last()
+                .ok_or_else(|| anyhow::anyhow!("No analysis available"))?,
+        };
+        Ok(hotspots::hotspots(snapshot, &self.file_history, limit))
+    }
+
+    /// Files that transitively import any of `paths` at an analyzed commit (latest by default)
+    pub fn impact(&self, commit_hash: Option<&str>, paths: &[String]) -> Result<ImpactReport> {
+        let snapshot = match commit_hash {
+            Some(hash) => self.find_snapshot(hash)
+                .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", hash))?,
+            None => self.snapshots.last()
+                .ok_or_else(|| anyhow::anyhow!("No analysis available"))?,
+        };
+        Ok(impact::impact(snapshot, paths))
+    }
+
+    /// Hotspot rankings for every analyzed commit, oldest first
+    pub fn hotspot_timeline(&self, limit: Option<usize>) -> Vec<HotspotReport> {
+        hotspots::hotspot_timeline(&self.snapshots, &self.file_history, limit)
+    }
+
+    /// Centrality, communities and folder mismatch of one snapshot
+    pub fn modularity(&self, commit_hash: &str) -> Result<ModularityReport> {
+        let snapshot = self.find_snapshot(commit_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", commit_hash))?;
+        Ok(modularity::analyze_modularity(snapshot))
+    }
+
+    /// Modularity reports for every analyzed commit, oldest first
+    pub fn modularity_timeline(&self) -> Vec<ModularityReport> {
+        modularity::modularity_timeline(&self.snapshots)
+    }
+
+    /// File and folder ownership, bus factor and edges crossing ownership boundaries.
+    /// Owners come from CODEOWNERS when present, otherwise from the top author.
+    pub fn ownership(&self, commit_hash: &str) -> Result<OwnershipReport> {
+        let snapshot = self.find_snapshot(commit_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", commit_hash))?;
+        Ok(ownership::analyze_ownership(
+            snapshot,
+            &self.file_history,
+            self.codeowners.as_ref(),
+            self.config.subfolder.as_deref(),
+        ))
+    }
+
+    /// Changes between each pair of consecutive snapshots, including renames
+    fn collect_snapshot_changes(&self, git_navigator: &GitTemporalNavigator) -> Vec<FileChangeSet> {
+        self.snapshots
+            .windows(2)
+            .map(|pair| {
+                let changes = git_navigator
+                    .get_file_changes(&pair[0].commit_info.hash, &pair[1].commit_info.hash)
+                    .unwrap_or_else(|e| {
+                        log::warn!("Failed to diff {} and {}: {}",
+                                  pair[0].commit_info.hash, pair[1].commit_info.hash, e);
+                        FileChangeSet::default()
+                    });
+                node_identity::project_relative_changes(&changes, self.config.subfolder.as_deref())
+            })
+            .collect()
+    }
+
+    /// Files changed between the previous analyzed snapshot and this one
+    pub fn get_snapshot_changes(&self, commit_hash: &str) -> Option<&FileChangeSet> {
+        let position = self.snapshots.iter().position(|s| s.commit_info.hash == commit_hash)?;
+        self.snapshot_changes.get(position.checked_sub(1)?)
+    }
+
+    /// Store on each snapshot the regressions it introduced relative to its predecessor
+    fn detect_regressions(&mut self) {
+        let thresholds = &self.regression_thresholds;
+        let events: Vec<Vec<RegressionEvent>> = std::iter::once(Vec::new())
+            .chain(self.snapshots.windows(2).enumerate().map(|(i, pair)| {
+                let renames = self.snapshot_changes
+                    .get(i)
+                    .map(|c| c.renamed_files.clone())
+                    .unwrap_or_default();
+                let diff = snapshot_diff::diff_snapshots(&pair[0], &pair[1], &renames);
+                regressions::detect_regressions(&pair[0], &pair[1], &diff, thresholds)
+            }))
+            .collect();
+
+        for (snapshot, events) in self.snapshots.iter_mut().zip(events) {
+            snapshot.regressions = events;
+        }
+    }
+
+    /// Replace the regression thresholds and re-evaluate all snapshots
+    pub fn set_regression_thresholds(&mut self, thresholds: RegressionThresholds) {
+        self.config.regression_thresholds = Some(thresholds.clone());
+        self.regression_thresholds = thresholds;
+        self.detect_regressions();
+    }
+
+    pub fn get_regression_thresholds(&self) -> &RegressionThresholds {
+        &self.regression_thresholds
+    }
+
+    /// Replace the quality scoring model and rescore all snapshots. Regressions are
+    /// re-evaluated since quality score drops depend on the model.
+    pub fn set_quality_model(&mut self, model: QualityScoreModel) {
+        for snapshot in &mut self.snapshots {
+            snapshot.analysis_result.apply_quality_model(&model);
+        }
+        self.config.quality_model = Some(model.clone());
+        self.quality_model = model;
+        self.detect_regressions();
+    }
+
+    pub fn get_quality_model(&self) -> &QualityScoreModel {
+        &self.quality_model
+    }
+
+    /// Per-factor explanation of a snapshot's quality score
+    pub fn quality_breakdown(&self, commit_hash: &str) -> Result<QualityScoreBreakdown> {
+        let snapshot = self.find_snapshot(commit_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", commit_hash))?;
+        Ok(snapshot.analysis_result.quality_breakdown.clone().unwrap_or_else(|| {
+            crate::quality_score::score(&snapshot.analysis_result, &self.quality_model)
+        }))
+    }
+
+    /// Quality model from the model file at the head of the repository: a preset
+    /// plus overrides, like the `quality_model` option
+    fn load_repository_quality_model(git_navigator: &GitTemporalNavigator) -> QualityScoreModel {
+        let Ok(Some(content)) = git_navigator.read_head_file(quality_score::QUALITY_MODEL_FILE) else {
+            return QualityScoreModel::default();
+        };
+        serde_json::from_str(&content)
+            .map_err(anyhow::Error::from)
+            .and_then(|config| QualityScoreModel::from_config(&config))
+            .unwrap_or_else(|e| {
+                log::warn!("Ignoring invalid {}: {}", quality_score::QUALITY_MODEL_FILE, e);
+                QualityScoreModel::default()
+            })
+    }
+
+    /// Project settings from a JSON file at the head of the repository, or the
+    /// defaults when it has none
+    fn load_repository_file<T: DeserializeOwned + Default>(git_navigator: &GitTemporalNavigator, path: &str) -> T {
+        let Ok(Some(content)) = git_navigator.read_head_file(path) else {
+            return T::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid {}: {}", path, e);
+            T::default()
+        })
+    }
+
+    /// Record the violations of the current rules as issues on every snapshot
+    fn evaluate_rules(&mut self) {
+        for snapshot in &mut self.snapshots {
+            architecture_rules::apply_rules(snapshot, &self.rules);
+        }
+    }
+
+    /// Replace the architecture rules and re-evaluate all snapshots
+    pub fn set_architecture_rules(&mut self, rules: RuleSet) -> Result<()> {
+        rules.validate()?;
+        self.config.architecture_rules = Some(rules.clone());
+        self.rules = rules;
+        self.evaluate_rules();
+        Ok(())
+    }
+
+    pub fn get_architecture_rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Rule violations of one snapshot
+    pub fn rule_violations(&self, commit_hash: &str) -> Result<Vec<AnalysisIssue>> {
+        let snapshot = self.find_snapshot(commit_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", commit_hash))?;
+        Ok(architecture_rules::rule_issues(snapshot).cloned().collect())
+    }
+
+    /// Number of rule violations per analyzed commit, oldest first
+    pub fn rule_violation_timeline(&self) -> Vec<RuleViolationCount> {
+        architecture_rules::violation_timeline(&self.snapshots)
+    }
+
+    /// Where the baseline is written, see [`ChronoGraphConfig::baseline_target`]
+    pub fn baseline_path(&self) -> Result<PathBuf> {
+        self.config.baseline_target()
+    }
+
+    /// Baseline from the configured path, else from the head of the analyzed branch
+    /// like the other project settings
+    fn load_baseline(&self, git_navigator: &GitTemporalNavigator) -> Option<Baseline> {
+        let Some(ref path) = self.config.baseline_path else {
+            return Self::load_repository_file(git_navigator, baseline::BASELINE_FILE);
+        };
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content)
+            .map_err(|e| log::warn!("Ignoring invalid baseline {}: {}", path.display(), e))
+            .ok()
+    }
+
+    /// Write a baseline and keep it
+    fn store_baseline(&mut self, baseline: Baseline) -> Result<Baseline> {
+        let path = self.baseline_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(&baseline)?)
+            .with_context(|| format!("Failed to write baseline to {}", path.display()))?;
+        self.baseline = Some(baseline.clone());
+        Ok(baseline)
+    }
+
+    /// Accept the current violations, cycles and metric values of a snapshot
+    /// (latest by default) as the baseline
+    pub fn record_baseline(&mut self, commit_hash: Option<&str>) -> Result<Baseline> {
+        let snapshot = match commit_hash {
+            Some(hash) => self.find_snapshot(hash)
+                .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", hash))?,
+            None => self.snapshots.last()
+                .ok_or_else(|| anyhow::anyhow!("No analysis available"))?,
+        };
+        let baseline = Baseline::record(snapshot);
+        self.store_baseline(baseline)
+    }
+
+    pub fn get_baseline(&self) -> Option<&Baseline> {
+        self.baseline.as_ref()
+    }
+
+    /// New violations, cycles and metric regressions of a snapshot (latest by
+    /// default) relative to the baseline
+    pub fn compare_with_baseline(&self, commit_hash: Option<&str>) -> Result<BaselineComparison> {
+        let baseline = self.baseline.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No baseline recorded"))?;
+        let snapshot = match commit_hash {
+            Some(hash) => self.find_snapshot(hash)
+                .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", hash))?,
+            None => self.snapshots.last()
+                .ok_or_else(|| anyhow::anyhow!("No analysis available"))?,
+        };
+        Ok(baseline.compare(snapshot))
+    }
+
+    /// Tighten the baseline to a snapshot (latest by default), keeping only what
+    /// is still present and the better value of every metric
+    pub fn tighten_baseline(&mut self, commit_hash: Option<&str>) -> Result<Baseline> {
+        let baseline = self.baseline.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No baseline recorded"))?;
+        let snapshot = match commit_hash {
+            Some(hash) => self.find_snapshot(hash)
+                .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", hash))?,
+            None => self.snapshots.last()
+                .ok_or_else(|| anyhow::anyhow!("No analysis available"))?,
+        };
+        let tightened = baseline.tighten(snapshot);
+        self.store_baseline(tightened)
+    }
+
+    /// Tighten the baseline when the latest snapshot improves on it
+    fn ratchet_baseline(&mut self) {
+        let improved = self.compare_with_baseline(None).map(|c| c.improved()).unwrap_or(false);
+        if improved {
+            match self.tighten_baseline(None) {
+                Ok(_) => log::info!("Tightened baseline written to {}", self.baseline_path().unwrap_or_default().display()),
+                Err(e) => log::warn!("Failed to tighten baseline: {}", e),
+            }
+        }
+    }
+
+    /// Regressions of one snapshot, or of all snapshots oldest first
+    pub fn get_regressions(&self, commit_hash: Option<&str>) -> Result<Vec<RegressionEvent>> {
+        match commit_hash {
+            Some(hash) => self.find_snapshot(hash)
+                .map(|s| s.regressions.clone())
+                .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", hash)),
+            None => Ok(self.snapshots.iter().flat_map(|s| s.regressions.clone()).collect()),
+        }
+    }
+
+    /// Stable node ids across renames
+    pub fn get_node_identities(&self) -> &NodeIdentityMap {
+        &self.node_identities
+    }
+
+    /// Every dependency cycle seen across the analyzed snapshots, in order of appearance
+    pub fn get_cycle_history(&self) -> &[TrackedCycle] {
+        &self.cycle_history
+    }
+
+    /// Metric history of a file across all analyzed snapshots, following renames
+    pub fn node_metric_history(&self, node_path: &str) -> Vec<NodeMetricPoint> {
+        self.node_identities.metric_history(&self.snapshots, node_path)
+    }
+
+    /// Chart-ready metric series of a file (rename-aware) or, when no snapshot
+    /// contains a file at that path, of the folder
+    pub fn metric_series(&self, node_path: &str) -> MetricTimeSeries {
+        let path = crate::dependency_view::normalize_path(node_path.as_ref());
+        let is_file = self.node_identities.latest_id(&path).is_some()
+            || self.snapshots.iter().any(|s| {
+                crate::graph_algorithms::node_metrics_or_derived(&s.analysis_result).contains_key(&path)
+            });
+
+        if is_file {
+            metric_series::node_series(&self.snapshots, &self.node_identities, &path)
+        } else {
+            metric_series::folder_series(&self.snapshots, &path)
+        }
+    }
+
+    /// Abstractness, instability and distance from the main sequence of every file,
+    /// or of every folder, in a snapshot
+    pub fn package_metrics(&self, commit_hash: &str, folders: bool) -> Result<Vec<PackageMetrics>> {
+        let snapshot = self.find_snapshot(commit_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", commit_hash))?;
+        Ok(if folders {
+            package_metrics::all_folder_package_metrics(&snapshot.analysis_result)
+        } else {
+            package_metrics::file_package_metrics(&snapshot.analysis_result)
+        })
+    }
+
+    /// Chart-ready series of the global architectural metrics
+    pub fn global_metric_series(&self) -> MetricTimeSeries {
+        metric_series::global_series(&self.snapshots)
+    }
+
+    /// Find a snapshot by full commit hash or unique prefix
+    pub fn find_snapshot(&self, commit_hash: &str) -> Option<&CommitSnapshot> {
+        if let Some(snapshot) = self.snapshots.iter().find(|s| s.commit_info.hash == commit_hash) {
+            return Some(snapshot);
+        }
+
+        let mut matches = self.snapshots.iter().filter(|s| s.commit_info.hash.starts_with(commit_hash));
+        match (matches.next(), matches.next()) {
+            (Some(snapshot), None) if !commit_hash.is_empty() => Some(snapshot),
+            _ => None,
+        }
+    }
+
+    /// Diff two analyzed snapshots, following files renamed between them
+    pub fn diff_snapshots(&self, from_hash: &str, to_hash: &str) -> Result<SnapshotDiff> {
+        let from = self.find_snapshot(from_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", from_hash))?;
+        let to = self.find_snapshot(to_hash)
+            .ok_or_else(|| anyhow::anyhow!("No analyzed snapshot for commit {}", to_hash))?;
+
+        let renames = match self.git_navigator {
+            Some(ref navigator) => navigator
+                .find_renames(&from.commit_info.hash, &to.commit_info.hash)
+                .unwrap_or_else(|e| {
+                    log::warn!("Rename detection failed, diffing by path only: {}", e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+        let renames = snapshot_diff::project_relative_renames(&renames, self.config.subfolder.as_deref());
+
+        Ok(snapshot_diff::diff_snapshots(from, to, &renames))
+    }
+    
+    /// Get repository information
+    pub fn get_repo_info(&self) -> Option<&RepoCloneInfo> {
+        self.git_navigator.as_ref().map(|nav| nav.clone_info())
+    }
+    
+    /// Get current configuration
+    pub fn get_config(&self) -> &ChronoGraphConfig {
+        &self.config
+    }
+    
+    /// Update configuration
+    pub fn update_config(&mut self, config: ChronoGraphConfig) {
+        self.config = config;
+    }
+    
+    /// Add a custom analyzer
+    pub fn register_analyzer(&mut self, analyzer: Box<dyn DependencyAnalyzer>) {
+        self.analyzer_registry.register(analyzer);
+    }
+    
+    /// List available analyzers
+    pub fn list_analyzers(&self) -> Vec<crate::dependency_analyzer::AnalyzerInfo> {
+        self.analyzer_registry.list_analyzers()
+    }
+
+    /// Get cache statistics
+    pub fn get_cache_statistics(&mut self) -> Option<CacheStatistics> {
+        self.cache.as_mut().and_then(|cache| cache.get_statistics().ok())
+    }
+
+    /// Clear cache for current repository
+    pub fn clear_repository_cache(&mut self) -> Result<usize> {
+        if let Some(ref mut cache) = self.cache {
+            let removed_files = cache.remove_repository(&self.config.github_url)?;
+            Ok(removed_files.len())
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Cleanup old cache entries
+    pub fn cleanup_old_cache(&mut self, max_age_days: u64) -> Result<usize> {
+        if let Some(ref mut cache) = self.cache {
+            cache.cleanup_old_entries(max_age_days)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Clear entire cache
+    pub fn clear_all_cache(&mut self) -> Result<usize> {
+        if let Some(ref mut cache) = self.cache {
+            cache.clear_all()
+        } else {
+            Ok(0)
+        }
+    }
+    
+    /// Get analysis statistics
+    pub fn get_statistics(&self) -> AnalysisStatistics {
+        let mut stats = AnalysisStatistics::default();
+        
+        if self.snapshots.is_empty() {
+            return stats;
+        }
+        
+        // Basic statistics
+        stats.total_snapshots = self.snapshots.len();
+        stats.total_dependencies = self.snapshots.iter()
+            .map(|s| s.analysis_result.dependencies.len())
+            .sum();
+        stats.total_files_analyzed = self.snapshots.iter()
+            .map(|s| s.analysis_result.analyzed_files.len())
+            .sum();
+        
+        // Temporal statistics
+        if let (Some(first), Some(last)) = (self.snapshots.first(), self.snapshots.last()) {
+            stats.time_span_seconds = last.commit_info.timestamp - first.commit_info.timestamp;
+            stats.first_commit_hash = first.commit_info.hash.clone();
+            stats.last_commit_hash = last.commit_info.hash.clone();
+        }
+        
+        // Author statistics
+        let mut authors = HashMap::new();
+        for snapshot in &self.snapshots {
+            let count = authors.entry(snapshot.commit_info.author_name.clone()).or_insert(0);
+            *count += 1;
+        }
+        stats.author_commit_counts = authors;
+        
+        stats
+    }
+    
+    /// Cleanup resources
+    pub fn cleanup(mut self) -> Result<()> {
+        if self.config.cleanup_after_analysis {
+            if let Some(git_navigator) = self.git_navigator.take() {
+                git_navigator.cleanup()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AnalysisStatistics {
+    pub total_snapshots: usize,
+    pub total_dependencies: usize,
+    pub total_files_analyzed: usize,
+    pub time_span_seconds: i64,
+    pub first_commit_hash: String,
+    pub last_commit_hash: String,
+    pub author_commit_counts: HashMap<String, usize>,
+}
+
+/// A bisect detached from the engine, see `ChronoGraphEngine::prepare_bisect`
+pub struct BisectJob {
+    config: ChronoGraphConfig,
+    analyzer_registry: AnalyzerRegistry,
+    quality_model: QualityScoreModel,
+    cache: Option<AnalysisCache>,
+    /// Scratch clone, only taken when the job is dropped
+    git_navigator: Option<GitTemporalNavigator>,
+    target: BisectTarget,
+    candidates: Vec<CommitInfo>,
+    present_at_start: bool,
+    present_at_end: bool,
+}
+
+impl BisectJob {
+    pub fn run(mut self) -> Result<BisectResult> {
+        let git_navigator = self.git_navigator.as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Bisect repository already removed"))?;
+        bisect::bisect_commits(&self.target, &self.candidates, self.present_at_start, self.present_at_end, |commit| {
+            let snapshot = analyze_commit(
+                &self.config,
+                &self.analyzer_registry,
+                &self.quality_model,
+                self.cache.as_mut(),
+                git_navigator,
+                commit,
+            )
+            .with_context(|| format!("Failed to analyze commit {} while bisecting", commit.hash))?;
+            Ok(self.target.is_present(&snapshot.analysis_result))
+        })
+    }
+}
+
+impl Drop for BisectJob {
+    fn drop(&mut self) {
+        if let Some(git_navigator) = self.git_navigator.take() {
+            if let Err(e) = git_navigator.cleanup() {
+                log::warn!("Failed to remove bisect repository: {}", e);
+            }
+        }
+    }
+}
+
+/// Check out a commit and analyze it, going through the cache when there is one
+fn analyze_commit(
+    config: &ChronoGraphConfig,
+    registry: &AnalyzerRegistry,
+    quality_model: &QualityScoreModel,
+    mut cache: Option<&mut AnalysisCache>,
+    git_navigator: &mut GitTemporalNavigator,
+    commit_info: &CommitInfo,
+) -> Result<CommitSnapshot> {
+
+    // Checkout the commit
+    git_navigator.checkout_commit(&commit_info.hash)
+        .context("Failed to checkout commit")?;
+
+    // Get the analyzer
+    let analyzer = registry
+        .get_analyzer(&config.analyzer_name)
+        .ok_or_else(|| anyhow::anyhow!("Analyzer '{}' not found", config.analyzer_name))?;
+
+    // Determine analysis path (subfolder or root)
+    let base_project_path = git_navigator.local_path();
+    let analysis_path = if let Some(ref subfolder) = config.subfolder {
+        let subfolder_path = base_project_path.join(subfolder);
+        if !subfolder_path.exists() {
+            anyhow::bail!("Subfolder '{}' does not exist at commit {}",
+                         subfolder, commit_info.hash);
+        }
+        subfolder_path
+    } else {
+        base_project_path.to_path_buf()
+    };
+
+    // Verify project can be analyzed at this commit
+    if !analyzer.can_analyze_project(&analysis_path) {
+        let suggestion = if analyzer.name() == "lakos" {
+            " (No pubspec.yaml found - this doesn't appear to be a Flutter/Dart project. If the project is in a subfolder, please specify it in the analysis settings.)"
+        } else {
+            ""
+        };
+        anyhow::bail!("Cannot analyze project at commit {}: Required project files not found{}",
+                     &commit_info.hash[..8], suggestion);
+    }
+
+    // Try to get analysis result from cache first
+    let cache_key = AnalysisCacheKey::new(
+        config.github_url.clone(),
+        commit_info.hash.clone(),
+        config.subfolder.clone(),
+        config.analyzer_name.clone(),
+        &config.analysis_config,
+    );
+
+    // Check cache if available
+    if let Some(cache) = cache.as_deref_mut() {
+        if let Ok(Some(mut cached_result)) = cache.get(&cache_key) {
+            log::debug!("Cache hit for commit {}", &commit_info.hash[..8]);
+            cached_result.apply_quality_model(quality_model);
+            return Ok(CommitSnapshot {
+                commit_info: commit_info.clone(),
+                analysis_result: cached_result,
+                project_path: analysis_path,
+                regressions: Vec::new(),
+            });
+        }
+    }
+
+    log::debug!("Cache miss, analyzing commit {}", &commit_info.hash[..8]);
+
+    // Run analysis on the specified path
+    let mut analysis_result = analyzer.analyze_project(&analysis_path, &config.analysis_config)
+        .context("Failed to run dependency analysis")?;
+    analysis_result.apply_quality_model(quality_model);
+
+    // Store result in cache if available
+    if let Some(cache) = cache {
+        if let Err(e) = cache.put(&cache_key, &analysis_result) {
+            log::warn!("Failed to cache analysis result for commit {}: {}",
+                     commit_info.hash, e);
+        }
+    }
+
+    Ok(CommitSnapshot {
+        commit_info: commit_info.clone(),
+        analysis_result,
+        project_path: analysis_path,
+        regressions: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_config_defaults() {
+        let config = ChronoGraphConfig::default();
+        assert_eq!(config.analyzer_name, "lakos");
+        assert_eq!(config.commit_sampling, 5);
+        assert_eq!(config.max_commits, Some(100));
+        assert!(config.cleanup_after_analysis);
+    }
+    
+    #[test]
+    fn test_commit_sampling() {
+        let engine = ChronoGraphEngine::new(ChronoGraphConfig {
+            commit_sampling: 2,
+            max_commits: None,
+            is_local_repository: false,
+            ..Default::default()
+        });
+        
+        // Create test commits
+        let commits: Vec<CommitInfo> = (0..10).map(|i| CommitInfo {
+            hash: format!("hash{}", i),
+            author_name: "test".to_string(),
+            author_email: "test@test.com".to_string(),
+            message: format!("Commit {}", i),
+            timestamp: i,
+            merge_parent_hash: None,
+        }).collect();
+        
+        let sampled = engine.sample_commits(&commits);
+        
+        // Should include first, last, and every 2nd commit
+        assert!(sampled.len() >= 3); // At least first, last, and some in between
+        assert_eq!(sampled[0].hash, "hash0"); // First commit
+        assert_eq!(sampled.last().unwrap().hash, "hash9"); // Last commit
+    }
+
+    /// Reads `source target` lines from the project's deps.txt
+    struct DepsFileAnalyzer;
+
+    impl DependencyAnalyzer for DepsFileAnalyzer {
+        fn name(&self) -> &str {
+            "deps-file"
+        }
+
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+
+        fn capabilities(&self) -> crate::dependency_analyzer::AnalyzerCapabilities {
+            crate::dependency_analyzer::AnalyzerCapabilities {
+                supports_weighted_analysis: false,
+                supports_symbol_tracking: false,
+                supports_line_numbers: false,
+                supports_dynamic_imports: false,
+                supports_declarations: false,
+                supported_file_extensions: vec!["txt".to_string()],
+                performance_tier: crate::dependency_analyzer::PerformanceTier::Fast,
+            }
+        }
+
+        fn analyze_project(&self, project_path: &std::path::Path, _config: &AnalysisConfig) -> Result<AnalysisResult> {
+            let content = std::fs::read_to_string(project_path.join("deps.txt"))?;
+            let deps = content
+                .lines()
+                .filter_map(|line| line.split_once(' '))
+                .map(|(source, target)| crate::test_fixtures::dep(source, target))
+                .collect();
+            Ok(crate::test_fixtures::analysis_result(deps))
+        }
+    }
+
+    #[test]
+    fn test_concurrent_bisects() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        let mut lines = Vec::new();
+        let mut hashes = Vec::new();
+        for i in 1..=8 {
+            match i {
+                3 => lines.push("lib/a.dart lib/b.dart"),
+                6 => lines.push("lib/x.dart lib/y.dart"),
+                _ => lines.push("lib/c.dart lib/c.dart"),
+            }
+            let content = lines.join("\n");
+            hashes.push(test_repo.commit(&[("deps.txt", Some(&content))], "alice", i * 1_000));
+        }
+
+        let base_dir = tempfile::tempdir().unwrap();
+        let mut engine = ChronoGraphEngine::new(ChronoGraphConfig {
+            github_url: test_repo.dir.path().to_string_lossy().to_string(),
+            local_base_dir: base_dir.path().to_path_buf(),
+            analyzer_name: "deps-file".to_string(),
+            is_local_repository: true,
+            ..Default::default()
+        });
+        engine.git_navigator = Some(test_repo.navigator(base_dir.path()));
+        engine.snapshots = vec![
+            crate::test_fixtures::snapshot(&hashes[0], 1_000, Vec::new()),
+            crate::test_fixtures::snapshot(&hashes[7], 8_000, Vec::new()),
+        ];
+        engine.snapshots[1].analysis_result.dependencies = vec![
+            crate::test_fixtures::dep("lib/a.dart", "lib/b.dart"),
+            crate::test_fixtures::dep("lib/x.dart", "lib/y.dart"),
+        ];
+
+        let jobs: Vec<BisectJob> = [("lib/a.dart", "lib/b.dart"), ("lib/x.dart", "lib/y.dart")]
+            .into_iter()
+            .map(|(source, target)| {
+                let target = BisectTarget::Edge { source: source.to_string(), target: target.to_string() };
+                let mut job = engine.prepare_bisect(target, &hashes[0], &hashes[7]).unwrap();
+                job.analyzer_registry.register(Box::new(DepsFileAnalyzer));
+                job.cache = None;
+                job
+            })
+            .collect();
+
+        let introduced: Vec<String> = std::thread::scope(|scope| {
+            let runs: Vec<_> = jobs.into_iter().map(|job| scope.spawn(move || job.run())).collect();
+            runs.into_iter().map(|run| run.join().unwrap().unwrap().commit.hash).collect()
+        });
+        assert_eq!(introduced, vec![hashes[2].clone(), hashes[5].clone()]);
+
+        // The engine's own checkout is untouched and the scratch clones are gone
+        let navigator = engine.git_navigator.as_ref().unwrap();
+        assert_eq!(navigator.current_commit(), None);
+        assert_eq!(std::fs::read_to_string(navigator.local_path().join("deps.txt")).unwrap(), lines.join("\n"));
+        assert_eq!(std::fs::read_dir(base_dir.path().join("bisect")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_repository_settings_files() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        test_repo.commit(&[
+            (regressions::THRESHOLDS_FILE, Some(r#"{"nccd_increase": 0.5, "new_cycles": false}"#)),
+            (architecture_rules::RULES_FILE, Some("not json")),
+        ], "alice", 1_000);
+
+        let base_dir = tempfile::tempdir().unwrap();
+        let navigator = test_repo.navigator(base_dir.path());
+        let thresholds: RegressionThresholds =
+            ChronoGraphEngine::load_repository_file(&navigator, regressions::THRESHOLDS_FILE);
+        assert_eq!(thresholds.nccd_increase, 0.5);
+        assert!(!thresholds.new_cycles);
+        assert_eq!(thresholds.quality_score_drop, RegressionThresholds::default().quality_score_drop);
+
+        let rules: RuleSet = ChronoGraphEngine::load_repository_file(&navigator, architecture_rules::RULES_FILE);
+        assert!(rules.rules.is_empty());
+    }
+
+    #[test]
+    fn test_baseline_location() {
+        let baseline = Baseline::record(&crate::test_fixtures::snapshot("c1", 1_000, Vec::new()));
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        test_repo.commit(&[(
+            baseline::BASELINE_FILE,
+            Some(&serde_json::to_string(&baseline).unwrap()),
+        )], "alice", 1_000);
+        let base_dir = tempfile::tempdir().unwrap();
+        let navigator = test_repo.navigator(base_dir.path());
+
+        let mut engine = ChronoGraphEngine::new(ChronoGraphConfig {
+            github_url: "https://example.com/app.git".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(engine.load_baseline(&navigator).map(|b| b.commit_hash), Some("c1".to_string()));
+
+        engine.snapshots.push(crate::test_fixtures::snapshot("c2", 2_000, Vec::new()));
+        assert!(engine.record_baseline(None).is_err());
+        assert!(engine.get_baseline().is_none());
+
+        // Ratcheting a remote repository's baseline needs somewhere to write it
+        engine.config.ratchet_baseline = true;
+        assert!(engine.config.validate().is_err());
+
+        let path = base_dir.path().join("baseline.json");
+        engine.config.baseline_path = Some(path.clone());
+        assert!(engine.config.validate().is_ok());
+        engine.record_baseline(None).unwrap();
+        assert_eq!(engine.load_baseline(&navigator).map(|b| b.commit_hash), Some("c2".to_string()));
+    }
+
+    #[test]
+    fn test_repository_quality_model() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        test_repo.commit(&[(
+            quality_score::QUALITY_MODEL_FILE,
+            Some(r#"{"preset": "library", "coupling": {"nccd_threshold": 0.4}}"#),
+        )], "alice", 1_000);
+
+        let base_dir = tempfile::tempdir().unwrap();
+        let navigator = test_repo.navigator(base_dir.path());
+        let model = ChronoGraphEngine::load_repository_quality_model(&navigator);
+        assert_eq!(model.preset, quality_score::QualityPreset::Library);
+        assert_eq!(model.coupling.nccd_threshold, 0.4);
+        assert!(!model.orphans.enabled);
+    }
+}
\ No newline at end of file
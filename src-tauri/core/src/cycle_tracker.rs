@@ -0,0 +1,258 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::DependencyGraph;
+use crate::node_identity::NodeIdentityMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A cycle as observed in one snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CyclePoint {
+    pub commit_hash: String,
+    pub timestamp: i64,
+    pub size: usize,
+    /// Member paths as they were named in this snapshot
+    pub members: Vec<String>,
+}
+
+/// One dependency cycle followed across snapshots by member overlap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackedCycle {
+    pub id: String,
+    pub first_commit: String,
+    pub first_author: String,
+    pub first_timestamp: i64,
+    /// Intra-cycle edges that were new in the first commit; adding them closed the cycle
+    pub closing_edges: Vec<(String, String)>,
+    pub history: Vec<CyclePoint>,
+    pub max_size: usize,
+    pub resolved_in: Option<String>,
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<i64>,
+    /// Intra-cycle edges removed in the resolving commit
+    pub breaking_edges: Vec<(String, String)>,
+    /// Set when the cycle did not disappear but was absorbed into a larger one
+    pub merged_into: Option<String>,
+}
+
+impl TrackedCycle {
+    pub fn is_active(&self) -> bool {
+        self.resolved_in.is_none() && self.merged_into.is_none()
+    }
+}
+
+/// Cycles of one snapshot in stable-id space, plus the edges needed to explain them
+struct SnapshotCycles {
+    cycles: Vec<BTreeSet<String>>,
+    edges: HashSet<(String, String)>,
+    /// Stable id -> path in this snapshot
+    paths: HashMap<String, String>,
+}
+
+impl SnapshotCycles {
+    fn new(snapshot: &CommitSnapshot, identities: &NodeIdentityMap) -> Self {
+        let hash = &snapshot.commit_info.hash;
+        let mut paths = HashMap::new();
+        let mut id_of = |path: &str| -> String {
+            let id = identities.stable_id(hash, path).unwrap_or(path).to_string();
+            paths.insert(id.clone(), path.to_string());
+            id
+        };
+
+        let graph = DependencyGraph::from_dependencies(&snapshot.analysis_result.dependencies);
+        let cycles = graph.cycles().into_iter().map(|c| c.iter().map(|m| id_of(m)).collect()).collect();
+        let edges = snapshot
+            .analysis_result
+            .dependencies
+            .iter()
+            .map(|d| (id_of(&normalize_path(&d.source_file)), id_of(&normalize_path(&d.target_file))))
+            .collect();
+
+        Self { cycles, edges, paths }
+    }
+
+    fn path<'a>(&'a self, id: &'a str) -> &'a str {
+        self.paths.get(id).map(String::as_str).unwrap_or(id)
+    }
+
+    /// Edges of `self` between members that are not in `other`, named as in `self`
+    fn edges_missing_from(&self, members: &BTreeSet<String>, other: &SnapshotCycles) -> Vec<(String, String)> {
+        let mut edges: Vec<(String, String)> = self
+            .edges
+            .iter()
+            .filter(|(s, t)| s != t && members.contains(s) && members.contains(t))
+            .filter(|edge| !other.edges.contains(*edge))
+            .map(|(s, t)| (self.path(s).to_string(), self.path(t).to_string()))
+            .collect();
+        edges.sort();
+        edges
+    }
+}
+
+/// Follow every cycle through the chronologically ordered snapshots
+pub fn track_cycles(snapshots: &[CommitSnapshot], identities: &NodeIdentityMap) -> Vec<TrackedCycle> {
+    let mut tracked: Vec<TrackedCycle> = Vec::new();
+    // Active cycle index in `tracked` -> its members in the previous snapshot
+    let mut active: Vec<(usize, BTreeSet<String>)> = Vec::new();
+    let mut previous: Option<SnapshotCycles> = None;
+
+    for snapshot in snapshots {
+        let commit = &snapshot.commit_info;
+        let current = SnapshotCycles::new(snapshot, identities);
+
+        // Greedy one-to-one matching, largest overlap first
+        let mut candidates: Vec<(usize, usize, usize)> = Vec::new();
+        for (active_idx, (_, members)) in active.iter().enumerate() {
+            for (cycle_idx, cycle) in current.cycles.iter().enumerate() {
+                let overlap = members.intersection(cycle).count();
+                if overlap > 0 {
+                    candidates.push((overlap, active_idx, cycle_idx));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| (a.1, a.2).cmp(&(b.1, b.2))));
+
+        let mut matched_active: HashMap<usize, usize> = HashMap::new();
+        let mut matched_cycles: HashMap<usize, usize> = HashMap::new();
+        for (_, active_idx, cycle_idx) in candidates {
+            if matched_active.contains_key(&active_idx) || matched_cycles.contains_key(&cycle_idx) {
+                continue;
+            }
+            matched_active.insert(active_idx, cycle_idx);
+            matched_cycles.insert(cycle_idx, active_idx);
+        }
+
+        let mut next_active = Vec::new();
+
+        // Cycles that continued, were absorbed or were broken
+        for (active_idx, (tracked_idx, members)) in active.iter().enumerate() {
+            if let Some(&cycle_idx) = matched_active.get(&active_idx) {
+                next_active.push((*tracked_idx, current.cycles[cycle_idx].clone()));
+                continue;
+            }
+
+            let absorbed_by = current
+                .cycles
+                .iter()
+                .enumerate()
+                .filter(|(_, cycle)| members.is_subset(cycle))
+                .find_map(|(cycle_idx, _)| matched_cycles.get(&cycle_idx));
+            match absorbed_by {
+                Some(&other_active) => {
+                    let other_id = tracked[active[other_active].0].id.clone();
+                    tracked[*tracked_idx].merged_into = Some(other_id);
+                }
+                None => {
+                    let cycle = &mut tracked[*tracked_idx];
+                    cycle.resolved_in = Some(commit.hash.clone());
+                    cycle.resolved_by = Some(commit.author_name.clone());
+                    cycle.resolved_at = Some(commit.timestamp);
+                    if let Some(ref previous) = previous {
+                        cycle.breaking_edges = previous.edges_missing_from(members, &current);
+                    }
+                }
+            }
+        }
+
+        // Newly appeared cycles
+        for (cycle_idx, members) in current.cycles.iter().enumerate() {
+            if matched_cycles.contains_key(&cycle_idx) {
+                continue;
+            }
+            let closing_edges = match previous {
+                Some(ref previous) => current.edges_missing_from(members, previous),
+                None => Vec::new(),
+            };
+            tracked.push(TrackedCycle {
+                id: format!("cycle-{}", tracked.len() + 1),
+                first_commit: commit.hash.clone(),
+                first_author: commit.author_name.clone(),
+                first_timestamp: commit.timestamp,
+                closing_edges,
+                history: Vec::new(),
+                max_size: 0,
+                resolved_in: None,
+                resolved_by: None,
+                resolved_at: None,
+                breaking_edges: Vec::new(),
+                merged_into: None,
+            });
+            next_active.push((tracked.len() - 1, members.clone()));
+        }
+
+        for (tracked_idx, members) in &next_active {
+            let cycle = &mut tracked[*tracked_idx];
+            let mut paths: Vec<String> = members.iter().map(|m| current.path(m).to_string()).collect();
+            paths.sort();
+            cycle.max_size = cycle.max_size.max(paths.len());
+            cycle.history.push(CyclePoint {
+                commit_hash: commit.hash.clone(),
+                timestamp: commit.timestamp,
+                size: paths.len(),
+                members: paths,
+            });
+        }
+
+        active = next_active;
+        previous = Some(current);
+    }
+
+    tracked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{commit, dep, snapshot};
+
+    #[test]
+    fn test_cycle_appears_grows_and_resolves() {
+        let mut snapshots = vec![
+            snapshot("c1", 100, vec![dep("a.dart", "b.dart")]),
+            snapshot("c2", 200, vec![dep("a.dart", "b.dart"), dep("b.dart", "a.dart")]),
+            snapshot("c3", 300, vec![dep("a.dart", "b.dart"), dep("b.dart", "c.dart"), dep("c.dart", "a.dart")]),
+            snapshot("c4", 400, vec![dep("a.dart", "b.dart"), dep("b.dart", "c.dart")]),
+        ];
+        snapshots[1].commit_info = commit("c2", "bob", 200);
+        snapshots[3].commit_info = commit("c4", "carol", 400);
+
+        let identities = NodeIdentityMap::build(&snapshots, &[]);
+        let cycles = track_cycles(&snapshots, &identities);
+
+        assert_eq!(cycles.len(), 1);
+        let cycle = &cycles[0];
+        assert_eq!(cycle.first_commit, "c2");
+        assert_eq!(cycle.first_author, "bob");
+        assert_eq!(cycle.closing_edges, vec![("b.dart".to_string(), "a.dart".to_string())]);
+        assert_eq!(cycle.history.iter().map(|p| p.size).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(cycle.max_size, 3);
+        assert_eq!(cycle.resolved_in.as_deref(), Some("c4"));
+        assert_eq!(cycle.resolved_by.as_deref(), Some("carol"));
+        assert_eq!(cycle.breaking_edges, vec![("c.dart".to_string(), "a.dart".to_string())]);
+        assert!(!cycle.is_active());
+    }
+
+    #[test]
+    fn test_absorbed_cycle_is_marked_merged() {
+        let snapshots = vec![
+            snapshot("c1", 100, vec![
+                dep("a.dart", "b.dart"), dep("b.dart", "a.dart"),
+                dep("c.dart", "d.dart"), dep("d.dart", "c.dart"),
+            ]),
+            snapshot("c2", 200, vec![
+                dep("a.dart", "b.dart"), dep("b.dart", "a.dart"),
+                dep("c.dart", "d.dart"), dep("d.dart", "c.dart"),
+                dep("b.dart", "c.dart"), dep("d.dart", "a.dart"),
+            ]),
+        ];
+
+        let identities = NodeIdentityMap::build(&snapshots, &[]);
+        let cycles = track_cycles(&snapshots, &identities);
+
+        assert_eq!(cycles.len(), 2);
+        assert!(cycles[0].is_active());
+        assert_eq!(cycles[0].history.last().unwrap().size, 4);
+        assert_eq!(cycles[1].merged_into.as_deref(), Some("cycle-1"));
+        assert!(cycles[1].resolved_in.is_none());
+    }
+}
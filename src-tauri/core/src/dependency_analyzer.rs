@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::quality_score::{self, QualityScoreBreakdown, QualityScoreModel};
 
 /// Represents the weight/strength of a dependency relationship
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,7 +41,7 @@ impl DependencyWeight {
 }
 
 /// Type of dependency relationship
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum RelationshipType {
     /// Direct import statement
     Import,
@@ -56,6 +57,20 @@ pub enum RelationshipType {
     Custom(String),
 }
 
+impl RelationshipType {
+    /// Lowercase name used in exports
+    pub fn name(&self) -> &str {
+        match self {
+            RelationshipType::Import => "import",
+            RelationshipType::Export => "export",
+            RelationshipType::Part => "part",
+            RelationshipType::Dynamic => "dynamic",
+            RelationshipType::Test => "test",
+            RelationshipType::Custom(name) => name,
+        }
+    }
+}
+
 /// Raw dependency extracted by an analyzer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RawDependency {
@@ -80,6 +95,8 @@ pub struct AnalyzerCapabilities {
     pub supports_symbol_tracking: bool,
     pub supports_line_numbers: bool,
     pub supports_dynamic_imports: bool,
+    /// Whether type declarations are counted, enabling abstractness metrics
+    pub supports_declarations: bool,
     pub supported_file_extensions: Vec<String>,
     pub performance_tier: PerformanceTier,
 }
@@ -108,6 +125,9 @@ pub struct AnalysisResult {
 
     // Architecture quality score (derived from metrics)
     pub architecture_quality_score: Option<f64>,
+    /// How each factor contributed to the quality score
+    #[serde(default)]
+    pub quality_breakdown: Option<QualityScoreBreakdown>,
 
     // Existing fields
     pub analyzer_name: String,
@@ -192,6 +212,16 @@ pub struct NodeMetrics {
     pub is_orphan: bool,                     // No dependencies in either direction
     pub in_cycle: bool,                      // Part of dependency cycle
     pub cycle_id: Option<u32>,               // Which cycle (if multiple)
+
+    // Martin Metrics (only when the analyzer can see declarations)
+    #[serde(default)]
+    pub abstract_types: Option<u32>,         // Abstract classes, interfaces and mixins
+    #[serde(default)]
+    pub total_types: Option<u32>,            // All type declarations
+    #[serde(default)]
+    pub abstractness: Option<f64>,           // abstract_types / total_types
+    #[serde(default)]
+    pub distance: Option<f64>,               // |abstractness + instability - 1|
 }
 
 impl Default for NodeMetrics {
@@ -206,6 +236,10 @@ impl Default for NodeMetrics {
             is_orphan: false,
             in_cycle: false,
             cycle_id: None,
+            abstract_types: None,
+            total_types: None,
+            abstractness: None,
+            distance: None,
         }
     }
 }
@@ -247,42 +281,16 @@ impl From<RawDependency> for EnhancedDependency {
 }
 
 impl AnalysisResult {
-    /// Calculate a composite architecture quality score from Lakos metrics
+    /// Calculate the architecture quality score with the default scoring model
     pub fn calculate_quality_score(&mut self) {
-        if let Some(global_metrics) = &self.global_metrics {
-            let mut score = 100.0; // Start with perfect score
-
-            // Penalize for cycles (major architectural issue)
-            if !global_metrics.is_acyclic {
-                score -= 30.0;
-                score -= (global_metrics.detected_cycles.len() as f64) * 5.0;
-            }
-
-            // Penalize for high coupling (NCCD > 0.5 is concerning)
-            if global_metrics.normalized_ccd > 0.5 {
-                score -= (global_metrics.normalized_ccd - 0.5) * 40.0;
-            }
-
-            // Penalize for orphan libraries (indicates poor architecture)
-            score -= (global_metrics.orphan_libraries.len() as f64) * 2.0;
-
-            // Bonus for good average instability (around 0.5 is ideal)
-            if let Some(node_metrics) = &self.node_metrics {
-                let instabilities: Vec<f64> = node_metrics.values()
-                    .map(|nm| nm.instability)
-                    .filter(|i| *i > 0.0) // Filter out zero instabilities
-                    .collect();
-
-                if !instabilities.is_empty() {
-                    let avg_instability = instabilities.iter().sum::<f64>() / instabilities.len() as f64;
-                    let instability_deviation = (avg_instability - 0.5).abs();
-                    score += (0.2 - instability_deviation.min(0.2)) * 25.0; // Bonus for good balance
-                }
-            }
+        self.apply_quality_model(&QualityScoreModel::default());
+    }
 
-            // Ensure score is between 0 and 100
-            self.architecture_quality_score = Some(score.max(0.0).min(100.0));
-        }
+    /// Score the architecture with a configurable model, keeping the per-factor breakdown
+    pub fn apply_quality_model(&mut self, model: &QualityScoreModel) {
+        let breakdown = quality_score::score(self, model);
+        self.architecture_quality_score = Some(breakdown.score);
+        self.quality_breakdown = Some(breakdown);
     }
 
     /// Get enhanced dependencies, falling back to basic dependencies
@@ -302,6 +310,21 @@ impl AnalysisResult {
 }
 
 impl NodeMetrics {
+    /// Record declared types and derive abstractness and distance from the main
+    /// sequence; files declaring no types get neither
+    pub fn set_type_declarations(&mut self, abstract_types: u32, total_types: u32) {
+        self.abstract_types = Some(abstract_types);
+        self.total_types = Some(total_types);
+        if total_types > 0 {
+            let abstractness = abstract_types as f64 / total_types as f64;
+            self.abstractness = Some(abstractness);
+            self.distance = Some(crate::package_metrics::distance(abstractness, self.instability));
+        } else {
+            self.abstractness = None;
+            self.distance = None;
+        }
+    }
+
     /// Calculate architectural role based on metrics
     pub fn architectural_role(&self) -> &'static str {
         if self.is_orphan {
@@ -354,6 +377,15 @@ pub struct AnalysisIssue {
     pub message: String,
     pub file_path: Option<PathBuf>,
     pub line_number: Option<u32>,
+    /// Offending import, for dependency-related issues
+    #[serde(default)]
+    pub import_statement: Option<String>,
+    /// Imported file, for dependency-related issues
+    #[serde(default)]
+    pub target_path: Option<PathBuf>,
+    /// Architecture rule that produced this issue, if any
+    #[serde(default)]
+    pub rule: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -561,6 +593,110 @@ pub mod utils {
         Ok(())
     }
     
+    /// Count top-level type declarations in Dart source as (abstract, total).
+    /// Abstract, sealed and interface classes and plain mixins count as abstract;
+    /// classes, mixin classes, enums and extension types as concrete.
+    pub fn count_dart_type_declarations(source: &str) -> (u32, u32) {
+        let mut abstract_types = 0;
+        let mut total_types = 0;
+        let mut in_block_comment = false;
+
+        for line in source.lines() {
+            let mut line = line.trim();
+            if in_block_comment {
+                match line.find("*/") {
+                    Some(end) => {
+                        in_block_comment = false;
+                        line = line[end + 2..].trim();
+                    }
+                    None => continue,
+                }
+            }
+            if line.starts_with("/*") && !line.contains("*/") {
+                in_block_comment = true;
+                continue;
+            }
+            if line.starts_with("//") {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let mut is_abstract = false;
+            for (i, token) in tokens.iter().enumerate() {
+                let next = tokens.get(i + 1).copied();
+                match *token {
+                    "abstract" | "sealed" | "interface" => is_abstract = true,
+                    "base" | "final" => {}
+                    "mixin" if next != Some("class") => {
+                        abstract_types += 1;
+                        total_types += 1;
+                        break;
+                    }
+                    "mixin" => {}
+                    "class" => {
+                        if is_abstract {
+                            abstract_types += 1;
+                        }
+                        total_types += 1;
+                        break;
+                    }
+                    "enum" => {
+                        total_types += 1;
+                        break;
+                    }
+                    "extension" if next == Some("type") => {
+                        total_types += 1;
+                        break;
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        (abstract_types, total_types)
+    }
+
+    /// Find the import, export or part directive in Dart source that resolves to
+    /// `target`, as its 1-based line and statement. `source_file` and `target` are
+    /// project-relative; `package:` URIs resolve into the project's `lib/`.
+    pub fn find_dart_import(source: &str, source_file: &str, target: &str) -> Option<(u32, String)> {
+        let source_dir = source_file.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        source.lines().enumerate().find_map(|(i, line)| {
+            let statement = line.trim();
+            let directive = statement.split_whitespace().next()?;
+            if !matches!(directive, "import" | "export" | "part") || statement.starts_with("part of") {
+                return None;
+            }
+            let quote = statement.find(['\'', '"'])?;
+            let quote_char = statement[quote..].chars().next()?;
+            let uri_end = statement[quote + 1..].find(quote_char)?;
+            let uri = &statement[quote + 1..quote + 1 + uri_end];
+            (resolve_dart_uri(uri, source_dir)? == target).then(|| (i as u32 + 1, statement.to_string()))
+        })
+    }
+
+    /// Project-relative path of a Dart URI, or None for SDK and unparsable URIs
+    fn resolve_dart_uri(uri: &str, source_dir: &str) -> Option<String> {
+        if let Some(package_path) = uri.strip_prefix("package:") {
+            let (_, path) = package_path.split_once('/')?;
+            return Some(format!("lib/{}", path));
+        }
+        if uri.contains(':') {
+            return None;
+        }
+        let mut parts: Vec<&str> = source_dir.split('/').filter(|p| !p.is_empty()).collect();
+        for part in uri.split('/') {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    parts.pop()?;
+                }
+                part => parts.push(part),
+            }
+        }
+        Some(parts.join("/"))
+    }
+
     fn should_ignore(path: &Path, patterns: &[String]) -> bool {
         for pattern in patterns {
             if glob::Pattern::new(pattern)
@@ -598,4 +734,23 @@ mod tests {
         assert!(DependencyWeight::Frequency(20).is_significant());
         assert!(!DependencyWeight::Frequency(5).is_significant());
     }
+
+    #[test]
+    fn test_count_dart_type_declarations() {
+        let source = r#"
+/// A repository.
+abstract class Repository {}
+abstract interface class Cache {}
+sealed class Result {}
+mixin Logging on Repository {}
+mixin class Helper {}
+final class Config {}
+enum Mode { light, dark }
+extension type UserId(int value) {}
+/* class Commented {} */
+// class Ignored {}
+final className = 'class';
+"#;
+        assert_eq!(utils::count_dart_type_declarations(source), (4, 8));
+    }
 }
\ No newline at end of file
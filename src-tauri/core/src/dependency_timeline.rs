@@ -0,0 +1,261 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::RawDependency;
+use crate::dependency_view::normalize_path;
+use crate::models::{DependencyInterval, FileDependency, ImportType, TemporalFileDependency};
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+/// Temporal index of every file dependency seen across the analyzed snapshots.
+///
+/// Snapshots are sampled, so "introduced in" is the first analyzed commit that
+/// contains the edge; the exact commit can lie anywhere since the previous snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyTimeline {
+    entries: Vec<TemporalFileDependency>,
+}
+
+impl DependencyTimeline {
+    /// Build the index from snapshots in chronological order
+    pub fn build(snapshots: &[CommitSnapshot]) -> Self {
+        let mut tracked: BTreeMap<(String, String), EdgeTrack> = BTreeMap::new();
+
+        for (position, snapshot) in snapshots.iter().enumerate() {
+            let commit = &snapshot.commit_info;
+            let mut present: HashSet<(String, String)> = HashSet::new();
+
+            for dep in &snapshot.analysis_result.dependencies {
+                let key = (normalize_path(&dep.source_file), normalize_path(&dep.target_file));
+                if !present.insert(key.clone()) {
+                    continue;
+                }
+
+                let track = tracked.entry(key).or_insert_with(|| EdgeTrack::new(dep, position));
+                track.latest = dep.clone();
+                track.presence_count += 1;
+                track.strength_over_time.push((
+                    commit.timestamp.to_string(),
+                    dep.weight.as_normalized_float(),
+                ));
+
+                if !track.open {
+                    track.open = true;
+                    track.authors.insert(commit.author_name.clone());
+                    track.intervals.push(DependencyInterval {
+                        introduced_in: commit.hash.clone(),
+                        introduced_by: commit.author_name.clone(),
+                        introduced_at: commit.timestamp,
+                        removed_in: None,
+                        removed_by: None,
+                        removed_at: None,
+                        last_seen: commit.hash.clone(),
+                    });
+                }
+                if let Some(interval) = track.intervals.last_mut() {
+                    interval.last_seen = commit.hash.clone();
+                }
+            }
+
+            // Close intervals of edges that disappeared in this snapshot
+            for (key, track) in tracked.iter_mut() {
+                if track.open && !present.contains(key) {
+                    track.open = false;
+                    if let Some(interval) = track.intervals.last_mut() {
+                        interval.removed_in = Some(commit.hash.clone());
+                        interval.removed_by = Some(commit.author_name.clone());
+                        interval.removed_at = Some(commit.timestamp);
+                    }
+                }
+            }
+        }
+
+        let entries = tracked
+            .into_values()
+            .map(|track| track.into_temporal(snapshots))
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[TemporalFileDependency] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lifecycle of a single edge
+    pub fn find(&self, source_file: &str, target_file: &str) -> Option<&TemporalFileDependency> {
+        let source = normalize_path(source_file.as_ref());
+        let target = normalize_path(target_file.as_ref());
+        self.entries.iter().find(|e| {
+            normalize_path(&e.dependency.source_file) == source
+                && normalize_path(&e.dependency.target_file) == target
+        })
+    }
+
+    /// Edges ordered from most stable (long-lived, never removed) to least
+    pub fn by_stability(&self) -> Vec<&TemporalFileDependency> {
+        let mut entries: Vec<&TemporalFileDependency> = self.entries.iter().collect();
+        entries.sort_by(|a, b| {
+            b.stability_score
+                .partial_cmp(&a.stability_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.strength_over_time.len().cmp(&a.strength_over_time.len()))
+        });
+        entries
+    }
+
+    /// Edges that were removed and re-added at least once, most churny first
+    pub fn churny(&self) -> Vec<&TemporalFileDependency> {
+        let mut entries: Vec<&TemporalFileDependency> = self
+            .entries
+            .iter()
+            .filter(|e| e.intervals.len() > 1)
+            .collect();
+        entries.sort_by(|a, b| {
+            b.intervals
+                .len()
+                .cmp(&a.intervals.len())
+                .then_with(|| {
+                    a.stability_score
+                        .partial_cmp(&b.stability_score)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+        });
+        entries
+    }
+}
+
+/// Accumulator for one edge while walking the snapshots
+struct EdgeTrack {
+    latest: RawDependency,
+    first_position: usize,
+    presence_count: usize,
+    open: bool,
+    authors: HashSet<String>,
+    intervals: Vec<DependencyInterval>,
+    strength_over_time: Vec<(String, f64)>,
+}
+
+impl EdgeTrack {
+    fn new(dep: &RawDependency, position: usize) -> Self {
+        Self {
+            latest: dep.clone(),
+            first_position: position,
+            presence_count: 0,
+            open: false,
+            authors: HashSet::new(),
+            intervals: Vec::new(),
+            strength_over_time: Vec::new(),
+        }
+    }
+
+    fn into_temporal(self, snapshots: &[CommitSnapshot]) -> TemporalFileDependency {
+        let first = self.intervals.first().cloned();
+        let last = self.intervals.last().cloned();
+
+        // Share of snapshots since introduction that contain the edge, divided by
+        // the number of times it was (re)introduced
+        let observed = snapshots.len() - self.first_position;
+        let presence_ratio = self.presence_count as f64 / observed.max(1) as f64;
+        let stability_score = presence_ratio / self.intervals.len().max(1) as f64;
+
+        let removed_in = last.as_ref().and_then(|i| i.removed_in.clone());
+        let last_seen = if removed_in.is_some() {
+            last.as_ref().map(|i| i.last_seen.clone())
+        } else {
+            None
+        };
+
+        TemporalFileDependency {
+            dependency: file_dependency(&self.latest),
+            first_seen: first.as_ref().map(|i| i.introduced_in.clone()).unwrap_or_default(),
+            last_seen,
+            authors: self.authors,
+            stability_score,
+            strength_over_time: self.strength_over_time,
+            introduced_by: first.map(|i| i.introduced_by).unwrap_or_default(),
+            removed_in,
+            reappeared: self.intervals.len() > 1,
+            intervals: self.intervals,
+        }
+    }
+}
+
+/// Convert an analyzer dependency into the persistent raw-layer form
+pub fn file_dependency(dep: &RawDependency) -> FileDependency {
+    let import_statement = dep.import_statement.clone().unwrap_or_default();
+    let import_type = if import_statement.contains("package:flutter/") || import_statement.contains("dart:") {
+        ImportType::External
+    } else if import_statement.contains("package:") {
+        ImportType::Package
+    } else {
+        ImportType::Relative
+    };
+
+    FileDependency {
+        source_file: PathBuf::from(normalize_path(&dep.source_file)),
+        target_file: PathBuf::from(normalize_path(&dep.target_file)),
+        import_statement,
+        line_number: dep.line_number.unwrap_or(0),
+        import_type,
+        symbols_imported: dep.symbols.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{commit, dep, snapshot};
+
+    #[test]
+    fn test_edge_lifecycle() {
+        let mut snapshots = vec![
+            snapshot("c1", 100, vec![dep("a.dart", "b.dart")]),
+            snapshot("c2", 200, vec![dep("a.dart", "b.dart"), dep("a.dart", "c.dart")]),
+            snapshot("c3", 300, vec![dep("a.dart", "b.dart")]),
+            snapshot("c4", 400, vec![dep("a.dart", "b.dart"), dep("a.dart", "c.dart")]),
+        ];
+        snapshots[1].commit_info = commit("c2", "bob", 200);
+        snapshots[2].commit_info = commit("c3", "carol", 300);
+
+        let timeline = DependencyTimeline::build(&snapshots);
+        assert_eq!(timeline.entries().len(), 2);
+
+        let stable = timeline.find("a.dart", "b.dart").unwrap();
+        assert_eq!(stable.first_seen, "c1");
+        assert_eq!(stable.introduced_by, "alice");
+        assert_eq!(stable.last_seen, None);
+        assert!(!stable.reappeared);
+        assert_eq!(stable.stability_score, 1.0);
+        assert_eq!(stable.strength_over_time.len(), 4);
+
+        let churny = timeline.find("a.dart", "c.dart").unwrap();
+        assert_eq!(churny.first_seen, "c2");
+        assert_eq!(churny.introduced_by, "bob");
+        assert!(churny.reappeared);
+        assert_eq!(churny.intervals.len(), 2);
+        assert_eq!(churny.intervals[0].removed_in.as_deref(), Some("c3"));
+        assert_eq!(churny.intervals[0].removed_by.as_deref(), Some("carol"));
+        assert_eq!(churny.removed_in, None);
+
+        assert_eq!(timeline.churny().len(), 1);
+        assert_eq!(normalize_path(&timeline.by_stability()[0].dependency.target_file), "b.dart");
+    }
+
+    #[test]
+    fn test_removed_edge_keeps_last_seen() {
+        let snapshots = vec![
+            snapshot("c1", 100, vec![dep("a.dart", "b.dart")]),
+            snapshot("c2", 200, vec![dep("a.dart", "b.dart")]),
+            snapshot("c3", 300, vec![dep("b.dart", "c.dart")]),
+        ];
+
+        let timeline = DependencyTimeline::build(&snapshots);
+        let removed = timeline.find("a.dart", "b.dart").unwrap();
+        assert_eq!(removed.last_seen.as_deref(), Some("c2"));
+        assert_eq!(removed.removed_in.as_deref(), Some("c3"));
+        assert!((removed.stability_score - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
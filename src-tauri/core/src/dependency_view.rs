@@ -0,0 +1,444 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::{RawDependency, RelationshipType};
+use crate::file_history::FileHistory;
+use crate::models::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Node kind names accepted in `FilterCriteria::node_types`
+pub const NODE_TYPE_FILE: &str = "file";
+pub const NODE_TYPE_FOLDER: &str = "folder";
+
+impl DependencyView {
+    /// Create an empty view collapsed at the given folder depth
+    pub fn new(folder_depth: usize) -> Self {
+        Self {
+            commit_hash: None,
+            folder_depth,
+            expanded_folders: HashSet::new(),
+            visible_dependencies: Vec::new(),
+            layout_state: LayoutState {
+                algorithm: LayoutAlgorithm::Hybrid,
+                viewport: Viewport {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 1200.0,
+                    height: 800.0,
+                },
+                zoom_level: 1.0,
+            },
+            filter_criteria: FilterCriteria::default(),
+        }
+    }
+
+    /// Expand or collapse a folder. Collapsing also collapses everything below it.
+    pub fn set_folder_expanded(&mut self, folder: &Path, expand: bool) {
+        let folder = PathBuf::from(normalize_path(folder));
+        if expand {
+            self.expanded_folders.insert(folder);
+        } else {
+            self.expanded_folders.retain(|f| !f.starts_with(&folder));
+        }
+    }
+
+    /// Recompute `visible_dependencies` from a snapshot using the current
+    /// folder depth, expanded folders and filter criteria
+    pub fn rebuild(&mut self, snapshot: &CommitSnapshot, history: Option<&FileHistory>) {
+        self.commit_hash = Some(snapshot.commit_info.hash.clone());
+        self.visible_dependencies = FolderAggregator::new(self, snapshot, history).aggregate();
+    }
+
+    /// Every node the view shows for a snapshot's files, including those without
+    /// dependencies, sorted by path
+    pub fn visible_nodes(&self, snapshot: &CommitSnapshot) -> Vec<String> {
+        let aggregator = FolderAggregator::new(self, snapshot, None);
+        let nodes: BTreeSet<String> = aggregator
+            .known_files
+            .iter()
+            .map(|file| aggregator.visible_node(file))
+            .collect();
+        nodes
+            .into_iter()
+            .filter(|node| self.filter_criteria.accepts_node_type(&aggregator.node_type(node)))
+            .collect()
+    }
+}
+
+impl Default for FilterCriteria {
+    fn default() -> Self {
+        Self {
+            min_dependency_strength: 0.1,
+            show_external_deps: false,
+            author_filter: None,
+            time_range: None,
+            node_types: HashSet::new(),
+        }
+    }
+}
+
+impl FilterCriteria {
+    /// Check whether a snapshot passes the author and time range filters
+    pub fn accepts_snapshot(&self, snapshot: &CommitSnapshot) -> bool {
+        if let Some(ref authors) = self.author_filter {
+            if !authors.is_empty()
+                && !authors.contains(&snapshot.commit_info.author_name)
+                && !authors.contains(&snapshot.commit_info.author_email)
+            {
+                return false;
+            }
+        }
+
+        if let Some((ref start, ref end)) = self.time_range {
+            let timestamp = snapshot.commit_info.timestamp;
+            if let Some(start) = parse_timestamp(start) {
+                if timestamp < start {
+                    return false;
+                }
+            }
+            if let Some(end) = parse_timestamp(end) {
+                if timestamp > end {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn accepts_node_type(&self, node_type: &NodeType) -> bool {
+        if self.node_types.is_empty() {
+            return true;
+        }
+        let kind = match node_type {
+            NodeType::Folder { .. } => NODE_TYPE_FOLDER,
+            NodeType::File { .. } => NODE_TYPE_FILE,
+        };
+        self.node_types.contains(kind)
+    }
+}
+
+/// Pick the snapshot a view should be built from.
+///
+/// Only snapshots accepted by the author/time filters are considered. A
+/// `commit_hash` selects that snapshot, or nothing when the filters exclude it;
+/// without one the latest accepted snapshot is used.
+pub fn select_snapshot<'a>(
+    snapshots: &'a [CommitSnapshot],
+    commit_hash: Option<&str>,
+    filters: &FilterCriteria,
+) -> Option<&'a CommitSnapshot> {
+    let mut candidates = snapshots.iter().filter(|s| filters.accepts_snapshot(s));
+
+    match commit_hash {
+        Some(hash) => candidates.find(|s| s.commit_info.hash == hash),
+        None => candidates.next_back(),
+    }
+}
+
+/// Find the latest snapshot at or before the given timestamp
+pub fn snapshot_at_timestamp<'a>(
+    snapshots: &'a [CommitSnapshot],
+    timestamp: &str,
+    filters: &FilterCriteria,
+) -> Option<&'a CommitSnapshot> {
+    let target = parse_timestamp(timestamp)?;
+    snapshots
+        .iter()
+        .filter(|s| filters.accepts_snapshot(s))
+        .filter(|s| s.commit_info.timestamp <= target)
+        .max_by_key(|s| s.commit_info.timestamp)
+}
+
+/// Parse a timestamp given either as Unix seconds or RFC 3339
+pub fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Some(seconds);
+    }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.timestamp())
+}
+
+/// Normalize a path to forward slashes without leading "./" or "/"
+pub fn normalize_path(path: &Path) -> String {
+    let normalized = path.to_string_lossy().replace('\\', "/");
+    normalized
+        .trim_start_matches("./")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+/// Collapses file-level dependencies of one snapshot into view-level edges
+struct FolderAggregator<'a> {
+    view: &'a DependencyView,
+    snapshot: &'a CommitSnapshot,
+    history: Option<&'a FileHistory>,
+    known_files: HashSet<String>,
+}
+
+impl<'a> FolderAggregator<'a> {
+    fn new(view: &'a DependencyView, snapshot: &'a CommitSnapshot, history: Option<&'a FileHistory>) -> Self {
+        let result = &snapshot.analysis_result;
+        let mut known_files: HashSet<String> = result
+            .analyzed_files
+            .iter()
+            .map(|p| normalize_path(p))
+            .collect();
+
+        if let Some(ref node_metrics) = result.node_metrics {
+            known_files.extend(node_metrics.keys().map(|k| normalize_path(Path::new(k))));
+        }
+
+        Self {
+            view,
+            snapshot,
+            history,
+            known_files,
+        }
+    }
+
+    fn aggregate(&self) -> Vec<ViewDependency> {
+        let filters = &self.view.filter_criteria;
+
+        // Group constituent dependencies by their visible endpoints
+        let mut groups: BTreeMap<(String, String), Vec<&RawDependency>> = BTreeMap::new();
+        for dep in &self.snapshot.analysis_result.dependencies {
+            if !filters.show_external_deps && self.is_external(dep) {
+                continue;
+            }
+
+            let source = self.visible_node(&normalize_path(&dep.source_file));
+            let target = self.visible_node(&normalize_path(&dep.target_file));
+
+            // Dependencies inside a collapsed folder are not drawn
+            if source == target {
+                continue;
+            }
+
+            groups.entry((source, target)).or_default().push(dep);
+        }
+
+        let mut node_cache: HashMap<String, NodeType> = HashMap::new();
+        let mut temporal_cache: HashMap<String, TemporalMetadata> = HashMap::new();
+        let mut visible = Vec::new();
+
+        for ((source, target), deps) in groups {
+            let strength: f64 = deps.iter().map(|d| d.weight.as_normalized_float()).sum();
+            if strength < filters.min_dependency_strength {
+                continue;
+            }
+
+            let source_type = node_cache
+                .entry(source.clone())
+                .or_insert_with(|| self.node_type(&source))
+                .clone();
+            let target_type = node_cache
+                .entry(target.clone())
+                .or_insert_with(|| self.node_type(&target))
+                .clone();
+
+            if !filters.accepts_node_type(&source_type) || !filters.accepts_node_type(&target_type) {
+                continue;
+            }
+
+            let is_export = deps
+                .iter()
+                .all(|d| matches!(d.relationship_type, RelationshipType::Export));
+
+            visible.push(ViewDependency {
+                source_node: NodePath(PathBuf::from(&source)),
+                target_node: NodePath(PathBuf::from(&target)),
+                node_type: source_type,
+                strength,
+                constituent_files: deps
+                    .iter()
+                    .map(|d| (d.source_file.clone(), d.target_file.clone()))
+                    .collect(),
+                temporal_data: temporal_cache
+                    .entry(source.clone())
+                    .or_insert_with(|| self.temporal_data(&source))
+                    .clone(),
+                visual_properties: visual_properties(strength, is_export),
+            });
+        }
+
+        visible
+    }
+
+    /// Map a file to the node it is shown as: the first collapsed ancestor
+    /// folder at or below `folder_depth`, or the file itself
+    fn visible_node(&self, file: &str) -> String {
+        let components: Vec<&str> = file.split('/').filter(|c| !c.is_empty()).collect();
+        let folder_count = components.len().saturating_sub(1);
+        let min_depth = self.view.folder_depth.max(1);
+
+        for depth in min_depth..=folder_count {
+            let folder = components[..depth].join("/");
+            if !self.view.expanded_folders.contains(Path::new(&folder)) {
+                return folder;
+            }
+        }
+
+        components.join("/")
+    }
+
+    fn node_type(&self, node: &str) -> NodeType {
+        if self.known_files.contains(node) {
+            let path = PathBuf::from(node);
+            let sloc = self
+                .snapshot
+                .analysis_result
+                .node_metrics
+                .as_ref()
+                .and_then(|m| {
+                    m.iter()
+                        .find(|(k, _)| normalize_path(Path::new(k.as_str())) == node)
+                        .map(|(_, metrics)| metrics.sloc)
+                })
+                .unwrap_or(0);
+            let parent_folder = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            return NodeType::File {
+                path,
+                sloc,
+                parent_folder,
+            };
+        }
+
+        let prefix = format!("{}/", node);
+        let mut file_count = 0;
+        let mut child_folders: HashSet<PathBuf> = HashSet::new();
+        for file in &self.known_files {
+            if let Some(rest) = file.strip_prefix(&prefix) {
+                file_count += 1;
+                if let Some((child, _)) = rest.split_once('/') {
+                    child_folders.insert(PathBuf::from(format!("{}{}", prefix, child)));
+                }
+            }
+        }
+
+        let mut child_folders: Vec<PathBuf> = child_folders.into_iter().collect();
+        child_folders.sort();
+
+        NodeType::Folder {
+            path: PathBuf::from(node),
+            file_count,
+            child_folders,
+        }
+    }
+
+    /// Targets that were not part of the analyzed file set live outside the project
+    fn is_external(&self, dep: &RawDependency) -> bool {
+        !self.known_files.is_empty() && !self.known_files.contains(&normalize_path(&dep.target_file))
+    }
+
+    /// History of the source node as of the snapshot, falling back to the snapshot commit
+    /// when no git history is available
+    fn temporal_data(&self, node: &str) -> TemporalMetadata {
+        let commit = &self.snapshot.commit_info;
+        if let Some(metadata) = self
+            .history
+            .and_then(|h| h.node_metadata(node, Some(commit.timestamp)))
+        {
+            return metadata;
+        }
+
+        TemporalMetadata {
+            creation_commit: commit.hash.clone(),
+            modification_commits: Vec::new(),
+            deletion_commit: None,
+            primary_authors: vec![commit.author_name.clone()],
+            change_frequency: 0.0,
+            created_at: commit.timestamp,
+            last_modified_at: commit.timestamp,
+            lines_added: 0,
+            lines_removed: 0,
+        }
+    }
+}
+
+fn visual_properties(strength: f64, is_export: bool) -> VisualProperties {
+    VisualProperties {
+        color: if is_export { "#7c3aed".to_string() } else { "#64748b".to_string() },
+        thickness: 1.0 + strength.max(1.0).ln(),
+        opacity: 0.85,
+        style: if is_export { EdgeStyle::Dashed } else { EdgeStyle::Solid },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_collapses_files_into_folders() {
+        let snapshot = snapshot("a", 1, vec![
+            dep("lib/ui/button.dart", "lib/data/repo.dart"),
+            dep("lib/ui/page.dart", "lib/data/api.dart"),
+            dep("lib/ui/page.dart", "lib/ui/button.dart"),
+            dep("lib/main.dart", "lib/ui/page.dart"),
+        ]);
+
+        let mut view = DependencyView::new(2);
+        view.rebuild(&snapshot, None);
+
+        let edges: Vec<(String, String, f64, usize)> = view.visible_dependencies.iter()
+            .map(|d| (
+                normalize_path(&d.source_node.0),
+                normalize_path(&d.target_node.0),
+                d.strength,
+                d.constituent_files.len(),
+            ))
+            .collect();
+
+        assert_eq!(edges.len(), 2);
+        assert!(edges.contains(&("lib/ui".to_string(), "lib/data".to_string(), 2.0, 2)));
+        assert!(edges.contains(&("lib/main.dart".to_string(), "lib/ui".to_string(), 1.0, 1)));
+    }
+
+    #[test]
+    fn test_expanded_folder_shows_children() {
+        let snapshot = snapshot("a", 1, vec![
+            dep("lib/ui/page.dart", "lib/ui/button.dart"),
+            dep("lib/ui/page.dart", "lib/data/api.dart"),
+        ]);
+
+        let mut view = DependencyView::new(2);
+        view.set_folder_expanded(Path::new("lib/ui"), true);
+        view.rebuild(&snapshot, None);
+        assert_eq!(view.visible_dependencies.len(), 2);
+
+        view.set_folder_expanded(Path::new("lib"), false);
+        view.rebuild(&snapshot, None);
+        assert_eq!(view.visible_dependencies.len(), 1);
+    }
+
+    #[test]
+    fn test_filters() {
+        let snapshots = vec![
+            snapshot("a", 100, vec![dep("lib/a/x.dart", "lib/b/y.dart")]),
+            snapshot("b", 200, vec![dep("lib/a/x.dart", "lib/b/y.dart")]),
+        ];
+
+        let mut filters = FilterCriteria::default();
+        assert_eq!(select_snapshot(&snapshots, None, &filters).unwrap().commit_info.hash, "b");
+
+        filters.time_range = Some(("0".to_string(), "150".to_string()));
+        assert!(select_snapshot(&snapshots, Some("b"), &filters).is_none());
+        assert_eq!(select_snapshot(&snapshots, Some("a"), &filters).unwrap().commit_info.hash, "a");
+        assert_eq!(snapshot_at_timestamp(&snapshots, "1970-01-01T00:03:00Z", &FilterCriteria::default())
+            .unwrap().commit_info.hash, "a");
+
+        let mut view = DependencyView::new(2);
+        view.filter_criteria.min_dependency_strength = 2.0;
+        view.rebuild(&snapshots[0], None);
+        assert!(view.visible_dependencies.is_empty());
+        assert_eq!(view.commit_hash.as_deref(), Some("a"));
+
+        view.filter_criteria.min_dependency_strength = 0.0;
+        view.filter_criteria.node_types.insert(NODE_TYPE_FILE.to_string());
+        view.rebuild(&snapshots[0], None);
+        assert!(view.visible_dependencies.is_empty());
+    }
+}
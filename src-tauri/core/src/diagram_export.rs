@@ -0,0 +1,433 @@
+//! Diagram-as-code exports of one snapshot for Markdown docs: Mermaid flowcharts,
+//! PlantUML component diagrams and Structurizr DSL workspaces, plus a plain SVG
+//! rendering for reports. Files are grouped into folders the same way as the
+//! graph view, and cycles between the drawn nodes are highlighted.
+
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::DependencyGraph;
+use crate::graph_export::escape_xml;
+use crate::models::DependencyView;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Write};
+use std::path::Path;
+
+const CYCLE_COLOR: &str = "#dc2626";
+const CYCLE_FILL: &str = "#fee2e2";
+
+struct DiagramNode {
+    path: String,
+    /// Shown relative to the subtree
+    label: String,
+    is_folder: bool,
+    in_cycle: bool,
+}
+
+struct DiagramEdge {
+    source: usize,
+    target: usize,
+    /// Number of file-to-file dependencies folded into this edge
+    file_dependencies: usize,
+    in_cycle: bool,
+}
+
+/// Folder-aggregated graph of a snapshot, ready to be written out
+struct Diagram {
+    title: String,
+    nodes: Vec<DiagramNode>,
+    edges: Vec<DiagramEdge>,
+}
+
+impl Diagram {
+    /// Collapse files into folders `folder_depth` levels below the subtree (or the
+    /// project root) and keep the nodes and edges inside the subtree
+    fn new(snapshot: &CommitSnapshot, folder_depth: usize, subtree: Option<&str>) -> Self {
+        let subtree = subtree
+            .map(|s| normalize_path(Path::new(s)).trim_end_matches('/').to_string())
+            .filter(|s| !s.is_empty());
+        let prefix = subtree.as_ref().map(|s| format!("{}/", s));
+        let base_depth = subtree.as_ref().map_or(0, |s| s.split('/').count());
+
+        let mut view = DependencyView::new(base_depth + folder_depth.max(1));
+        view.filter_criteria.min_dependency_strength = 0.0;
+        view.rebuild(snapshot, None);
+
+        let files: HashSet<String> = snapshot.analysis_result.analyzed_files.iter().map(|f| normalize_path(f)).collect();
+        let in_subtree = |node: &str| prefix.as_ref().is_none_or(|prefix| node.starts_with(prefix.as_str()));
+        let mut graph = DependencyGraph::default();
+        for node in view.visible_nodes(snapshot) {
+            if in_subtree(&node) {
+                graph.add_node(&node);
+            }
+        }
+        let mut file_dependencies: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        for dep in &view.visible_dependencies {
+            let source = normalize_path(&dep.source_node.0);
+            let target = normalize_path(&dep.target_node.0);
+            if !in_subtree(&source) || !in_subtree(&target) {
+                continue;
+            }
+            let (source, target) = (graph.add_node(&source), graph.add_node(&target));
+            graph.add_edge(source, target);
+            *file_dependencies.entry((source, target)).or_default() += dep.constituent_files.len();
+        }
+
+        let mut cycle_of: HashMap<usize, usize> = HashMap::new();
+        let cycles = graph.strongly_connected_components().into_iter().filter(|c| c.len() > 1);
+        for (cycle, members) in cycles.enumerate() {
+            cycle_of.extend(members.into_iter().map(|n| (n, cycle)));
+        }
+
+        // Sort nodes by path so the output is stable across runs
+        let mut order: Vec<usize> = (0..graph.node_count()).collect();
+        order.sort_by(|&a, &b| graph.nodes[a].cmp(&graph.nodes[b]));
+        let position: HashMap<usize, usize> = order.iter().enumerate().map(|(pos, &n)| (n, pos)).collect();
+
+        let nodes = order
+            .iter()
+            .map(|&n| {
+                let path = graph.nodes[n].clone();
+                let is_folder = !files.contains(&path);
+                let relative = prefix.as_ref().and_then(|p| path.strip_prefix(p.as_str())).unwrap_or(&path);
+                DiagramNode {
+                    label: if is_folder { format!("{}/", relative) } else { relative.to_string() },
+                    path,
+                    is_folder,
+                    in_cycle: cycle_of.contains_key(&n),
+                }
+            })
+            .collect();
+
+        let mut edges: Vec<DiagramEdge> = file_dependencies
+            .into_iter()
+            .map(|((source, target), file_dependencies)| DiagramEdge {
+                source: position[&source],
+                target: position[&target],
+                file_dependencies,
+                in_cycle: cycle_of.get(&source).is_some_and(|c| cycle_of.get(&target) == Some(c)),
+            })
+            .collect();
+        edges.sort_by_key(|e| (e.source, e.target));
+
+        let title = match subtree {
+            Some(subtree) => format!("{} at {}", subtree, short_hash(&snapshot.commit_info.hash)),
+            None => format!("Dependencies at {}", short_hash(&snapshot.commit_info.hash)),
+        };
+
+        Self { title, nodes, edges }
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
+
+/// Mermaid flowchart
+pub fn mermaid(snapshot: &CommitSnapshot, folder_depth: usize, subtree: Option<&str>) -> String {
+    let mut out = String::new();
+    write_mermaid(&mut out, &Diagram::new(snapshot, folder_depth, subtree)).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_mermaid(out: &mut String, diagram: &Diagram) -> fmt::Result {
+    let label = |text: &str| text.replace('"', "#quot;");
+
+    writeln!(out, "---")?;
+    writeln!(out, "title: {}", diagram.title)?;
+    writeln!(out, "---")?;
+    writeln!(out, "flowchart LR")?;
+    for (id, node) in diagram.nodes.iter().enumerate() {
+        if node.is_folder {
+            writeln!(out, "    n{}[\"{}\"]", id, label(&node.label))?;
+        } else {
+            writeln!(out, "    n{}(\"{}\")", id, label(&node.label))?;
+        }
+    }
+    for edge in &diagram.edges {
+        if edge.file_dependencies > 1 {
+            writeln!(out, "    n{} -->|{}| n{}", edge.source, edge.file_dependencies, edge.target)?;
+        } else {
+            writeln!(out, "    n{} --> n{}", edge.source, edge.target)?;
+        }
+    }
+
+    let cyclic_nodes: Vec<String> = (0..diagram.nodes.len())
+        .filter(|&id| diagram.nodes[id].in_cycle)
+        .map(|id| format!("n{}", id))
+        .collect();
+    if !cyclic_nodes.is_empty() {
+        writeln!(out, "    classDef cycle fill:{},stroke:{},stroke-width:2px", CYCLE_FILL, CYCLE_COLOR)?;
+        writeln!(out, "    class {} cycle", cyclic_nodes.join(","))?;
+    }
+    let cyclic_edges: Vec<String> = (0..diagram.edges.len())
+        .filter(|&index| diagram.edges[index].in_cycle)
+        .map(|index| index.to_string())
+        .collect();
+    if !cyclic_edges.is_empty() {
+        writeln!(out, "    linkStyle {} stroke:{},stroke-width:2px", cyclic_edges.join(","), CYCLE_COLOR)?;
+    }
+    Ok(())
+}
+
+/// PlantUML component diagram
+pub fn plantuml(snapshot: &CommitSnapshot, folder_depth: usize, subtree: Option<&str>) -> String {
+    let mut out = String::new();
+    write_plantuml(&mut out, &Diagram::new(snapshot, folder_depth, subtree)).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_plantuml(out: &mut String, diagram: &Diagram) -> fmt::Result {
+    writeln!(out, "@startuml")?;
+    writeln!(out, "title {}", diagram.title)?;
+    writeln!(out, "left to right direction")?;
+    writeln!(out, "skinparam component {{")?;
+    writeln!(out, "  BackgroundColor<<cycle>> {}", CYCLE_FILL)?;
+    writeln!(out, "  BorderColor<<cycle>> {}", CYCLE_COLOR)?;
+    writeln!(out, "}}")?;
+    for (id, node) in diagram.nodes.iter().enumerate() {
+        let kind = if node.is_folder { "folder" } else { "file" };
+        let stereotype = if node.in_cycle { " <<cycle>>" } else { "" };
+        writeln!(out, "component \"{}\" as n{} <<{}>>{}", node.label.replace('"', "'"), id, kind, stereotype)?;
+    }
+    for edge in &diagram.edges {
+        let arrow = if edge.in_cycle { format!("-[{},bold]->", CYCLE_COLOR) } else { "-->".to_string() };
+        if edge.file_dependencies > 1 {
+            writeln!(out, "n{} {} n{} : {}", edge.source, arrow, edge.target, edge.file_dependencies)?;
+        } else {
+            writeln!(out, "n{} {} n{}", edge.source, arrow, edge.target)?;
+        }
+    }
+    writeln!(out, "@enduml")
+}
+
+/// Structurizr DSL workspace with one container per node and a container view
+pub fn structurizr(snapshot: &CommitSnapshot, folder_depth: usize, subtree: Option<&str>) -> String {
+    let mut out = String::new();
+    write_structurizr(&mut out, &Diagram::new(snapshot, folder_depth, subtree)).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_structurizr(out: &mut String, diagram: &Diagram) -> fmt::Result {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    let tags = |in_cycle: bool| if in_cycle { " \"Cycle\"" } else { "" };
+
+    writeln!(out, "workspace {} {{", quote(&diagram.title))?;
+    writeln!(out, "    model {{")?;
+    writeln!(out, "        project = softwareSystem \"Project\" {{")?;
+    for (id, node) in diagram.nodes.iter().enumerate() {
+        let kind = if node.is_folder { "Folder" } else { "File" };
+        writeln!(
+            out,
+            "            n{} = container {} {} \"{}\"{}",
+            id, quote(&node.label), quote(&node.path), kind, tags(node.in_cycle)
+        )?;
+    }
+    writeln!(out, "        }}")?;
+    for edge in &diagram.edges {
+        let description = if edge.file_dependencies > 1 { format!("imports ({} file dependencies)", edge.file_dependencies) } else { "imports".to_string() };
+        writeln!(
+            out,
+            "        n{} -> n{} \"{}\" \"\"{}",
+            edge.source, edge.target, description, tags(edge.in_cycle)
+        )?;
+    }
+    writeln!(out, "    }}")?;
+    writeln!(out, "    views {{")?;
+    writeln!(out, "        container project \"Dependencies\" {{")?;
+    writeln!(out, "            include *")?;
+    writeln!(out, "            autoLayout lr")?;
+    writeln!(out, "        }}")?;
+    writeln!(out, "        styles {{")?;
+    writeln!(out, "            element \"Cycle\" {{")?;
+    writeln!(out, "                background {}", CYCLE_FILL)?;
+    writeln!(out, "                stroke {}", CYCLE_COLOR)?;
+    writeln!(out, "            }}")?;
+    writeln!(out, "            relationship \"Cycle\" {{")?;
+    writeln!(out, "                color {}", CYCLE_COLOR)?;
+    writeln!(out, "                thickness 4")?;
+    writeln!(out, "            }}")?;
+    writeln!(out, "        }}")?;
+    writeln!(out, "    }}")?;
+    writeln!(out, "}}")
+}
+
+/// Standalone SVG drawing, layered left to right so that dependencies point
+/// rightwards; the members of a cycle share a column
+pub fn svg(snapshot: &CommitSnapshot, folder_depth: usize, subtree: Option<&str>) -> String {
+    let mut out = String::new();
+    write_svg(&mut out, &Diagram::new(snapshot, folder_depth, subtree)).expect("writing to a String cannot fail");
+    out
+}
+
+const NODE_HEIGHT: f64 = 28.0;
+const ROW_GAP: f64 = 14.0;
+const COLUMN_GAP: f64 = 90.0;
+const MARGIN: f64 = 16.0;
+
+/// Column of every node: the longest path to it from a node nothing depends on,
+/// counted between cycles rather than files
+fn columns(diagram: &Diagram) -> Vec<usize> {
+    let mut graph = DependencyGraph::default();
+    for (id, node) in diagram.nodes.iter().enumerate() {
+        let index = graph.add_node(&node.path);
+        debug_assert_eq!(index, id);
+    }
+    for edge in &diagram.edges {
+        graph.add_edge(edge.source, edge.target);
+    }
+
+    // Components come in reverse topological order
+    let components = graph.strongly_connected_components();
+    let mut component_of = vec![0; graph.node_count()];
+    for (component, members) in components.iter().enumerate() {
+        for &node in members {
+            component_of[node] = component;
+        }
+    }
+    let mut column = vec![0; graph.node_count()];
+    for members in components.iter().rev() {
+        for &node in members {
+            for &target in &graph.successors[node] {
+                if component_of[target] != component_of[node] {
+                    column[target] = column[target].max(column[node] + 1);
+                }
+            }
+        }
+        let deepest = members.iter().map(|&n| column[n]).max().unwrap_or(0);
+        for &node in members {
+            column[node] = deepest;
+        }
+    }
+    column
+}
+
+fn write_svg(out: &mut String, diagram: &Diagram) -> fmt::Result {
+    let column = columns(diagram);
+    let column_count = column.iter().max().map_or(0, |c| c + 1);
+    let width_of = |label: &str| 16.0 + 7.0 * label.chars().count() as f64;
+
+    let mut column_width = vec![0.0_f64; column_count];
+    let mut rows = vec![0usize; column_count];
+    let mut position = Vec::with_capacity(diagram.nodes.len());
+    for (id, node) in diagram.nodes.iter().enumerate() {
+        column_width[column[id]] = column_width[column[id]].max(width_of(&node.label));
+        position.push(rows[column[id]]);
+        rows[column[id]] += 1;
+    }
+    let mut column_x = vec![MARGIN; column_count];
+    for c in 1..column_count {
+        column_x[c] = column_x[c - 1] + column_width[c - 1] + COLUMN_GAP;
+    }
+    let boxes: Vec<(f64, f64, f64)> = diagram.nodes
+        .iter()
+        .enumerate()
+        .map(|(id, node)| {
+            let y = MARGIN + position[id] as f64 * (NODE_HEIGHT + ROW_GAP);
+            (column_x[column[id]], y, width_of(&node.label))
+        })
+        .collect();
+
+    let width = column_x.last().zip(column_width.last()).map_or(2.0 * MARGIN, |(x, w)| x + w + MARGIN);
+    let height = MARGIN * 2.0 + rows.iter().max().copied().unwrap_or(0) as f64 * (NODE_HEIGHT + ROW_GAP);
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0:.0}" height="{1:.0}" viewBox="0 0 {0:.0} {1:.0}" font-family="sans-serif" font-size="12">"#,
+        width, height
+    )?;
+    writeln!(out, "<title>{}</title>", escape_xml(&diagram.title))?;
+    writeln!(out, "<defs>")?;
+    for (id, color) in [("arrow", "#64748b"), ("arrow-cycle", CYCLE_COLOR)] {
+        writeln!(
+            out,
+            r#"<marker id="{}" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="7" markerHeight="7" orient="auto"><path d="M0,0 L10,5 L0,10 z" fill="{}"/></marker>"#,
+            id, color
+        )?;
+    }
+    writeln!(out, "</defs>")?;
+
+    for edge in &diagram.edges {
+        let (sx, sy, sw) = boxes[edge.source];
+        let (tx, ty, tw) = boxes[edge.target];
+        // Leave from the right side towards later columns, otherwise from the left
+        let (x1, x2) = if tx > sx { (sx + sw, tx) } else if tx < sx { (sx, tx + tw) } else { (sx + sw, tx + tw) };
+        let (color, marker) = if edge.in_cycle { (CYCLE_COLOR, "arrow-cycle") } else { ("#64748b", "arrow") };
+        let path = if tx == sx {
+            // Same column: bow out to the right
+            let bend = sx + sw + 40.0;
+            format!("M{:.1},{:.1} C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}", x1, sy + NODE_HEIGHT / 2.0, bend, sy, bend, ty + NODE_HEIGHT, x2, ty + NODE_HEIGHT / 2.0)
+        } else {
+            format!("M{:.1},{:.1} L{:.1},{:.1}", x1, sy + NODE_HEIGHT / 2.0, x2, ty + NODE_HEIGHT / 2.0)
+        };
+        writeln!(
+            out,
+            r#"<path d="{}" fill="none" stroke="{}" stroke-width="{:.1}" marker-end="url(#{})"><title>{} file dependencies</title></path>"#,
+            path, color, 1.0 + (edge.file_dependencies as f64).ln(), marker, edge.file_dependencies
+        )?;
+    }
+
+    for (node, &(x, y, w)) in diagram.nodes.iter().zip(&boxes) {
+        let (fill, stroke) = if node.in_cycle { (CYCLE_FILL, CYCLE_COLOR) } else if node.is_folder { ("#e0e7ff", "#6366f1") } else { ("#f8fafc", "#94a3b8") };
+        let radius = if node.is_folder { 2 } else { 10 };
+        writeln!(
+            out,
+            r#"<g><title>{}</title><rect x="{:.1}" y="{:.1}" width="{:.1}" height="{}" rx="{}" fill="{}" stroke="{}"/><text x="{:.1}" y="{:.1}" text-anchor="middle" dominant-baseline="middle">{}</text></g>"#,
+            escape_xml(&node.path), x, y, w, NODE_HEIGHT, radius, fill, stroke,
+            x + w / 2.0, y + NODE_HEIGHT / 2.0, escape_xml(&node.label)
+        )?;
+    }
+    writeln!(out, "</svg>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_diagrams_aggregate_folders_and_mark_cycles() {
+        let snapshot = snapshot("c1", 100, vec![
+            dep("lib/app.dart", "lib/ui/page.dart"),
+            dep("lib/ui/page.dart", "lib/data/db.dart"),
+            dep("lib/ui/list.dart", "lib/data/db.dart"),
+            dep("lib/data/db.dart", "lib/ui/theme.dart"),
+        ]);
+
+        let diagram = Diagram::new(&snapshot, 2, None);
+        let labels: Vec<&str> = diagram.nodes.iter().map(|n| n.label.as_str()).collect();
+        assert_eq!(labels, vec!["lib/app.dart", "lib/data/", "lib/ui/"]);
+        let edges: Vec<(usize, usize, usize, bool)> = diagram.edges
+            .iter()
+            .map(|e| (e.source, e.target, e.file_dependencies, e.in_cycle))
+            .collect();
+        assert_eq!(edges, vec![(0, 2, 1, false), (1, 2, 1, true), (2, 1, 2, true)]);
+
+        let mermaid = mermaid(&snapshot, 2, None);
+        assert!(mermaid.contains("n2 -->|2| n1"));
+        assert!(mermaid.contains("class n1,n2 cycle"));
+        assert!(mermaid.contains("linkStyle 1,2 stroke"));
+
+        // Inside a subtree only its own nodes and edges remain, with or without edges
+        let subtree = Diagram::new(&snapshot, 1, Some("lib/ui/"));
+        let labels: Vec<&str> = subtree.nodes.iter().map(|n| n.label.as_str()).collect();
+        assert_eq!(labels, vec!["list.dart", "page.dart", "theme.dart"]);
+        assert!(subtree.edges.is_empty());
+
+        let plantuml = plantuml(&snapshot, 2, None);
+        assert!(plantuml.contains("component \"lib/ui/\" as n2 <<folder>> <<cycle>>"));
+
+        let structurizr = structurizr(&snapshot, 2, None);
+        assert!(structurizr.contains("n2 -> n1 \"imports (2 file dependencies)\" \"\" \"Cycle\""));
+
+        // The app comes before the cycle it depends on, whose members share a column
+        assert_eq!(columns(&diagram), vec![0, 1, 1]);
+        assert!(svg(&snapshot, 2, None).starts_with("<svg"));
+
+        // Folders without dependencies are drawn too
+        let mut snapshot = snapshot;
+        snapshot.analysis_result.analyzed_files.push(PathBuf::from("lib/util/strings.dart"));
+        let labels: Vec<String> = Diagram::new(&snapshot, 2, None).nodes.into_iter().map(|n| n.label).collect();
+        assert_eq!(labels, vec!["lib/app.dart", "lib/data/", "lib/ui/", "lib/util/"]);
+    }
+}
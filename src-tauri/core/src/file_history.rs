@@ -0,0 +1,328 @@
+use crate::dependency_view::normalize_path;
+use crate::git_navigator::{CommitChanges, FileChangeKind};
+use crate::models::TemporalMetadata;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Maximum number of authors reported as primary for a node
+const MAX_PRIMARY_AUTHORS: usize = 3;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// A single commit touching a file, from the full first-parent history
+#[derive(Debug, Clone)]
+struct FileEvent {
+    commit: String,
+    timestamp: i64,
+    author: String,
+    kind: FileChangeKind,
+    lines_added: usize,
+    lines_removed: usize,
+    /// The file left this path (renamed or moved out of the project)
+    moved_away: bool,
+}
+
+/// Per-file change history of the project, independent of commit sampling.
+///
+/// Paths are project-relative (the configured subfolder is stripped), matching
+/// the paths produced by the analyzers.
+#[derive(Debug, Clone, Default)]
+pub struct FileHistory {
+    events: HashMap<String, Vec<FileEvent>>,
+    /// New path -> path it was renamed from
+    renamed_from: HashMap<String, String>,
+    /// Timestamp of the newest commit in the history
+    history_end: i64,
+}
+
+impl FileHistory {
+    /// Index the changes of a first-parent history (oldest first)
+    pub fn build(history: &[CommitChanges], subfolder: Option<&str>) -> Self {
+        let mut file_history = Self::default();
+
+        for entry in history {
+            let commit = &entry.commit;
+            file_history.history_end = file_history.history_end.max(commit.timestamp);
+
+            for change in &entry.changes {
+                let event = |kind| FileEvent {
+                    commit: commit.hash.clone(),
+                    timestamp: commit.timestamp,
+                    author: commit.author_name.clone(),
+                    kind,
+                    lines_added: change.lines_added,
+                    lines_removed: change.lines_removed,
+                    moved_away: false,
+                };
+                // The line changes of a move are attributed to the destination
+                let moved_away = FileEvent {
+                    lines_added: 0,
+                    lines_removed: 0,
+                    moved_away: true,
+                    ..event(FileChangeKind::Renamed)
+                };
+
+                let path = project_relative(&change.path, subfolder);
+                let old_path = change
+                    .old_path
+                    .as_deref()
+                    .and_then(|p| project_relative(p, subfolder));
+
+                match (change.kind, old_path, path) {
+                    (FileChangeKind::Renamed, Some(old), Some(new)) => {
+                        file_history.push(&old, moved_away);
+                        file_history.push(&new, event(FileChangeKind::Renamed));
+                        file_history.renamed_from.insert(new, old);
+                    }
+                    // Moved into the project from elsewhere in the repository
+                    (FileChangeKind::Renamed, None, Some(new)) => {
+                        file_history.push(&new, event(FileChangeKind::Added));
+                    }
+                    // Moved out of the project
+                    (FileChangeKind::Renamed, Some(old), None) => {
+                        file_history.push(&old, moved_away);
+                    }
+                    (kind, _, Some(path)) => file_history.push(&path, event(kind)),
+                    _ => {}
+                }
+            }
+        }
+
+        file_history
+    }
+
+    fn push(&mut self, path: &str, event: FileEvent) {
+        self.events.entry(path.to_string()).or_default().push(event);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Every file path ever seen in the history
+    pub fn files(&self) -> impl Iterator<Item = &String> {
+        self.events.keys()
+    }
+
+    /// Lifecycle metadata of a file, or the aggregate of all files below a folder.
+    /// With `as_of`, only commits at or before that timestamp are considered.
+    pub fn node_metadata(&self, node: &str, as_of: Option<i64>) -> Option<TemporalMetadata> {
+        let node = normalize_path(Path::new(node));
+
+        if self.events.contains_key(&node) {
+            return self.summarize(&[self.file_events(&node)], as_of);
+        }
+
+        let prefix = format!("{}/", node);
+        let groups: Vec<Vec<&FileEvent>> = self
+            .events
+            .iter()
+            .filter(|(path, _)| node.is_empty() || path.starts_with(&prefix))
+            .map(|(_, events)| events.iter().collect())
+            .collect();
+        self.summarize(&groups, as_of)
+    }
+
+    /// Metadata for every file, keyed by project-relative path
+    pub fn all_file_metadata(&self, as_of: Option<i64>) -> HashMap<String, TemporalMetadata> {
+        self.events
+            .keys()
+            .filter_map(|path| Some((path.clone(), self.node_metadata(path, as_of)?)))
+            .collect()
+    }
+
+    /// Change weight per author of a file, following renames: lines added plus removed
+    /// per commit, at least 1 so that moves and binary changes still count
+    pub fn author_weights(&self, path: &str, as_of: Option<i64>) -> Vec<(String, f64)> {
+        let path = normalize_path(Path::new(path));
+        let mut weights: HashMap<&str, f64> = HashMap::new();
+        for event in self.file_events(&path) {
+            if event.moved_away || as_of.is_some_and(|t| event.timestamp > t) {
+                continue;
+            }
+            *weights.entry(event.author.as_str()).or_insert(0.0) +=
+                (event.lines_added + event.lines_removed).max(1) as f64;
+        }
+
+        let mut weights: Vec<(String, f64)> = weights.into_iter().map(|(a, w)| (a.to_string(), w)).collect();
+        weights.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        weights
+    }
+
+    /// Events of a file including those recorded under the paths it was renamed from
+    fn file_events(&self, path: &str) -> Vec<&FileEvent> {
+        let mut chain = vec![path];
+        let mut seen: HashSet<&str> = HashSet::from([path]);
+        let mut current = path;
+        while let Some(previous) = self.renamed_from.get(current) {
+            if !seen.insert(previous) {
+                break;
+            }
+            chain.push(previous);
+            current = previous;
+        }
+
+        // Each path contributes the events up to its rename so nothing is counted twice
+        let mut events: Vec<&FileEvent> = Vec::new();
+        let mut until: Option<i64> = None;
+        for path in chain {
+            if let Some(path_events) = self.events.get(path) {
+                events.extend(path_events.iter().filter(|e| until.is_none_or(|t| e.timestamp < t)));
+                until = path_events
+                    .iter()
+                    .find(|e| e.kind == FileChangeKind::Renamed && !e.moved_away)
+                    .map(|e| e.timestamp);
+            }
+        }
+
+        events.sort_by_key(|e| e.timestamp);
+        events
+    }
+
+    /// Combine the event lists of one or more files into a single metadata record
+    fn summarize(&self, groups: &[Vec<&FileEvent>], as_of: Option<i64>) -> Option<TemporalMetadata> {
+        let mut events: Vec<&FileEvent> = Vec::new();
+        let mut deletions: Vec<&FileEvent> = Vec::new();
+        let mut alive = false;
+
+        for group in groups {
+            let visible: Vec<&FileEvent> = group
+                .iter()
+                .copied()
+                .filter(|e| as_of.is_none_or(|t| e.timestamp <= t))
+                .collect();
+            let Some(last) = visible.last() else {
+                continue;
+            };
+            if last.kind == FileChangeKind::Deleted || last.moved_away {
+                deletions.push(last);
+            } else {
+                alive = true;
+            }
+            events.extend(visible);
+        }
+
+        events.sort_by_key(|e| e.timestamp);
+        let first = *events.first()?;
+        let last = *events.last()?;
+
+        let deletion = if alive {
+            None
+        } else {
+            deletions.into_iter().max_by_key(|e| e.timestamp)
+        };
+
+        let mut modification_commits: Vec<String> = Vec::new();
+        let mut seen_commits: HashSet<&str> = HashSet::from([first.commit.as_str()]);
+        for event in &events {
+            let is_deletion = deletion.is_some_and(|d| d.commit == event.commit);
+            if !is_deletion && seen_commits.insert(event.commit.as_str()) {
+                modification_commits.push(event.commit.clone());
+            }
+        }
+
+        let mut author_lines: HashMap<&str, (usize, usize)> = HashMap::new();
+        for event in &events {
+            let entry = author_lines.entry(event.author.as_str()).or_default();
+            entry.0 += event.lines_added + event.lines_removed;
+            entry.1 += 1;
+        }
+        let mut authors: Vec<(&str, (usize, usize))> = author_lines.into_iter().collect();
+        authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        let primary_authors = authors
+            .into_iter()
+            .take(MAX_PRIMARY_AUTHORS)
+            .map(|(author, _)| author.to_string())
+            .collect();
+
+        let end = deletion
+            .map(|d| d.timestamp)
+            .unwrap_or_else(|| as_of.unwrap_or(self.history_end).max(last.timestamp));
+        let lifetime_days = ((end - first.timestamp) as f64 / SECONDS_PER_DAY).max(1.0);
+        let change_count = seen_commits.len();
+
+        Some(TemporalMetadata {
+            creation_commit: first.commit.clone(),
+            modification_commits,
+            deletion_commit: deletion.map(|d| d.commit.clone()),
+            primary_authors,
+            change_frequency: change_count as f64 / lifetime_days * 30.0,
+            created_at: first.timestamp,
+            last_modified_at: last.timestamp,
+            lines_added: events.iter().map(|e| e.lines_added).sum(),
+            lines_removed: events.iter().map(|e| e.lines_removed).sum(),
+        })
+    }
+}
+
+/// Strip the analyzed subfolder from a repository path; None if outside it
+pub(crate) fn project_relative(path: &Path, subfolder: Option<&str>) -> Option<String> {
+    let path = normalize_path(path);
+    match subfolder.map(|s| s.replace('\\', "/")) {
+        Some(subfolder) if !subfolder.trim_matches('/').is_empty() => {
+            let prefix = format!("{}/", subfolder.trim_matches('/'));
+            path.strip_prefix(&prefix).map(str::to_string)
+        }
+        _ => Some(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{commit_changes, file_change, modified, renamed};
+
+    const DAY: i64 = 86_400;
+
+    fn history() -> Vec<CommitChanges> {
+        vec![
+            commit_changes("c1", "alice", 0, vec![
+                file_change("app/lib/a.dart", FileChangeKind::Added, 10),
+                file_change("app/lib/b.dart", FileChangeKind::Added, 5),
+                file_change("docs/readme.md", FileChangeKind::Added, 3),
+            ]),
+            commit_changes("c2", "bob", 10 * DAY, vec![modified("app/lib/a.dart", 40)]),
+            commit_changes("c3", "alice", 20 * DAY, vec![renamed("app/lib/a.dart", "app/lib/core/a.dart")]),
+            commit_changes("c4", "carol", 30 * DAY, vec![file_change("app/lib/b.dart", FileChangeKind::Deleted, 0)]),
+        ]
+    }
+
+    #[test]
+    fn test_file_metadata_follows_renames() {
+        let file_history = FileHistory::build(&history(), Some("app"));
+        assert!(file_history.files().all(|f| f.starts_with("lib/")));
+
+        let moved = file_history.node_metadata("lib/core/a.dart", None).unwrap();
+        assert_eq!(moved.creation_commit, "c1");
+        assert_eq!(moved.modification_commits, vec!["c2", "c3"]);
+        assert_eq!(moved.deletion_commit, None);
+        assert_eq!(moved.primary_authors[0], "bob");
+        assert_eq!(moved.lines_added, 50);
+        assert!((moved.change_frequency - 3.0).abs() < 1e-9);
+        assert_eq!(
+            file_history.author_weights("lib/core/a.dart", None),
+            vec![("bob".to_string(), 40.0), ("alice".to_string(), 11.0)]
+        );
+
+        let old = file_history.node_metadata("lib/a.dart", None).unwrap();
+        assert_eq!(old.deletion_commit.as_deref(), Some("c3"));
+
+        let deleted = file_history.node_metadata("lib/b.dart", None).unwrap();
+        assert_eq!(deleted.deletion_commit.as_deref(), Some("c4"));
+    }
+
+    #[test]
+    fn test_folder_metadata_and_as_of() {
+        let file_history = FileHistory::build(&history(), Some("app"));
+
+        let folder = file_history.node_metadata("lib", None).unwrap();
+        assert_eq!(folder.creation_commit, "c1");
+        assert_eq!(folder.deletion_commit, None);
+        assert_eq!(folder.last_modified_at, 30 * DAY);
+        assert_eq!(folder.lines_added, 55);
+
+        let early = file_history.node_metadata("lib/b.dart", Some(15 * DAY)).unwrap();
+        assert_eq!(early.deletion_commit, None);
+        assert!(file_history.node_metadata("lib/core/a.dart", Some(-1)).is_none());
+    }
+}
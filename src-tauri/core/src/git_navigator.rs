@@ -3,7 +3,9 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use crate::analysis_cache::AnalysisCache;
+use crate::models::FileChangeSet;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommitInfo {
@@ -15,6 +17,32 @@ pub struct CommitInfo {
     pub merge_parent_hash: Option<String>, // For merge commits
 }
 
+/// How a single file was touched by a commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileChangeKind {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+}
+
+/// One file touched by a commit, relative to the repository root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub old_path: Option<PathBuf>, // Set for renames
+    pub kind: FileChangeKind,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+/// Files changed by a first-parent commit relative to its first parent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitChanges {
+    pub commit: CommitInfo,
+    pub changes: Vec<FileChange>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepoCloneInfo {
     pub original_url: String,
@@ -63,13 +91,13 @@ impl GitTemporalNavigator {
 
         // Check if cache already exists and is valid
         let repo = if cache_path.exists() && Repository::open(&cache_path).is_ok() {
-            println!("Found existing cached repository at {}, removing and re-cloning...", cache_path.display());
+            log::info!("Found existing cached repository at {}, removing and re-cloning...", cache_path.display());
             
             // Remove the existing cache to get a fresh clone
             fs::remove_dir_all(&cache_path)
                 .context("Failed to remove existing cached repository")?;
             
-            println!("Cloning local repository from {} to {}", source_path.display(), cache_path.display());
+            log::info!("Cloning local repository from {} to {}", source_path.display(), cache_path.display());
             
             // Clone fresh
             Repository::clone(
@@ -77,7 +105,7 @@ impl GitTemporalNavigator {
                 &cache_path
             ).context("Failed to clone local repository")?
         } else {
-            println!("Cloning local repository from {} to {}", source_path.display(), cache_path.display());
+            log::info!("Cloning local repository from {} to {}", source_path.display(), cache_path.display());
             
             // Clone the local repository to temp location
             Repository::clone(
@@ -126,7 +154,7 @@ impl GitTemporalNavigator {
         }
 
         let repo = if cache_path.exists() && Repository::open(&cache_path).is_ok() {
-            println!("Found existing repository at {}, updating...", cache_path.display());
+            log::info!("Found existing repository at {}, updating...", cache_path.display());
             
             // Open existing repository and fetch updates
             let repo = Repository::open(&cache_path)
@@ -137,7 +165,7 @@ impl GitTemporalNavigator {
             
             repo
         } else {
-            println!("Cloning {} to {}", github_url, cache_path.display());
+            log::info!("Cloning {} to {}", github_url, cache_path.display());
             
             // Clone the repository for the first time
             Repository::clone(github_url, &cache_path)
@@ -194,7 +222,7 @@ impl GitTemporalNavigator {
                     repo.reset(commit.as_object(), git2::ResetType::Hard, None)
                         .context("Failed to reset to latest commit")?;
                     
-                    println!("Updated repository to latest commit: {}", commit.id());
+                    log::info!("Updated repository to latest commit: {}", commit.id());
                     reset_successful = true;
                     break;
                 }
@@ -202,7 +230,7 @@ impl GitTemporalNavigator {
         }
         
         if !reset_successful {
-            println!("Warning: Could not reset to latest commit, using existing state");
+            log::warn!("Could not reset to latest commit, using existing state");
         }
         
         Ok(())
@@ -222,6 +250,32 @@ impl GitTemporalNavigator {
         }
     }
 
+    /// Clone this navigator's repository into `path`, with the same branch and
+    /// merge sequence, for work that checks out commits on its own such as
+    /// bisecting. The copy has its own working tree and objects, so checkouts,
+    /// updates or removal of either clone do not affect the other.
+    pub fn clone_into(&self, path: &Path) -> Result<Self> {
+        if path.exists() {
+            fs::remove_dir_all(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let source = self.clone_info.local_path.to_string_lossy();
+        let repo = Repository::clone(&source, path)
+            .with_context(|| format!("Failed to clone {} to {}", source, path.display()))?;
+        Ok(Self {
+            repo,
+            clone_info: RepoCloneInfo {
+                local_path: path.to_path_buf(),
+                ..self.clone_info.clone()
+            },
+            merge_sequence: self.merge_sequence.clone(),
+            current_commit: None,
+        })
+    }
+
     /// Build the merge sequence following first-parent commits to main branch
     pub fn build_merge_sequence(&mut self) -> Result<()> {
         self.build_merge_sequence_with_subfolder(None)
@@ -229,22 +283,13 @@ impl GitTemporalNavigator {
 
     /// Build the merge sequence with optional subfolder filtering
     pub fn build_merge_sequence_with_subfolder(&mut self, subfolder: Option<&str>) -> Result<()> {
-        println!("Building merge sequence for branch: {}", self.clone_info.default_branch);
+        log::info!("Building merge sequence for branch: {}", self.clone_info.default_branch);
         
         if let Some(subfolder) = subfolder {
-            println!("Filtering commits for subfolder: {}", subfolder);
+            log::info!("Filtering commits for subfolder: {}", subfolder);
         }
         
-        // Get the main branch reference
-        let branch_ref = format!("refs/heads/{}", self.clone_info.default_branch);
-        let reference = self.repo.find_reference(&branch_ref)
-            .or_else(|_| self.repo.find_reference("refs/heads/main"))
-            .or_else(|_| self.repo.find_reference("refs/heads/master"))
-            .context("Failed to find main branch")?;
-
-        let target_oid = reference.target().context("Failed to get branch target")?;
-        let mut current_commit = self.repo.find_commit(target_oid)
-            .context("Failed to find head commit")?;
+        let mut current_commit = Self::main_branch_head(&self.repo, &self.clone_info.default_branch)?;
 
         let mut sequence = Vec::new();
         let mut visited = std::collections::HashSet::new();
@@ -264,19 +309,19 @@ impl GitTemporalNavigator {
 
             // Progress reporting every 100 commits
             if total_commits % 100 == 0 {
-                println!("Progress: {} commits processed, {} matching", total_commits, filtered_commits);
+                log::debug!("Progress: {} commits processed, {} matching", total_commits, filtered_commits);
             }
 
             // Check if commit should be included based on subfolder filter
             let should_include = if let Some(subfolder) = subfolder {
-                println!("🔍 Checking commit {} against subfolder '{}'", &commit_hash[..8], subfolder);
+                log::debug!("Checking commit {} against subfolder '{}'", &commit_hash[..8], subfolder);
                 match self.commit_touches_subfolder(&current_commit, subfolder) {
                     Ok(touches) => {
-                        println!("   Result: {}", if touches { "✓ MATCH" } else { "✗ No match" });
+                        log::debug!("Result: {}", if touches { "match" } else { "no match" });
                         touches
                     },
                     Err(e) => {
-                        println!("   Error checking commit {}: {}", &commit_hash[..8], e);
+                        log::debug!("Error checking commit {}: {}", &commit_hash[..8], e);
                         false // Skip commit on error
                     }
                 }
@@ -289,19 +334,19 @@ impl GitTemporalNavigator {
                 sequence.push(commit_info);
                 filtered_commits += 1;
                 
-                println!("✓ MATCHED commit #{}: {} - {}", filtered_commits, &commit_hash[..8],
+                log::debug!("MATCHED commit #{}: {} - {}", filtered_commits, &commit_hash[..8],
                          current_commit.message().unwrap_or("<no message>").lines().next().unwrap_or(""));
 
                 // Limit to a reasonable number of commits for analysis
                 if filtered_commits >= 50 {
-                    println!("Found {} matches - limiting to 50 commits for performance", filtered_commits);
+                    log::warn!("Found {} matches - limiting to 50 commits for performance", filtered_commits);
                     break;
                 }
             }
 
             // Early exit if we have scanned too many commits (performance optimization)
             if total_commits > 500 {
-                println!("Performance limit: Scanned {} commits, stopping to avoid UI timeout", total_commits);
+                log::warn!("Performance limit: Scanned {} commits, stopping to avoid UI timeout", total_commits);
                 break;
             }
 
@@ -317,10 +362,10 @@ impl GitTemporalNavigator {
         self.merge_sequence = sequence;
 
         if let Some(_subfolder) = subfolder {
-            println!("Built filtered merge sequence: {} relevant commits out of {} total commits", 
+            log::info!("Built filtered merge sequence: {} relevant commits out of {} total commits", 
                      filtered_commits, total_commits);
         } else {
-            println!("Built merge sequence with {} commits", self.merge_sequence.len());
+            log::info!("Built merge sequence with {} commits", self.merge_sequence.len());
         }
         
         Ok(())
@@ -387,7 +432,7 @@ impl GitTemporalNavigator {
                 // Check if the file is directly in the subfolder or its subdirectories
                 if file_path.starts_with(&subfolder_prefix) || file_path == subfolder {
                     touches_subfolder = true;
-                    println!("✓ MATCH: {} touches {}", file_path, subfolder);
+                    log::debug!("MATCH: {} touches {}", file_path, subfolder);
                     return false; // Stop iteration
                 }
 
@@ -409,9 +454,9 @@ impl GitTemporalNavigator {
             if error_code == git2::ErrorCode::User {
                 // GIT_EUSER (-7): This is not an error, just early termination from callback
                 // This happens when we return false from the callback (normal operation)
-                println!("   🏁 Diff iteration stopped early (normal - found match or hit limit)");
+                log::debug!("Diff iteration stopped early (normal - found match or hit limit)");
             } else {
-                println!("   ⚠️  Real error processing diff for commit {}: {} (code: {:?})", 
+                log::debug!("Real error processing diff for commit {}: {} (code: {:?})", 
                          &commit.id().to_string()[..8], e, error_code);
                 return Ok(false); // Skip commit only on real errors
             }
@@ -419,21 +464,21 @@ impl GitTemporalNavigator {
 
         // Enhanced debug output showing comparison logic
         if files_checked > 0 {
-            println!("   📁 Commit {} processed {} files. Target: '{}'", 
+            log::debug!("Commit {} processed {} files. Target: '{}'", 
                      &commit.id().to_string()[..8], files_checked, subfolder);
-            println!("      Looking for paths starting with: '{}'", subfolder_prefix);
+            log::debug!("Looking for paths starting with: '{}'", subfolder_prefix);
             if !sample_files.is_empty() {
-                println!("      Sample file paths: {:?}", sample_files);
+                log::debug!("Sample file paths: {:?}", sample_files);
                 // Show the matching test for the first sample file
                 if let Some(first_file) = sample_files.first() {
-                    println!("      Test: '{}' starts_with('{}') = {}", 
+                    log::debug!("Test: '{}' starts_with('{}') = {}", 
                              first_file, subfolder_prefix, first_file.starts_with(&subfolder_prefix));
-                    println!("      Test: '{}' == '{}' = {}", 
+                    log::debug!("Test: '{}' == '{}' = {}", 
                              first_file, subfolder, first_file == subfolder);
                 }
             }
             if !touches_subfolder {
-                println!("      ❌ No match found");
+                log::debug!("No match found");
             }
         }
 
@@ -478,6 +523,139 @@ impl GitTemporalNavigator {
         }
     }
 
+    /// Head commit of the main branch
+    fn main_branch_head<'r>(repo: &'r Repository, default_branch: &str) -> Result<Commit<'r>> {
+        let branch_ref = format!("refs/heads/{}", default_branch);
+        let reference = repo.find_reference(&branch_ref)
+            .or_else(|_| repo.find_reference("refs/heads/main"))
+            .or_else(|_| repo.find_reference("refs/heads/master"))
+            .context("Failed to find main branch")?;
+
+        let target_oid = reference.target().context("Failed to get branch target")?;
+        repo.find_commit(target_oid)
+            .context("Failed to find head commit")
+    }
+
+    /// Walk the complete first-parent history of the main branch (oldest first)
+    /// and list the files each commit changed within `subfolder`, with renames
+    /// detected. Unlike the merge sequence this is not sampled or capped, so
+    /// results are read from and stored in `cache` per commit. Commits that
+    /// cannot be diffed are skipped with a warning.
+    pub fn first_parent_history(
+        &self,
+        subfolder: Option<&str>,
+        mut cache: Option<&mut AnalysisCache>,
+    ) -> Result<Vec<CommitChanges>> {
+        let subfolder = subfolder.map(|s| s.trim_matches('/')).filter(|s| !s.is_empty());
+        let repo_url = &self.clone_info.original_url;
+        let mut history = Vec::new();
+        let mut current = Some(Self::main_branch_head(&self.repo, &self.clone_info.default_branch)?);
+
+        while let Some(commit) = current {
+            let hash = commit.id().to_string();
+            let cached = cache
+                .as_deref_mut()
+                .and_then(|cache| cache.get_commit_changes(repo_url, &hash, subfolder).ok().flatten());
+            let changes = match cached {
+                Some(changes) => Some(changes),
+                None => match self.commit_file_changes(&commit, subfolder) {
+                    Ok(changes) => {
+                        if let Some(cache) = cache.as_deref_mut() {
+                            if let Err(e) = cache.put_commit_changes(repo_url, &hash, subfolder, &changes) {
+                                log::warn!("Failed to cache file changes for commit {}: {}", hash, e);
+                            }
+                        }
+                        Some(changes)
+                    }
+                    Err(e) => {
+                        log::warn!("Skipping file history of commit {}: {}", hash, e);
+                        None
+                    }
+                },
+            };
+            if let Some(changes) = changes {
+                history.push(CommitChanges {
+                    commit: Self::extract_commit_info(&commit),
+                    changes,
+                });
+            }
+            current = commit.parents().next();
+        }
+
+        history.reverse();
+        Ok(history)
+    }
+
+    /// Files under `subfolder` changed by a commit relative to its first parent
+    /// (or the empty tree for roots)
+    fn commit_file_changes(&self, commit: &Commit, subfolder: Option<&str>) -> Result<Vec<FileChange>> {
+        let current_tree = commit.tree().context("Failed to get current commit tree")?;
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree().context("Failed to get parent tree")?),
+            None => None,
+        };
+
+        let mut diff_opts = git2::DiffOptions::new();
+        diff_opts.context_lines(0);
+        diff_opts.interhunk_lines(0);
+        diff_opts.max_size(1024 * 1024); // Limit diff size to 1MB
+        if let Some(subfolder) = subfolder {
+            diff_opts.pathspec(subfolder);
+        }
+
+        let mut diff = self.repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&current_tree),
+            Some(&mut diff_opts),
+        ).context("Failed to create diff")?;
+
+        let mut find_opts = git2::DiffFindOptions::new();
+        find_opts.renames(true);
+        diff.find_similar(Some(&mut find_opts))
+            .context("Failed to detect renames")?;
+
+        let mut changes = Vec::new();
+        for (index, delta) in diff.deltas().enumerate() {
+            let kind = match delta.status() {
+                git2::Delta::Added | git2::Delta::Copied => FileChangeKind::Added,
+                git2::Delta::Deleted => FileChangeKind::Deleted,
+                git2::Delta::Renamed => FileChangeKind::Renamed,
+                git2::Delta::Modified | git2::Delta::Typechange => FileChangeKind::Modified,
+                _ => continue,
+            };
+
+            let path = match kind {
+                FileChangeKind::Deleted => delta.old_file().path(),
+                _ => delta.new_file().path(),
+            };
+            let Some(path) = path.map(Path::to_path_buf) else {
+                continue;
+            };
+            let old_path = match kind {
+                FileChangeKind::Renamed => delta.old_file().path().map(Path::to_path_buf),
+                _ => None,
+            };
+
+            // Binary or oversized files have no line stats
+            let (lines_added, lines_removed) = git2::Patch::from_diff(&diff, index)
+                .ok()
+                .flatten()
+                .and_then(|patch| patch.line_stats().ok())
+                .map(|(_, added, removed)| (added, removed))
+                .unwrap_or((0, 0));
+
+            changes.push(FileChange {
+                path,
+                old_path,
+                kind,
+                lines_added,
+                lines_removed,
+            });
+        }
+
+        Ok(changes)
+    }
+
     /// Extract commit information including author details
     fn extract_commit_info(commit: &Commit) -> CommitInfo {
         let signature = commit.author();
@@ -505,7 +683,7 @@ impl GitTemporalNavigator {
 
     /// Checkout a specific commit by hash
     pub fn checkout_commit(&mut self, commit_hash: &str) -> Result<()> {
-        println!("Checking out commit: {}", commit_hash);
+        log::debug!("Checking out commit: {}", commit_hash);
         
         let oid = Oid::from_str(commit_hash)
             .context("Invalid commit hash")?;
@@ -525,11 +703,104 @@ impl GitTemporalNavigator {
             .context("Failed to checkout commit")?;
 
         self.current_commit = Some(commit_hash.to_string());
-        println!("Successfully checked out commit: {}", commit_hash);
+        log::debug!("Successfully checked out commit: {}", commit_hash);
         
         Ok(())
     }
 
+    /// Analyze a branch or tag instead of the default branch. Remote branches are
+    /// preferred over stale local ones; the ref is checked out as a local branch.
+    pub fn use_ref(&mut self, reference: &str) -> Result<()> {
+        {
+            let commit = self.repo.revparse_single(&format!("refs/remotes/origin/{}", reference))
+                .or_else(|_| self.repo.revparse_single(reference))
+                .with_context(|| format!("Unknown branch or tag: {}", reference))?
+                .peel_to_commit()
+                .with_context(|| format!("{} does not point to a commit", reference))?;
+
+            // The checked-out branch cannot be force-updated, and is already current
+            let checked_out = self.repo.head().ok().and_then(|h| h.shorthand().map(str::to_string));
+            if checked_out.as_deref() != Some(reference) {
+                self.repo.branch(reference, &commit, true)
+                    .with_context(|| format!("Failed to create local branch for {}", reference))?;
+            }
+        }
+
+        self.clone_info.default_branch = reference.to_string();
+        self.build_merge_sequence()
+    }
+
+    /// Contents of a file at the head of the main branch, or None if it does not exist.
+    /// Reads from the object database, so the current checkout does not matter.
+    pub fn read_head_file(&self, path: &str) -> Result<Option<String>> {
+        let head = Self::main_branch_head(&self.repo, &self.clone_info.default_branch)?;
+        let entry = match head.tree()?.get_path(Path::new(path)) {
+            Ok(entry) => entry,
+            Err(_) => return Ok(None),
+        };
+        let Ok(blob) = entry.to_object(&self.repo)?.peel_to_blob() else {
+            return Ok(None);
+        };
+        Ok(Some(String::from_utf8_lossy(blob.content()).into_owned()))
+    }
+
+    /// Detect files renamed or moved between two commits.
+    /// Paths are relative to the repository root; the commits need not be adjacent.
+    pub fn find_renames(&self, from_hash: &str, to_hash: &str) -> Result<Vec<(PathBuf, PathBuf)>> {
+        Ok(self.get_file_changes(from_hash, to_hash)?.renamed_files)
+    }
+
+    /// Files added, modified, deleted and renamed between two commits, with
+    /// renames detected by content similarity. Paths are relative to the repository root.
+    pub fn get_file_changes(&self, from_hash: &str, to_hash: &str) -> Result<FileChangeSet> {
+        let from_tree = self.repo.find_commit(Oid::from_str(from_hash).context("Invalid commit hash")?)
+            .context("Commit not found")?
+            .tree()
+            .context("Failed to get commit tree")?;
+        let to_tree = self.repo.find_commit(Oid::from_str(to_hash).context("Invalid commit hash")?)
+            .context("Commit not found")?
+            .tree()
+            .context("Failed to get commit tree")?;
+
+        let mut diff = self.repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), None)
+            .context("Failed to create diff")?;
+
+        let mut find_opts = git2::DiffFindOptions::new();
+        find_opts.renames(true);
+        diff.find_similar(Some(&mut find_opts))
+            .context("Failed to detect renames")?;
+
+        let mut changes = FileChangeSet {
+            added_files: HashSet::new(),
+            modified_files: HashSet::new(),
+            deleted_files: HashSet::new(),
+            renamed_files: Vec::new(),
+        };
+
+        for delta in diff.deltas() {
+            let old_path = delta.old_file().path().map(Path::to_path_buf);
+            let new_path = delta.new_file().path().map(Path::to_path_buf);
+
+            match (delta.status(), old_path, new_path) {
+                (git2::Delta::Added | git2::Delta::Copied, _, Some(path)) => {
+                    changes.added_files.insert(path);
+                }
+                (git2::Delta::Deleted, Some(path), _) => {
+                    changes.deleted_files.insert(path);
+                }
+                (git2::Delta::Modified | git2::Delta::Typechange, _, Some(path)) => {
+                    changes.modified_files.insert(path);
+                }
+                (git2::Delta::Renamed, Some(old_path), Some(new_path)) => {
+                    changes.renamed_files.push((old_path, new_path));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(changes)
+    }
+
     /// Get the merge sequence (architectural evolution)
     pub fn get_merge_sequence(&self) -> &[CommitInfo] {
         &self.merge_sequence
@@ -572,9 +843,9 @@ impl GitTemporalNavigator {
                     // Check if this looks like a timestamped directory
                     if let Some(timestamp_part) = dir_name.split('-').last() {
                         if timestamp_part.chars().all(|c| c.is_ascii_digit()) {
-                            println!("Cleaning up old repository: {}", path.display());
+                            log::info!("Cleaning up old repository: {}", path.display());
                             if let Err(e) = fs::remove_dir_all(&path) {
-                                println!("Warning: Failed to remove {}: {}", path.display(), e);
+                                log::warn!("Failed to remove {}: {}", path.display(), e);
                             } else {
                                 cleaned_count += 1;
                             }
@@ -585,7 +856,7 @@ impl GitTemporalNavigator {
         }
         
         if cleaned_count > 0 {
-            println!("Cleaned up {} old repository directories", cleaned_count);
+            log::info!("Cleaned up {} old repository directories", cleaned_count);
         }
         
         Ok(())
@@ -641,14 +912,14 @@ impl GitTemporalNavigator {
             .context("Failed to checkout HEAD")?;
 
         self.current_commit = None;
-        println!("Returned to HEAD of {}", self.clone_info.default_branch);
+        log::debug!("Returned to HEAD of {}", self.clone_info.default_branch);
         
         Ok(())
     }
 
     /// Clean up - remove local repository
     pub fn cleanup(self) -> Result<()> {
-        println!("Cleaning up local repository: {}", self.clone_info.local_path.display());
+        log::info!("Cleaning up local repository: {}", self.clone_info.local_path.display());
         
         if self.clone_info.local_path.exists() {
             fs::remove_dir_all(&self.clone_info.local_path)
@@ -684,6 +955,128 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_renames() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        let content = "import 'b.dart';\nclass A {\n  void run() {}\n}\n";
+        let first = test_repo.commit(&[("lib/a.dart", Some(content))], "alice", 1_000);
+        let second = test_repo.commit(&[
+            ("lib/a.dart", None),
+            ("lib/core/a.dart", Some(content)),
+        ], "bob", 2_000);
+
+        let base_dir = tempdir().unwrap();
+        let navigator = test_repo.navigator(base_dir.path());
+        let renames = navigator.find_renames(&first, &second).unwrap();
+
+        assert_eq!(renames, vec![(PathBuf::from("lib/a.dart"), PathBuf::from("lib/core/a.dart"))]);
+    }
+
+    #[test]
+    fn test_first_parent_history() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        let content = "import 'b.dart';\nclass A {\n  void run() {}\n}\n";
+        test_repo.commit(&[("lib/a.dart", Some(content)), ("lib/b.dart", Some("class B {}\n"))], "alice", 1_000);
+        test_repo.commit(&[("lib/b.dart", Some("class B {\n  int x = 0;\n}\n"))], "bob", 2_000);
+        test_repo.commit(&[("lib/a.dart", None), ("lib/core/a.dart", Some(content))], "carol", 3_000);
+
+        let base_dir = tempdir().unwrap();
+        let navigator = test_repo.navigator(base_dir.path());
+        let history = navigator.first_parent_history(None, None).unwrap();
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].commit.author_name, "alice");
+        assert_eq!(history[0].changes.len(), 2);
+        assert!(history[0].changes.iter().all(|c| c.kind == FileChangeKind::Added));
+
+        let modified = &history[1].changes[0];
+        assert_eq!(modified.kind, FileChangeKind::Modified);
+        assert_eq!((modified.lines_added, modified.lines_removed), (3, 1));
+
+        let renamed = &history[2].changes[0];
+        assert_eq!(renamed.kind, FileChangeKind::Renamed);
+        assert_eq!(renamed.old_path.as_deref(), Some(Path::new("lib/a.dart")));
+        assert_eq!(renamed.path, PathBuf::from("lib/core/a.dart"));
+
+        assert!(navigator.read_head_file("lib/b.dart").unwrap().unwrap().contains("int x"));
+        assert!(navigator.read_head_file("lib/a.dart").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_first_parent_history_in_subfolder() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        test_repo.commit(&[("app/lib/a.dart", Some("class A {}\n")), ("docs/a.md", Some("# A\n"))], "alice", 1_000);
+        test_repo.commit(&[("application/b.dart", Some("class B {}\n"))], "bob", 2_000);
+        test_repo.commit(&[("app/lib/a.dart", Some("class A {\n}\n"))], "carol", 3_000);
+
+        let base_dir = tempdir().unwrap();
+        let navigator = test_repo.navigator(base_dir.path());
+        let mut cache = AnalysisCache::new(base_dir.path().join("cache")).unwrap();
+        let history = navigator.first_parent_history(Some("app"), Some(&mut cache)).unwrap();
+
+        let paths: Vec<Vec<&Path>> = history
+            .iter()
+            .map(|c| c.changes.iter().map(|f| f.path.as_path()).collect())
+            .collect();
+        assert_eq!(paths, vec![
+            vec![Path::new("app/lib/a.dart")],
+            vec![],
+            vec![Path::new("app/lib/a.dart")],
+        ]);
+
+        let cached = cache
+            .get_commit_changes(&navigator.clone_info().original_url, &history[2].commit.hash, Some("app"))
+            .unwrap()
+            .unwrap();
+        assert_eq!((cached[0].lines_added, cached[0].lines_removed), (2, 1));
+        assert!(cache
+            .get_commit_changes(&navigator.clone_info().original_url, &history[2].commit.hash, None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_clone_into() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        let first = test_repo.commit(&[("lib/a.dart", Some("class A {}\n"))], "alice", 1_000);
+        test_repo.commit(&[("lib/b.dart", Some("class B {}\n"))], "bob", 2_000);
+
+        let base_dir = tempdir().unwrap();
+        let navigator = test_repo.navigator(base_dir.path());
+        let mut copy = navigator.clone_into(&base_dir.path().join("copy")).unwrap();
+        assert_eq!(copy.get_merge_sequence().len(), navigator.get_merge_sequence().len());
+
+        copy.checkout_commit(&first).unwrap();
+        assert!(!copy.local_path().join("lib/b.dart").exists());
+        assert!(navigator.local_path().join("lib/b.dart").exists());
+        assert_eq!(navigator.current_commit(), None);
+
+        let copy_path = copy.local_path().to_path_buf();
+        copy.cleanup().unwrap();
+        assert!(!copy_path.exists());
+        assert!(navigator.local_path().exists());
+    }
+
+    #[test]
+    fn test_use_ref() {
+        let test_repo = crate::test_fixtures::TestRepo::new();
+        test_repo.commit(&[("lib/a.dart", Some("class A {}\n"))], "alice", 1_000);
+        let tagged = test_repo.commit(&[("lib/b.dart", Some("class B {}\n"))], "bob", 2_000);
+        test_repo.commit(&[("lib/c.dart", Some("class C {}\n"))], "carol", 3_000);
+        let commit = test_repo.repo.find_commit(Oid::from_str(&tagged).unwrap()).unwrap();
+        test_repo.repo.tag_lightweight("v1", commit.as_object(), false).unwrap();
+
+        let base_dir = tempdir().unwrap();
+        let mut navigator = test_repo.navigator(base_dir.path());
+        assert_eq!(navigator.get_merge_sequence().len(), 3);
+
+        navigator.use_ref("v1").unwrap();
+        assert_eq!(navigator.get_merge_sequence().len(), 2);
+        assert_eq!(navigator.get_merge_sequence().last().unwrap().hash, tagged);
+        assert!(navigator.read_head_file("lib/c.dart").unwrap().is_none());
+        assert!(navigator.use_ref("missing").is_err());
+    }
+
     // Note: Integration tests would require actual repositories
     // These should be run separately with real GitHub URLs
 }
\ No newline at end of file
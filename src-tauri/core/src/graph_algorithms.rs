@@ -0,0 +1,620 @@
+use crate::dependency_analyzer::{AnalysisResult, GlobalArchitecturalMetrics, NodeMetrics, RawDependency};
+use crate::dependency_view::normalize_path;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::Path;
+
+/// PageRank parameters shared by every ranking of files, so scores are comparable
+pub const PAGERANK_DAMPING: f64 = 0.85;
+pub const PAGERANK_ITERATIONS: usize = 100;
+
+/// Directed file graph with dense node indices, built from raw dependencies
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<String>,
+    pub index: HashMap<String, usize>,
+    /// Outgoing edges per node (deduplicated, no self-loops)
+    pub successors: Vec<Vec<usize>>,
+    /// Incoming edges per node
+    pub predecessors: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    /// Build a graph from a list of dependencies
+    pub fn from_dependencies(dependencies: &[RawDependency]) -> Self {
+        let mut graph = Self::default();
+        for dep in dependencies {
+            let source = graph.add_node(&normalize_path(&dep.source_file));
+            let target = graph.add_node(&normalize_path(&dep.target_file));
+            graph.add_edge(source, target);
+        }
+        graph
+    }
+
+    /// Build a graph from an analysis result, including isolated analyzed files
+    pub fn from_analysis(result: &AnalysisResult) -> Self {
+        let mut graph = Self::from_dependencies(&result.dependencies);
+        for file in &result.analyzed_files {
+            graph.add_node(&normalize_path(file));
+        }
+        graph
+    }
+
+    /// Add a node if missing and return its index
+    pub fn add_node(&mut self, name: &str) -> usize {
+        if let Some(&idx) = self.index.get(name) {
+            return idx;
+        }
+        let idx = self.nodes.len();
+        self.nodes.push(name.to_string());
+        self.index.insert(name.to_string(), idx);
+        self.successors.push(Vec::new());
+        self.predecessors.push(Vec::new());
+        idx
+    }
+
+    /// Add a directed edge, ignoring duplicates and self-loops
+    pub fn add_edge(&mut self, source: usize, target: usize) {
+        if source == target || self.successors[source].contains(&target) {
+            return;
+        }
+        self.successors[source].push(target);
+        self.predecessors[target].push(source);
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.successors.iter().map(Vec::len).sum()
+    }
+
+    /// Look up a node index by (unnormalized) path
+    pub fn node_index(&self, path: &Path) -> Option<usize> {
+        self.index.get(&normalize_path(path)).copied()
+    }
+
+    /// Strongly connected components (iterative Tarjan), in reverse topological order
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.node_count();
+        let mut index_of: Vec<Option<usize>> = vec![None; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in 0..n {
+            if index_of[root].is_some() {
+                continue;
+            }
+
+            // Each frame is (node, position of the next successor to visit)
+            let mut call_stack = vec![(root, 0usize)];
+            index_of[root] = Some(next_index);
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (node, ref mut next_child)) = call_stack.last_mut() {
+                if let Some(&child) = self.successors[node].get(*next_child) {
+                    *next_child += 1;
+                    match index_of[child] {
+                        None => {
+                            index_of[child] = Some(next_index);
+                            lowlink[child] = next_index;
+                            next_index += 1;
+                            stack.push(child);
+                            on_stack[child] = true;
+                            call_stack.push((child, 0));
+                        }
+                        Some(child_index) if on_stack[child] => {
+                            lowlink[node] = lowlink[node].min(child_index);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(&(parent, _)) = call_stack.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+
+                if Some(lowlink[node]) == index_of[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Dependency cycles as sorted member paths, one per strongly connected component
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles: Vec<Vec<String>> = self
+            .strongly_connected_components()
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let members: BTreeSet<String> = component
+                    .into_iter()
+                    .map(|idx| self.nodes[idx].clone())
+                    .collect();
+                members.into_iter().collect()
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Number of nodes reachable from `start`, including itself
+    pub fn reachable_count(&self, start: usize) -> usize {
+        let mut seen = vec![false; self.node_count()];
+        let mut queue = VecDeque::from([start]);
+        seen[start] = true;
+        let mut count = 0;
+        while let Some(node) = queue.pop_front() {
+            count += 1;
+            for &next in &self.successors[node] {
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        count
+    }
+
+    /// Lakos level of every node: 1 for nodes without dependencies, otherwise one more
+    /// than the highest level they depend on. Members of a cycle share a level.
+    pub fn levels(&self) -> Vec<u32> {
+        let mut component_of = vec![0; self.node_count()];
+        let components = self.strongly_connected_components();
+        for (component_idx, component) in components.iter().enumerate() {
+            for &node in component {
+                component_of[node] = component_idx;
+            }
+        }
+
+        // Tarjan emits components after everything they reach, so dependencies come first
+        let mut component_level = vec![1u32; components.len()];
+        for (component_idx, component) in components.iter().enumerate() {
+            let level = component
+                .iter()
+                .flat_map(|&node| &self.successors[node])
+                .map(|&next| component_of[next])
+                .filter(|&next_component| next_component != component_idx)
+                .map(|next_component| component_level[next_component] + 1)
+                .max()
+                .unwrap_or(1);
+            component_level[component_idx] = level;
+        }
+
+        component_of.iter().map(|&c| component_level[c]).collect()
+    }
+
+    /// PageRank with the standard damping and iteration limit
+    pub fn pagerank(&self) -> Vec<f64> {
+        self.pagerank_with(PAGERANK_DAMPING, PAGERANK_ITERATIONS)
+    }
+
+    /// PageRank along dependency edges, so files many others (transitively) depend
+    /// on rank highest. Dangling nodes spread their rank evenly; scores sum to 1.
+    pub fn pagerank_with(&self, damping: f64, iterations: usize) -> Vec<f64> {
+        let n = self.node_count();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let base = (1.0 - damping) / n as f64;
+        let mut rank = vec![1.0 / n as f64; n];
+        for _ in 0..iterations {
+            let dangling: f64 = (0..n)
+                .filter(|&node| self.successors[node].is_empty())
+                .map(|node| rank[node])
+                .sum();
+            let mut next = vec![base + damping * dangling / n as f64; n];
+            for (node, targets) in self.successors.iter().enumerate() {
+                let share = damping * rank[node] / targets.len().max(1) as f64;
+                for &target in targets {
+                    next[target] += share;
+                }
+            }
+
+            let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if delta < 1e-10 {
+                break;
+            }
+        }
+        rank
+    }
+
+    /// Betweenness centrality over directed shortest paths (Brandes), normalized by
+    /// the number of ordered node pairs (n - 1)(n - 2)
+    pub fn betweenness(&self) -> Vec<f64> {
+        let n = self.node_count();
+        let mut centrality = vec![0.0; n];
+
+        for source in 0..n {
+            let mut order = Vec::with_capacity(n);
+            let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut paths = vec![0.0; n];
+            let mut distance: Vec<Option<usize>> = vec![None; n];
+            paths[source] = 1.0;
+            distance[source] = Some(0);
+
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                order.push(node);
+                let next_distance = distance[node].map(|d| d + 1);
+                for &target in &self.successors[node] {
+                    if distance[target].is_none() {
+                        distance[target] = next_distance;
+                        queue.push_back(target);
+                    }
+                    if distance[target] == next_distance {
+                        paths[target] += paths[node];
+                        predecessors[target].push(node);
+                    }
+                }
+            }
+
+            let mut dependency = vec![0.0; n];
+            for &node in order.iter().rev() {
+                for &previous in &predecessors[node] {
+                    dependency[previous] += paths[previous] / paths[node] * (1.0 + dependency[node]);
+                }
+                if node != source {
+                    centrality[node] += dependency[node];
+                }
+            }
+        }
+
+        if n > 2 {
+            let pairs = ((n - 1) * (n - 2)) as f64;
+            for value in &mut centrality {
+                *value /= pairs;
+            }
+        }
+        centrality
+    }
+
+    /// Edges as an undirected weighted adjacency; mutual imports weigh 2
+    fn undirected_adjacency(&self) -> Vec<BTreeMap<usize, f64>> {
+        let mut adjacency = vec![BTreeMap::new(); self.node_count()];
+        for (source, targets) in self.successors.iter().enumerate() {
+            for &target in targets {
+                *adjacency[source].entry(target).or_insert(0.0) += 1.0;
+                *adjacency[target].entry(source).or_insert(0.0) += 1.0;
+            }
+        }
+        adjacency
+    }
+
+    /// Community per node from the Louvain method on the undirected graph.
+    /// Communities are numbered in order of their first node.
+    pub fn louvain(&self) -> Vec<usize> {
+        let mut adjacency = self.undirected_adjacency();
+        let mut membership: Vec<usize> = (0..self.node_count()).collect();
+
+        while let Some(communities) = louvain_local_moves(&adjacency) {
+            for community in &mut membership {
+                *community = communities[*community];
+            }
+
+            let count = communities.iter().max().map_or(0, |c| c + 1);
+            let mut aggregated = vec![BTreeMap::new(); count];
+            for (node, neighbours) in adjacency.iter().enumerate() {
+                for (&neighbour, &weight) in neighbours {
+                    *aggregated[communities[node]].entry(communities[neighbour]).or_insert(0.0) += weight;
+                }
+            }
+            adjacency = aggregated;
+        }
+
+        renumber(&membership)
+    }
+
+    /// Newman modularity Q of a partition of the undirected graph
+    pub fn modularity(&self, partition: &[usize]) -> f64 {
+        let adjacency = self.undirected_adjacency();
+        let total: f64 = adjacency.iter().flat_map(|n| n.values()).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let mut internal: HashMap<usize, f64> = HashMap::new();
+        let mut degree: HashMap<usize, f64> = HashMap::new();
+        for (node, neighbours) in adjacency.iter().enumerate() {
+            for (&neighbour, &weight) in neighbours {
+                *degree.entry(partition[node]).or_insert(0.0) += weight;
+                if partition[node] == partition[neighbour] {
+                    *internal.entry(partition[node]).or_insert(0.0) += weight;
+                }
+            }
+        }
+
+        degree
+            .iter()
+            .map(|(community, &tot)| {
+                internal.get(community).copied().unwrap_or(0.0) / total - (tot / total).powi(2)
+            })
+            .sum()
+    }
+}
+
+/// One Louvain phase: greedily move nodes to the neighbouring community with the
+/// best modularity gain until nothing moves. Returns the renumbered communities,
+/// or `None` when no node moved.
+fn louvain_local_moves(adjacency: &[BTreeMap<usize, f64>]) -> Option<Vec<usize>> {
+    let n = adjacency.len();
+    let degree: Vec<f64> = adjacency.iter().map(|a| a.values().sum()).collect();
+    let total: f64 = degree.iter().sum();
+    if total == 0.0 {
+        return None;
+    }
+
+    let mut community: Vec<usize> = (0..n).collect();
+    let mut community_degree = degree.clone();
+    let mut moved_any = false;
+
+    loop {
+        let mut moved = false;
+        for node in 0..n {
+            let current = community[node];
+            community_degree[current] -= degree[node];
+
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for (&neighbour, &weight) in &adjacency[node] {
+                if neighbour != node {
+                    *links.entry(community[neighbour]).or_insert(0.0) += weight;
+                }
+            }
+
+            let gain = |c: usize, link: f64| link - community_degree[c] * degree[node] / total;
+            let mut best = current;
+            let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+            for (&candidate, &link) in &links {
+                let candidate_gain = gain(candidate, link);
+                if candidate_gain > best_gain + 1e-12 {
+                    best = candidate;
+                    best_gain = candidate_gain;
+                }
+            }
+
+            community_degree[best] += degree[node];
+            if best != current {
+                community[node] = best;
+                moved = true;
+                moved_any = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    moved_any.then(|| renumber(&community))
+}
+
+/// Relabel community ids as 0, 1, 2, ... in order of first occurrence
+fn renumber(partition: &[usize]) -> Vec<usize> {
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    partition
+        .iter()
+        .map(|c| {
+            let next = ids.len();
+            *ids.entry(*c).or_insert(next)
+        })
+        .collect()
+}
+
+/// Node metrics keyed by normalized path.
+///
+/// Uses the analyzer's own metrics when present and otherwise derives degree,
+/// instability, component dependency and cycle membership from the edges.
+pub fn node_metrics_or_derived(result: &AnalysisResult) -> HashMap<String, NodeMetrics> {
+    if let Some(ref metrics) = result.node_metrics {
+        return metrics
+            .iter()
+            .map(|(path, m)| (normalize_path(Path::new(path)), m.clone()))
+            .collect();
+    }
+
+    let graph = DependencyGraph::from_analysis(result);
+    let mut cycle_of: HashMap<usize, u32> = HashMap::new();
+    let cyclic_components = graph
+        .strongly_connected_components()
+        .into_iter()
+        .filter(|c| c.len() > 1);
+    for (cycle_id, component) in cyclic_components.enumerate() {
+        for node in component {
+            cycle_of.insert(node, cycle_id as u32);
+        }
+    }
+
+    (0..graph.node_count())
+        .map(|idx| {
+            let in_degree = graph.predecessors[idx].len() as u32;
+            let out_degree = graph.successors[idx].len() as u32;
+            let total_degree = in_degree + out_degree;
+            let metrics = NodeMetrics {
+                file_path: graph.nodes[idx].clone(),
+                component_dependency: graph.reachable_count(idx) as u32,
+                in_degree,
+                out_degree,
+                instability: if total_degree > 0 {
+                    out_degree as f64 / total_degree as f64
+                } else {
+                    0.0
+                },
+                sloc: 0,
+                is_orphan: total_degree == 0,
+                in_cycle: cycle_of.contains_key(&idx),
+                cycle_id: cycle_of.get(&idx).copied(),
+                ..NodeMetrics::default()
+            };
+            (graph.nodes[idx].clone(), metrics)
+        })
+        .collect()
+}
+
+/// Global metrics as reported by the analyzer, or derived from the edges and node metrics
+pub fn global_metrics_or_derived(result: &AnalysisResult) -> GlobalArchitecturalMetrics {
+    if let Some(ref metrics) = result.global_metrics {
+        return metrics.clone();
+    }
+
+    let graph = DependencyGraph::from_analysis(result);
+    let node_metrics = node_metrics_or_derived(result);
+    let num_nodes = graph.node_count() as u32;
+    let num_edges = graph.edge_count() as u32;
+    let ccd: u32 = node_metrics.values().map(|m| m.component_dependency).sum();
+    let total_sloc: u32 = node_metrics.values().map(|m| m.sloc).sum();
+    let cycles = graph.cycles();
+
+    // NCCD compares CCD with that of a balanced binary tree of the same size
+    let n = num_nodes as f64;
+    let balanced_ccd = (n + 1.0) * (n + 1.0).log2() - n;
+
+    let mut orphan_libraries: Vec<String> = node_metrics
+        .iter()
+        .filter(|(_, m)| m.is_orphan)
+        .map(|(path, _)| path.clone())
+        .collect();
+    orphan_libraries.sort();
+
+    GlobalArchitecturalMetrics {
+        is_acyclic: cycles.is_empty(),
+        num_nodes,
+        num_edges,
+        avg_degree: if num_nodes > 0 { 2.0 * num_edges as f64 / n } else { 0.0 },
+        cumulative_component_dependency: ccd,
+        average_component_dependency: if num_nodes > 0 { ccd as f64 / n } else { 0.0 },
+        normalized_ccd: if balanced_ccd > 0.0 { ccd as f64 / balanced_ccd } else { 0.0 },
+        total_sloc,
+        average_sloc: if num_nodes > 0 { total_sloc as f64 / n } else { 0.0 },
+        detected_cycles: cycles,
+        orphan_libraries,
+    }
+}
+
+/// Dependency cycles of an analysis result, independent of the analyzer's own cycle report
+pub fn dependency_cycles(result: &AnalysisResult) -> Vec<Vec<String>> {
+    DependencyGraph::from_dependencies(&result.dependencies).cycles()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::dep;
+
+    #[test]
+    fn test_cycles_from_strongly_connected_components() {
+        let graph = DependencyGraph::from_dependencies(&[
+            dep("a.dart", "b.dart"),
+            dep("b.dart", "c.dart"),
+            dep("c.dart", "a.dart"),
+            dep("c.dart", "d.dart"),
+            dep("d.dart", "e.dart"),
+            dep("e.dart", "d.dart"),
+            dep("f.dart", "a.dart"),
+        ]);
+
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edge_count(), 7);
+        assert_eq!(graph.cycles(), vec![
+            vec!["a.dart".to_string(), "b.dart".to_string(), "c.dart".to_string()],
+            vec!["d.dart".to_string(), "e.dart".to_string()],
+        ]);
+    }
+
+    #[test]
+    fn test_acyclic_graph_has_no_cycles() {
+        let graph = DependencyGraph::from_dependencies(&[
+            dep("lib/a.dart", "lib/b.dart"),
+            dep("lib/a.dart", "lib/c.dart"),
+            dep("lib/b.dart", "lib/c.dart"),
+        ]);
+        assert!(graph.cycles().is_empty());
+        assert_eq!(graph.strongly_connected_components().len(), 3);
+    }
+
+    #[test]
+    fn test_levels_collapse_cycles() {
+        let graph = DependencyGraph::from_dependencies(&[
+            dep("app.dart", "a.dart"),
+            dep("a.dart", "b.dart"),
+            dep("b.dart", "a.dart"),
+            dep("b.dart", "util.dart"),
+        ]);
+        let levels = graph.levels();
+        let level = |name: &str| levels[graph.index[name]];
+
+        assert_eq!(level("util.dart"), 1);
+        assert_eq!(level("a.dart"), 2);
+        assert_eq!(level("b.dart"), 2);
+        assert_eq!(level("app.dart"), 3);
+    }
+
+    #[test]
+    fn test_pagerank_favors_shared_dependencies() {
+        let graph = DependencyGraph::from_dependencies(&[
+            dep("a.dart", "core.dart"),
+            dep("b.dart", "core.dart"),
+            dep("c.dart", "core.dart"),
+            dep("c.dart", "b.dart"),
+        ]);
+        let rank = graph.pagerank();
+        let core = graph.index["core.dart"];
+
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(rank.iter().enumerate().all(|(i, &r)| i == core || r < rank[core]));
+        assert!(rank[graph.index["b.dart"]] > rank[graph.index["a.dart"]]);
+    }
+
+    #[test]
+    fn test_betweenness_of_bridge() {
+        let graph = DependencyGraph::from_dependencies(&[
+            dep("a.dart", "hub.dart"),
+            dep("b.dart", "hub.dart"),
+            dep("hub.dart", "c.dart"),
+        ]);
+        let betweenness = graph.betweenness();
+
+        // hub.dart lies on a->c and b->c, normalized by (4 - 1)(4 - 2) pairs
+        assert!((betweenness[graph.index["hub.dart"]] - 2.0 / 6.0).abs() < 1e-9);
+        assert_eq!(betweenness[graph.index["a.dart"]], 0.0);
+    }
+
+    #[test]
+    fn test_louvain_separates_clusters() {
+        let graph = DependencyGraph::from_dependencies(&[
+            dep("a1.dart", "a2.dart"),
+            dep("a2.dart", "a3.dart"),
+            dep("a3.dart", "a1.dart"),
+            dep("b1.dart", "b2.dart"),
+            dep("b2.dart", "b3.dart"),
+            dep("b3.dart", "b1.dart"),
+            dep("a1.dart", "b1.dart"),
+        ]);
+        let communities = graph.louvain();
+        let community = |name: &str| communities[graph.index[name]];
+
+        assert_eq!(community("a1.dart"), community("a3.dart"));
+        assert_eq!(community("b1.dart"), community("b2.dart"));
+        assert_ne!(community("a1.dart"), community("b1.dart"));
+        assert!(graph.modularity(&communities) > graph.modularity(&vec![0; graph.node_count()]));
+    }
+}
@@ -0,0 +1,455 @@
+//! Graph interchange formats for external tools: GraphML (yEd, Gephi), GEXF with
+//! the timeline as dynamic spells (Gephi) and DOT (Graphviz).
+
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::NodeMetrics;
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::node_metrics_or_derived;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
+/// Imports from one file to another, merged into a single edge
+#[derive(Debug, Clone, PartialEq)]
+struct Edge {
+    relationship_type: String,
+    /// Number of import statements between the two files
+    weight: u32,
+    /// First line importing the target, when the analyzer reports it
+    line_number: Option<u32>,
+}
+
+/// Graph of one snapshot with nodes and edges in a stable order
+struct SnapshotGraph {
+    nodes: BTreeMap<String, NodeMetrics>,
+    edges: BTreeMap<(String, String), Edge>,
+}
+
+impl SnapshotGraph {
+    fn new(snapshot: &CommitSnapshot) -> Self {
+        let result = &snapshot.analysis_result;
+        let mut nodes: BTreeMap<String, NodeMetrics> = node_metrics_or_derived(result).into_iter().collect();
+        for file in &result.analyzed_files {
+            nodes.entry(normalize_path(file)).or_default();
+        }
+
+        let mut edges: BTreeMap<(String, String), Edge> = BTreeMap::new();
+        for dep in &result.dependencies {
+            let key = (normalize_path(&dep.source_file), normalize_path(&dep.target_file));
+            nodes.entry(key.0.clone()).or_default();
+            nodes.entry(key.1.clone()).or_default();
+            let edge = edges.entry(key).or_insert_with(|| Edge {
+                relationship_type: dep.relationship_type.name().to_string(),
+                weight: 0,
+                line_number: None,
+            });
+            edge.weight += 1;
+            edge.line_number = match (edge.line_number, dep.line_number) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+        }
+
+        Self { nodes, edges }
+    }
+}
+
+/// Attribute type, named as in GraphML; GEXF calls `int` `integer`
+#[derive(Clone, Copy)]
+enum AttributeType {
+    Int,
+    Double,
+    Boolean,
+    String,
+}
+
+impl AttributeType {
+    fn graphml(self) -> &'static str {
+        match self {
+            AttributeType::Int => "int",
+            AttributeType::Double => "double",
+            AttributeType::Boolean => "boolean",
+            AttributeType::String => "string",
+        }
+    }
+
+    fn gexf(self) -> &'static str {
+        match self {
+            AttributeType::Int => "integer",
+            other => other.graphml(),
+        }
+    }
+}
+
+const NODE_ATTRIBUTES: [(&str, AttributeType); 10] = [
+    ("folder", AttributeType::String),
+    ("component_dependency", AttributeType::Int),
+    ("in_degree", AttributeType::Int),
+    ("out_degree", AttributeType::Int),
+    ("instability", AttributeType::Double),
+    ("sloc", AttributeType::Int),
+    ("in_cycle", AttributeType::Boolean),
+    ("is_orphan", AttributeType::Boolean),
+    ("abstractness", AttributeType::Double),
+    ("distance", AttributeType::Double),
+];
+
+const EDGE_ATTRIBUTES: [(&str, AttributeType); 3] = [
+    ("relationship_type", AttributeType::String),
+    ("weight", AttributeType::Int),
+    ("line_number", AttributeType::Int),
+];
+
+/// Folder of a normalized path, empty at the root
+fn folder_of(path: &str) -> &str {
+    path.rsplit_once('/').map(|(folder, _)| folder).unwrap_or("")
+}
+
+/// Values in `NODE_ATTRIBUTES` order; None for metrics the analyzer did not report
+fn node_values(path: &str, metrics: &NodeMetrics) -> [Option<String>; 10] {
+    [
+        Some(folder_of(path).to_string()),
+        Some(metrics.component_dependency.to_string()),
+        Some(metrics.in_degree.to_string()),
+        Some(metrics.out_degree.to_string()),
+        Some(metrics.instability.to_string()),
+        Some(metrics.sloc.to_string()),
+        Some(metrics.in_cycle.to_string()),
+        Some(metrics.is_orphan.to_string()),
+        metrics.abstractness.map(|v| v.to_string()),
+        metrics.distance.map(|v| v.to_string()),
+    ]
+}
+
+/// Values in `EDGE_ATTRIBUTES` order
+fn edge_values(edge: &Edge) -> [Option<String>; 3] {
+    [
+        Some(edge.relationship_type.clone()),
+        Some(edge.weight.to_string()),
+        edge.line_number.map(|v| v.to_string()),
+    ]
+}
+
+pub(crate) fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// GraphML document with one directed graph of a single snapshot
+pub fn graphml(snapshot: &CommitSnapshot) -> String {
+    let mut out = String::new();
+    write_graphml(&mut out, snapshot).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_graphml(out: &mut String, snapshot: &CommitSnapshot) -> fmt::Result {
+    let graph = SnapshotGraph::new(snapshot);
+    let commit = &snapshot.commit_info;
+
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    for (name, kind) in [("commit_hash", "string"), ("author", "string"), ("timestamp", "long")] {
+        writeln!(out, r#"  <key id="{0}" for="graph" attr.name="{0}" attr.type="{1}"/>"#, name, kind)?;
+    }
+    for (name, kind) in NODE_ATTRIBUTES {
+        writeln!(out, r#"  <key id="{0}" for="node" attr.name="{0}" attr.type="{1}"/>"#, name, kind.graphml())?;
+    }
+    for (name, kind) in EDGE_ATTRIBUTES {
+        writeln!(out, r#"  <key id="{0}" for="edge" attr.name="{0}" attr.type="{1}"/>"#, name, kind.graphml())?;
+    }
+
+    writeln!(out, r#"  <graph id="{}" edgedefault="directed">"#, escape_xml(&commit.hash))?;
+    writeln!(out, r#"    <data key="commit_hash">{}</data>"#, escape_xml(&commit.hash))?;
+    writeln!(out, r#"    <data key="author">{}</data>"#, escape_xml(&commit.author_name))?;
+    writeln!(out, r#"    <data key="timestamp">{}</data>"#, commit.timestamp)?;
+
+    for (path, metrics) in &graph.nodes {
+        writeln!(out, r#"    <node id="{}">"#, escape_xml(path))?;
+        for ((name, _), value) in NODE_ATTRIBUTES.iter().zip(node_values(path, metrics)) {
+            if let Some(value) = value {
+                writeln!(out, r#"      <data key="{}">{}</data>"#, name, escape_xml(&value))?;
+            }
+        }
+        writeln!(out, "    </node>")?;
+    }
+
+    for (index, ((source, target), edge)) in graph.edges.iter().enumerate() {
+        writeln!(
+            out,
+            r#"    <edge id="e{}" source="{}" target="{}">"#,
+            index, escape_xml(source), escape_xml(target)
+        )?;
+        for ((name, _), value) in EDGE_ATTRIBUTES.iter().zip(edge_values(edge)) {
+            if let Some(value) = value {
+                writeln!(out, r#"      <data key="{}">{}</data>"#, name, escape_xml(&value))?;
+            }
+        }
+        writeln!(out, "    </edge>")?;
+    }
+
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")
+}
+
+/// Runs of equal consecutive values as `(value, start, end)`; a run lasts from its
+/// first snapshot's timestamp until the next snapshot without it. The last run of
+/// the timeline stays open.
+fn runs<T: PartialEq + Clone>(values: &[Option<T>], times: &[i64]) -> Vec<(T, i64, Option<i64>)> {
+    let mut runs: Vec<(T, i64, Option<i64>)> = Vec::new();
+    let mut current: Option<(T, i64)> = None;
+    for (index, value) in values.iter().enumerate() {
+        if current.as_ref().map(|(v, _)| Some(v)) != Some(value.as_ref()) {
+            if let Some((v, start)) = current.take() {
+                runs.push((v, start, Some(times[index])));
+            }
+            current = value.clone().map(|v| (v, times[index]));
+        }
+    }
+    if let Some((v, start)) = current {
+        runs.push((v, start, None));
+    }
+    runs
+}
+
+fn interval(start: i64, end: Option<i64>) -> String {
+    match end {
+        Some(end) => format!(r#"start="{}" endopen="{}""#, start, end),
+        None => format!(r#"start="{}""#, start),
+    }
+}
+
+/// GEXF 1.3 document. A single snapshot is a static graph; a timeline is a
+/// dynamic graph where every node and edge carries spells for the snapshots it
+/// exists in and attribute values change at the snapshots where they change.
+/// Times are commit timestamps in Unix seconds.
+pub fn gexf(snapshots: &[CommitSnapshot], description: &str) -> String {
+    let mut out = String::new();
+    write_gexf(&mut out, snapshots, description).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_gexf(out: &mut String, snapshots: &[CommitSnapshot], description: &str) -> fmt::Result {
+    let graphs: Vec<SnapshotGraph> = snapshots.iter().map(SnapshotGraph::new).collect();
+    let times: Vec<i64> = snapshots.iter().map(|s| s.commit_info.timestamp).collect();
+    let dynamic = snapshots.len() > 1;
+
+    let nodes: BTreeSet<&String> = graphs.iter().flat_map(|g| g.nodes.keys()).collect();
+    let edges: BTreeSet<&(String, String)> = graphs.iter().flat_map(|g| g.edges.keys()).collect();
+
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#)?;
+    writeln!(out, r#"  <meta lastmodifieddate="{}">"#, chrono::Utc::now().format("%Y-%m-%d"))?;
+    writeln!(out, "    <creator>ChronoGraph</creator>")?;
+    writeln!(out, "    <description>{}</description>", escape_xml(description))?;
+    writeln!(out, "  </meta>")?;
+    if dynamic {
+        writeln!(
+            out,
+            r#"  <graph defaultedgetype="directed" mode="dynamic" timeformat="double" timerepresentation="interval">"#
+        )?;
+    } else {
+        writeln!(out, r#"  <graph defaultedgetype="directed" mode="static">"#)?;
+    }
+
+    let mode = if dynamic { "dynamic" } else { "static" };
+    for (class, attributes) in [("node", &NODE_ATTRIBUTES[..]), ("edge", &EDGE_ATTRIBUTES[..])] {
+        writeln!(out, r#"    <attributes class="{}" mode="{}">"#, class, mode)?;
+        for (name, kind) in attributes {
+            writeln!(out, r#"      <attribute id="{0}" title="{0}" type="{1}"/>"#, name, kind.gexf())?;
+        }
+        writeln!(out, "    </attributes>")?;
+    }
+
+    writeln!(out, "    <nodes>")?;
+    for path in nodes {
+        let values: Vec<Option<[Option<String>; 10]>> = graphs
+            .iter()
+            .map(|g| g.nodes.get(path).map(|m| node_values(path, m)))
+            .collect();
+        writeln!(out, r#"      <node id="{0}" label="{0}">"#, escape_xml(path))?;
+        write_attvalues(out, &NODE_ATTRIBUTES, &values, &times)?;
+        write_spells(out, &values, &times, dynamic)?;
+        writeln!(out, "      </node>")?;
+    }
+    writeln!(out, "    </nodes>")?;
+
+    writeln!(out, "    <edges>")?;
+    for (index, key) in edges.into_iter().enumerate() {
+        let values: Vec<Option<[Option<String>; 3]>> = graphs
+            .iter()
+            .map(|g| g.edges.get(key).map(edge_values))
+            .collect();
+        writeln!(
+            out,
+            r#"      <edge id="e{}" source="{}" target="{}">"#,
+            index, escape_xml(&key.0), escape_xml(&key.1)
+        )?;
+        write_attvalues(out, &EDGE_ATTRIBUTES, &values, &times)?;
+        write_spells(out, &values, &times, dynamic)?;
+        writeln!(out, "      </edge>")?;
+    }
+    writeln!(out, "    </edges>")?;
+
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</gexf>")
+}
+
+fn write_attvalues<const N: usize>(
+    out: &mut String,
+    attributes: &[(&str, AttributeType); N],
+    values: &[Option<[Option<String>; N]>],
+    times: &[i64],
+) -> fmt::Result {
+    writeln!(out, "        <attvalues>")?;
+    for (index, (name, _)) in attributes.iter().enumerate() {
+        let series: Vec<Option<String>> = values
+            .iter()
+            .map(|v| v.as_ref().and_then(|v| v[index].clone()))
+            .collect();
+        for (value, start, end) in runs(&series, times) {
+            if times.len() > 1 {
+                writeln!(
+                    out,
+                    r#"          <attvalue for="{}" value="{}" {}/>"#,
+                    name, escape_xml(&value), interval(start, end)
+                )?;
+            } else {
+                writeln!(out, r#"          <attvalue for="{}" value="{}"/>"#, name, escape_xml(&value))?;
+            }
+        }
+    }
+    writeln!(out, "        </attvalues>")
+}
+
+fn write_spells<T>(out: &mut String, values: &[Option<T>], times: &[i64], dynamic: bool) -> fmt::Result {
+    if !dynamic {
+        return Ok(());
+    }
+    let present: Vec<Option<()>> = values.iter().map(|v| v.as_ref().map(|_| ())).collect();
+    writeln!(out, "        <spells>")?;
+    for ((), start, end) in runs(&present, times) {
+        writeln!(out, "          <spell {}/>", interval(start, end))?;
+    }
+    writeln!(out, "        </spells>")
+}
+
+fn quote_dot(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Graphviz digraph of a snapshot, optionally with one cluster per folder.
+/// Files in a cycle and the edges between them are drawn in red.
+pub fn dot(snapshot: &CommitSnapshot, cluster_by_folder: bool) -> String {
+    let mut out = String::new();
+    write_dot(&mut out, snapshot, cluster_by_folder).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_dot(out: &mut String, snapshot: &CommitSnapshot, cluster_by_folder: bool) -> fmt::Result {
+    let graph = SnapshotGraph::new(snapshot);
+
+    writeln!(out, "digraph {} {{", quote_dot(&snapshot.commit_info.hash))?;
+    writeln!(out, "  rankdir=LR;")?;
+    writeln!(out, "  node [shape=box, fontname=\"Helvetica\"];")?;
+
+    let node_line = |path: &str, metrics: &NodeMetrics, label: &str| {
+        let color = if metrics.in_cycle { ", color=red" } else { "" };
+        format!("{} [label={}{}];", quote_dot(path), quote_dot(label), color)
+    };
+
+    if cluster_by_folder {
+        let mut folders: BTreeMap<&str, Vec<(&String, &NodeMetrics)>> = BTreeMap::new();
+        for (path, metrics) in &graph.nodes {
+            folders.entry(folder_of(path)).or_default().push((path, metrics));
+        }
+        for (index, (folder, nodes)) in folders.into_iter().enumerate() {
+            let indent = if folder.is_empty() {
+                "  "
+            } else {
+                writeln!(out, "  subgraph cluster_{} {{", index)?;
+                writeln!(out, "    label={};", quote_dot(folder))?;
+                "    "
+            };
+            for (path, metrics) in nodes {
+                let name = path.rsplit('/').next().unwrap_or(path);
+                writeln!(out, "{}{}", indent, node_line(path, metrics, name))?;
+            }
+            if !folder.is_empty() {
+                writeln!(out, "  }}")?;
+            }
+        }
+    } else {
+        for (path, metrics) in &graph.nodes {
+            writeln!(out, "  {}", node_line(path, metrics, path))?;
+        }
+    }
+
+    for ((source, target), edge) in &graph.edges {
+        let cycle_of = |node: &String| graph.nodes.get(node).and_then(|m| m.cycle_id);
+        let cyclic = cycle_of(source).is_some_and(|c| cycle_of(target) == Some(c));
+        let mut attributes = Vec::new();
+        if edge.weight > 1 {
+            attributes.push(format!("label=\"{}\"", edge.weight));
+        }
+        if cyclic {
+            attributes.push("color=red".to_string());
+        }
+        if attributes.is_empty() {
+            writeln!(out, "  {} -> {};", quote_dot(source), quote_dot(target))?;
+        } else {
+            writeln!(out, "  {} -> {} [{}];", quote_dot(source), quote_dot(target), attributes.join(", "))?;
+        }
+    }
+
+    writeln!(out, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_graph_exports() {
+        let first = snapshot("c1", 100, vec![
+            dep("lib/a.dart", "lib/ui/b.dart"),
+            dep("lib/ui/b.dart", "lib/a.dart"),
+        ]);
+        let second = snapshot("c2", 200, vec![dep("lib/a.dart", "lib/ui/b.dart")]);
+
+        let graphml = graphml(&second);
+        assert!(graphml.contains(r#"<node id="lib/ui/b.dart">"#));
+        assert!(graphml.contains(r#"<edge id="e0" source="lib/a.dart" target="lib/ui/b.dart">"#));
+        assert!(graphml.contains(r#"<data key="relationship_type">import</data>"#));
+
+        // The back edge only exists until the second commit; the forward edge stays open
+        let gexf = gexf(&[first, second.clone()], "test");
+        assert!(gexf.contains(r#"mode="dynamic""#));
+        assert!(gexf.contains(r#"<edge id="e1" source="lib/ui/b.dart" target="lib/a.dart">"#));
+        assert!(gexf.contains(r#"<spell start="100" endopen="200"/>"#));
+        assert!(gexf.contains(r#"<spell start="100"/>"#));
+        assert!(gexf.contains(r#"<attvalue for="in_cycle" value="true" start="100" endopen="200"/>"#));
+        assert!(gexf.contains(r#"<attvalue for="in_cycle" value="false" start="200"/>"#));
+
+        let dot = dot(&second, true);
+        assert!(dot.contains("subgraph cluster_"));
+        assert!(dot.contains(r#"label="lib/ui";"#));
+        assert!(dot.contains(r#""lib/a.dart" -> "lib/ui/b.dart";"#));
+
+        // An edge joining two separate cycles is not part of either
+        let joined = snapshot("c3", 300, vec![
+            dep("lib/a.dart", "lib/b.dart"),
+            dep("lib/b.dart", "lib/a.dart"),
+            dep("lib/b.dart", "lib/c.dart"),
+            dep("lib/c.dart", "lib/d.dart"),
+            dep("lib/d.dart", "lib/c.dart"),
+        ]);
+        let joined_dot = super::dot(&joined, false);
+        assert!(joined_dot.contains(r#""lib/a.dart" -> "lib/b.dart" [color=red];"#));
+        assert!(joined_dot.contains(r#""lib/c.dart" -> "lib/d.dart" [color=red];"#));
+        assert!(joined_dot.contains(r#""lib/b.dart" -> "lib/c.dart";"#));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_view::normalize_path;
+use crate::file_history::FileHistory;
+use crate::graph_algorithms::{node_metrics_or_derived, DependencyGraph};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// A file ranked by how much change pressure meets size and centrality
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hotspot {
+    pub path: String,
+    pub rank: usize,
+    /// Commits touching the file up to the snapshot
+    pub change_count: usize,
+    /// Lines added plus removed up to the snapshot
+    pub churn: usize,
+    pub sloc: u32,
+    pub in_degree: u32,
+    pub component_dependency: u32,
+    pub pagerank: f64,
+    /// Normalized (0-1) components of the score
+    pub change_score: f64,
+    pub size_score: f64,
+    pub centrality_score: f64,
+    /// change_score * (size_score + centrality_score) / 2
+    pub score: f64,
+}
+
+/// Hotspots of one analyzed snapshot, highest score first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotspotReport {
+    pub commit_hash: String,
+    pub timestamp: i64,
+    pub hotspots: Vec<Hotspot>,
+}
+
+/// Rank the files of a snapshot. Change data comes from the full git history up to
+/// the snapshot commit; files without history score zero on change pressure.
+pub fn hotspots(snapshot: &CommitSnapshot, history: &FileHistory, limit: Option<usize>) -> HotspotReport {
+    let result = &snapshot.analysis_result;
+    let timestamp = snapshot.commit_info.timestamp;
+    let metrics = node_metrics_or_derived(result);
+
+    let graph = DependencyGraph::from_analysis(result);
+    let pagerank = graph.pagerank();
+
+    let files: BTreeSet<String> = result
+        .analyzed_files
+        .iter()
+        .map(|p| normalize_path(p))
+        .chain(metrics.keys().cloned())
+        .collect();
+
+    let mut hotspots: Vec<Hotspot> = files
+        .into_iter()
+        .map(|path| {
+            let node = metrics.get(&path);
+            let temporal = history.node_metadata(&path, Some(timestamp));
+            Hotspot {
+                change_count: temporal.as_ref().map(|t| t.modification_commits.len() + 1).unwrap_or(0),
+                churn: temporal.as_ref().map(|t| t.lines_added + t.lines_removed).unwrap_or(0),
+                sloc: node.map(|m| m.sloc).unwrap_or(0),
+                in_degree: node.map(|m| m.in_degree).unwrap_or(0),
+                component_dependency: node.map(|m| m.component_dependency).unwrap_or(0),
+                pagerank: graph
+                    .node_index(Path::new(&path))
+                    .map(|idx| pagerank[idx])
+                    .unwrap_or(0.0),
+                path,
+                rank: 0,
+                change_score: 0.0,
+                size_score: 0.0,
+                centrality_score: 0.0,
+                score: 0.0,
+            }
+        })
+        .collect();
+
+    let max_changes = max_of(&hotspots, |h| h.change_count as f64);
+    let max_churn = max_of(&hotspots, |h| h.churn as f64);
+    let max_sloc = max_of(&hotspots, |h| h.sloc as f64);
+    let max_in_degree = max_of(&hotspots, |h| h.in_degree as f64);
+    let max_cd = max_of(&hotspots, |h| h.component_dependency as f64);
+    let max_pagerank = max_of(&hotspots, |h| h.pagerank);
+
+    for hotspot in &mut hotspots {
+        hotspot.change_score = (ratio(hotspot.change_count as f64, max_changes)
+            + ratio(hotspot.churn as f64, max_churn))
+            / 2.0;
+        hotspot.size_score = ratio(hotspot.sloc as f64, max_sloc);
+        hotspot.centrality_score = (ratio(hotspot.in_degree as f64, max_in_degree)
+            + ratio(hotspot.component_dependency as f64, max_cd)
+            + ratio(hotspot.pagerank, max_pagerank))
+            / 3.0;
+        hotspot.score = hotspot.change_score * (hotspot.size_score + hotspot.centrality_score) / 2.0;
+    }
+
+    hotspots.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| {
+                (b.size_score + b.centrality_score)
+                    .partial_cmp(&(a.size_score + a.centrality_score))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    if let Some(limit) = limit {
+        hotspots.truncate(limit);
+    }
+    for (rank, hotspot) in hotspots.iter_mut().enumerate() {
+        hotspot.rank = rank + 1;
+    }
+
+    HotspotReport {
+        commit_hash: snapshot.commit_info.hash.clone(),
+        timestamp,
+        hotspots,
+    }
+}
+
+/// Hotspot reports for every snapshot, oldest first
+pub fn hotspot_timeline(
+    snapshots: &[CommitSnapshot],
+    history: &FileHistory,
+    limit: Option<usize>,
+) -> Vec<HotspotReport> {
+    snapshots
+        .iter()
+        .map(|snapshot| hotspots(snapshot, history, limit))
+        .collect()
+}
+
+fn max_of(hotspots: &[Hotspot], value: impl Fn(&Hotspot) -> f64) -> f64 {
+    hotspots.iter().map(value).fold(0.0, f64::max)
+}
+
+fn ratio(value: f64, max: f64) -> f64 {
+    if max > 0.0 {
+        value / max
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{commit_changes, dep, modified, snapshot};
+
+    #[test]
+    fn test_hotspots_combine_change_and_centrality() {
+        let history = FileHistory::build(
+            &[
+                commit_changes("c1", "alice", 100, vec![modified("core.dart", 50), modified("a.dart", 5), modified("b.dart", 5)]),
+                commit_changes("c2", "alice", 200, vec![modified("core.dart", 20)]),
+                commit_changes("c3", "alice", 300, vec![modified("core.dart", 10), modified("a.dart", 1)]),
+                commit_changes("c4", "alice", 400, vec![modified("b.dart", 100)]),
+            ],
+            None,
+        );
+        let snap = snapshot(
+            "c3",
+            300,
+            vec![dep("a.dart", "core.dart"), dep("b.dart", "core.dart"), dep("a.dart", "b.dart")],
+        );
+
+        let report = hotspots(&snap, &history, None);
+        assert_eq!(report.hotspots.len(), 3);
+        let top = &report.hotspots[0];
+        assert_eq!(top.path, "core.dart");
+        assert_eq!(top.rank, 1);
+        assert_eq!(top.change_count, 3);
+        assert_eq!(top.churn, 80);
+        assert_eq!(top.in_degree, 2);
+        assert_eq!(top.change_score, 1.0);
+
+        // Changes after the snapshot are not counted
+        let b = report.hotspots.iter().find(|h| h.path == "b.dart").unwrap();
+        assert_eq!(b.change_count, 1);
+
+        assert_eq!(hotspots(&snap, &history, Some(1)).hotspots.len(), 1);
+    }
+}
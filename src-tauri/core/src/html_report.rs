@@ -0,0 +1,507 @@
+//! Single-file HTML report of an analysis for architecture reviews and CI
+//! artifacts. Charts and diagrams are inline SVG, so it opens offline without
+//! scripts or the desktop app.
+
+use crate::architecture_rules::RuleViolationCount;
+use crate::chronograph_engine::{ChronoGraphEngine, CommitSnapshot};
+use crate::cycle_tracker::TrackedCycle;
+use crate::dependency_analyzer::AnalysisIssue;
+use crate::diagram_export;
+use crate::graph_export::escape_xml;
+use crate::hotspots::HotspotReport;
+use crate::metric_series::MetricTimeSeries;
+use crate::snapshot_diff::SnapshotDiff;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+
+/// What the report covers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportOptions {
+    /// Length in days of the periods dependency changes are summarized over
+    pub period_days: u32,
+    /// Commits to draw diagrams of; the first and latest analyzed commits when empty
+    pub diagram_commits: Vec<String>,
+    /// Folder depth files are collapsed to in diagrams
+    pub folder_depth: usize,
+    pub hotspot_limit: usize,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            period_days: 30,
+            diagram_commits: Vec::new(),
+            folder_depth: 2,
+            hotspot_limit: 10,
+        }
+    }
+}
+
+/// Global metrics charted on the timeline, with their titles
+const CHARTED_METRICS: [(&str, &str); 6] = [
+    ("num_nodes", "Files"),
+    ("num_edges", "Dependencies"),
+    ("cycle_count", "Cycles"),
+    ("average_component_dependency", "Average component dependency"),
+    ("normalized_ccd", "Normalized CCD"),
+    ("architecture_quality_score", "Quality score"),
+];
+
+/// Largest node metric changes listed per period
+const PERIOD_CHANGE_LIMIT: usize = 5;
+
+/// How the graph changed over one period of the timeline
+struct PeriodChange {
+    start: i64,
+    end: i64,
+    /// Analyzed commits in the period
+    commits: usize,
+    diff: SnapshotDiff,
+}
+
+/// Split the timeline into periods of `period_days` from the first commit and
+/// diff the last snapshot of each period against the one before the period
+fn period_changes(
+    snapshots: &[CommitSnapshot],
+    period_days: u32,
+    diff: impl Fn(&CommitSnapshot, &CommitSnapshot) -> Result<SnapshotDiff>,
+) -> Result<Vec<PeriodChange>> {
+    let Some(first) = snapshots.first() else {
+        return Ok(Vec::new());
+    };
+    let length = i64::from(period_days.max(1)) * 86_400;
+    let origin = first.commit_info.timestamp;
+    let period_of = |snapshot: &CommitSnapshot| (snapshot.commit_info.timestamp - origin).div_euclid(length);
+
+    let mut changes = Vec::new();
+    let mut previous = first;
+    let mut index = 1;
+    while index < snapshots.len() {
+        let period = period_of(&snapshots[index]);
+        let mut last = index;
+        while last + 1 < snapshots.len() && period_of(&snapshots[last + 1]) == period {
+            last += 1;
+        }
+        let current = &snapshots[last];
+        changes.push(PeriodChange {
+            start: origin + period * length,
+            end: origin + (period + 1) * length,
+            commits: last - index + 1,
+            diff: diff(previous, current)?,
+        });
+        previous = current;
+        index = last + 1;
+    }
+    Ok(changes)
+}
+
+/// Everything the report shows, gathered from the engine up front
+struct ReportData<'a> {
+    title: String,
+    snapshots: &'a [CommitSnapshot],
+    series: MetricTimeSeries,
+    hotspots: HotspotReport,
+    cycles: &'a [TrackedCycle],
+    has_rules: bool,
+    violation_timeline: Vec<RuleViolationCount>,
+    violations: Vec<AnalysisIssue>,
+    periods: Vec<PeriodChange>,
+    /// Caption and SVG per drawn commit
+    diagrams: Vec<(String, String)>,
+}
+
+/// Render the report of everything the engine analyzed
+pub fn report(engine: &ChronoGraphEngine, options: &ReportOptions) -> Result<String> {
+    let snapshots = engine.get_snapshots();
+    let (first, latest) = match (snapshots.first(), snapshots.last()) {
+        (Some(first), Some(latest)) => (first, latest),
+        _ => return Err(anyhow!("No analysis available")),
+    };
+
+    let mut drawn: Vec<&CommitSnapshot> = Vec::new();
+    for hash in &options.diagram_commits {
+        drawn.push(engine.find_snapshot(hash).ok_or_else(|| anyhow!("No analyzed snapshot for commit {}", hash))?);
+    }
+    if drawn.is_empty() {
+        drawn.push(first);
+        if latest.commit_info.hash != first.commit_info.hash {
+            drawn.push(latest);
+        }
+    }
+    let diagrams = drawn
+        .into_iter()
+        .map(|snapshot| {
+            let caption = format!(
+                "{} on {} by {}",
+                short_hash(&snapshot.commit_info.hash), date(snapshot.commit_info.timestamp), snapshot.commit_info.author_name
+            );
+            (caption, diagram_export::svg(snapshot, options.folder_depth, None))
+        })
+        .collect();
+
+    let config = engine.get_config();
+    let repository = engine.get_repo_info().map_or(config.github_url.as_str(), |info| info.original_url.as_str());
+    let title = match config.subfolder {
+        Some(ref subfolder) => format!("{} ({})", repository, subfolder),
+        None => repository.to_string(),
+    };
+
+    let data = ReportData {
+        title,
+        snapshots,
+        series: engine.global_metric_series(),
+        hotspots: engine.hotspots(None, Some(options.hotspot_limit))?,
+        cycles: engine.get_cycle_history(),
+        has_rules: !engine.get_architecture_rules().rules.is_empty(),
+        violation_timeline: engine.rule_violation_timeline(),
+        violations: engine.rule_violations(&latest.commit_info.hash)?,
+        periods: period_changes(snapshots, options.period_days, |from, to| {
+            engine.diff_snapshots(&from.commit_info.hash, &to.commit_info.hash)
+        })?,
+        diagrams,
+    };
+
+    let mut out = String::new();
+    write_report(&mut out, &data).expect("writing to a String cannot fail");
+    Ok(out)
+}
+
+fn date(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(8)]
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.3}", value)
+    }
+}
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2rem auto; max-width: 1200px; padding: 0 1rem; color: #0f172a; }
+h1 { margin-bottom: 0.25rem; }
+h2 { margin-top: 2.5rem; border-bottom: 1px solid #e2e8f0; padding-bottom: 0.25rem; }
+.meta { color: #64748b; }
+.cards { display: flex; flex-wrap: wrap; gap: 0.75rem; }
+.card { border: 1px solid #e2e8f0; border-radius: 6px; padding: 0.5rem 1rem; min-width: 8rem; }
+.card b { display: block; font-size: 1.4rem; }
+.charts { display: grid; grid-template-columns: repeat(auto-fill, minmax(380px, 1fr)); gap: 1rem; }
+table { border-collapse: collapse; width: 100%; font-size: 0.9rem; }
+th, td { text-align: left; padding: 0.3rem 0.5rem; border-bottom: 1px solid #e2e8f0; vertical-align: top; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+code { font-size: 0.85rem; }
+.error { color: #dc2626; }
+.warning { color: #d97706; }
+.better { color: #16a34a; }
+.worse { color: #dc2626; }
+figure { margin: 1rem 0; overflow-x: auto; }
+figcaption { color: #64748b; margin-bottom: 0.5rem; }
+";
+
+fn write_report(out: &mut String, data: &ReportData) -> fmt::Result {
+    let first = &data.snapshots[0].commit_info;
+    let latest = &data.snapshots[data.snapshots.len() - 1].commit_info;
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>ChronoGraph report: {}</title>", escape_xml(&data.title))?;
+    writeln!(out, "<style>{}</style>\n</head>\n<body>", STYLE)?;
+    writeln!(out, "<h1>{}</h1>", escape_xml(&data.title))?;
+    writeln!(
+        out,
+        "<p class=\"meta\">{} analyzed commits from {} to {} ({}), generated {}</p>",
+        data.snapshots.len(), date(first.timestamp), date(latest.timestamp),
+        short_hash(&latest.hash), date(chrono::Utc::now().timestamp())
+    )?;
+
+    write_summary(out, data)?;
+    write_timeline(out, data)?;
+    write_hotspots(out, &data.hotspots)?;
+    write_cycles(out, data.cycles)?;
+    write_violations(out, data)?;
+    write_periods(out, &data.periods)?;
+
+    writeln!(out, "<h2>Diagrams</h2>")?;
+    for (caption, svg) in &data.diagrams {
+        writeln!(out, "<figure>\n<figcaption>{}</figcaption>\n{}</figure>", escape_xml(caption), svg)?;
+    }
+
+    writeln!(out, "</body>\n</html>")
+}
+
+fn write_summary(out: &mut String, data: &ReportData) -> fmt::Result {
+    writeln!(out, "<div class=\"cards\">")?;
+    for (metric, title) in CHARTED_METRICS {
+        let latest = data.series.series.get(metric).and_then(|values| values.last().copied().flatten());
+        if let Some(value) = latest {
+            writeln!(out, "<div class=\"card\"><b>{}</b>{}</div>", format_value(value), title)?;
+        }
+    }
+    let active = data.cycles.iter().filter(|c| c.is_active()).count();
+    writeln!(out, "<div class=\"card\"><b>{}</b>Active cycles</div>", active)?;
+    writeln!(out, "</div>")
+}
+
+fn write_timeline(out: &mut String, data: &ReportData) -> fmt::Result {
+    writeln!(out, "<h2>Timeline</h2>\n<div class=\"charts\">")?;
+    for (metric, title) in CHARTED_METRICS {
+        if let Some(values) = data.series.series.get(metric) {
+            write_chart(out, title, &data.series.timestamps, &data.series.commit_hashes, values)?;
+        }
+    }
+    writeln!(out, "</div>")?;
+
+    writeln!(out, "<details>\n<summary>{} analyzed commits</summary>", data.snapshots.len())?;
+    writeln!(out, "<table>\n<tr><th>Date</th><th>Commit</th><th>Author</th><th>Message</th><th>Regressions</th></tr>")?;
+    for snapshot in data.snapshots.iter().rev() {
+        let commit = &snapshot.commit_info;
+        let regressions: Vec<String> = snapshot.regressions.iter().map(|r| escape_xml(&r.message)).collect();
+        writeln!(
+            out,
+            "<tr><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            date(commit.timestamp), short_hash(&commit.hash), escape_xml(&commit.author_name),
+            escape_xml(commit.message.lines().next().unwrap_or("")), regressions.join("<br>")
+        )?;
+    }
+    writeln!(out, "</table>\n</details>")
+}
+
+const CHART_WIDTH: f64 = 380.0;
+const CHART_HEIGHT: f64 = 150.0;
+const CHART_LEFT: f64 = 48.0;
+const CHART_TOP: f64 = 24.0;
+const CHART_BOTTOM: f64 = 20.0;
+
+/// Line chart of one metric over commit time; points carry the commit as tooltip
+fn write_chart(
+    out: &mut String,
+    title: &str,
+    timestamps: &[i64],
+    hashes: &[String],
+    values: &[Option<f64>],
+) -> fmt::Result {
+    let points: Vec<(i64, &str, f64)> = timestamps
+        .iter()
+        .zip(hashes)
+        .zip(values)
+        .filter_map(|((&t, hash), value)| value.map(|v| (t, hash.as_str(), v)))
+        .collect();
+    let (Some(&(t0, _, _)), Some(&(t1, _, _))) = (points.first(), points.last()) else {
+        return Ok(());
+    };
+    let min = points.iter().map(|p| p.2).fold(f64::INFINITY, f64::min);
+    let max = points.iter().map(|p| p.2).fold(f64::NEG_INFINITY, f64::max);
+
+    let plot_width = CHART_WIDTH - CHART_LEFT - 8.0;
+    let plot_height = CHART_HEIGHT - CHART_TOP - CHART_BOTTOM;
+    let x = |t: i64| match t1 - t0 {
+        0 => CHART_LEFT + plot_width / 2.0,
+        span => CHART_LEFT + (t - t0) as f64 / span as f64 * plot_width,
+    };
+    let y = |v: f64| match max - min {
+        range if range > 0.0 => CHART_TOP + (max - v) / range * plot_height,
+        _ => CHART_TOP + plot_height / 2.0,
+    };
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" font-family="sans-serif" font-size="11">"#,
+        CHART_WIDTH, CHART_HEIGHT
+    )?;
+    writeln!(out, r#"<text x="0" y="14" font-size="13" font-weight="bold">{}</text>"#, escape_xml(title))?;
+    writeln!(
+        out,
+        r##"<line x1="{0}" y1="{1}" x2="{0}" y2="{2}" stroke="#cbd5e1"/><line x1="{0}" y1="{2}" x2="{3}" y2="{2}" stroke="#cbd5e1"/>"##,
+        CHART_LEFT, CHART_TOP, CHART_TOP + plot_height, CHART_LEFT + plot_width
+    )?;
+    writeln!(
+        out,
+        r##"<text x="{0}" y="{1}" text-anchor="end" fill="#64748b">{2}</text><text x="{0}" y="{3}" text-anchor="end" fill="#64748b">{4}</text>"##,
+        CHART_LEFT - 4.0, CHART_TOP + 4.0, format_value(max), CHART_TOP + plot_height, format_value(min)
+    )?;
+    writeln!(
+        out,
+        r##"<text x="{}" y="{}" fill="#64748b">{}</text><text x="{}" y="{}" text-anchor="end" fill="#64748b">{}</text>"##,
+        CHART_LEFT, CHART_HEIGHT - 4.0, date(t0), CHART_LEFT + plot_width, CHART_HEIGHT - 4.0, date(t1)
+    )?;
+
+    let line: Vec<String> = points.iter().map(|&(t, _, v)| format!("{:.1},{:.1}", x(t), y(v))).collect();
+    writeln!(out, r##"<polyline points="{}" fill="none" stroke="#2563eb" stroke-width="1.5"/>"##, line.join(" "))?;
+    for &(t, hash, v) in &points {
+        writeln!(
+            out,
+            r##"<circle cx="{:.1}" cy="{:.1}" r="2.5" fill="#2563eb"><title>{} {}: {}</title></circle>"##,
+            x(t), y(v), short_hash(hash), date(t), format_value(v)
+        )?;
+    }
+    writeln!(out, "</svg>")
+}
+
+fn write_hotspots(out: &mut String, report: &HotspotReport) -> fmt::Result {
+    writeln!(out, "<h2>Hotspots</h2>")?;
+    if report.hotspots.is_empty() {
+        return writeln!(out, "<p>No hotspots.</p>");
+    }
+    writeln!(
+        out,
+        "<table>\n<tr><th>#</th><th>File</th><th>Score</th><th>Changes</th><th>Churn</th><th>SLOC</th><th>Dependents</th><th>Component dependency</th></tr>"
+    )?;
+    for hotspot in &report.hotspots {
+        writeln!(
+            out,
+            "<tr><td class=\"num\">{}</td><td><code>{}</code></td><td class=\"num\">{:.2}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            hotspot.rank, escape_xml(&hotspot.path), hotspot.score, hotspot.change_count,
+            hotspot.churn, hotspot.sloc, hotspot.in_degree, hotspot.component_dependency
+        )?;
+    }
+    writeln!(out, "</table>")
+}
+
+fn write_cycles(out: &mut String, cycles: &[TrackedCycle]) -> fmt::Result {
+    writeln!(out, "<h2>Cycle history</h2>")?;
+    if cycles.is_empty() {
+        return writeln!(out, "<p>No dependency cycles were found in any analyzed commit.</p>");
+    }
+    writeln!(out, "<table>\n<tr><th>Status</th><th>Introduced</th><th>Size</th><th>Members</th><th>Ended</th></tr>")?;
+    for cycle in cycles {
+        let status = if cycle.merged_into.is_some() {
+            "merged"
+        } else if cycle.resolved_in.is_some() {
+            "resolved"
+        } else {
+            "<span class=\"error\">active</span>"
+        };
+        let last = cycle.history.last();
+        let members: Vec<String> = last
+            .map(|point| point.members.iter().map(|m| format!("<code>{}</code>", escape_xml(m))).collect())
+            .unwrap_or_default();
+        let ended = match (&cycle.resolved_in, cycle.resolved_at) {
+            (Some(hash), Some(at)) => format!(
+                "{} <code>{}</code> by {}",
+                date(at), short_hash(hash), escape_xml(cycle.resolved_by.as_deref().unwrap_or(""))
+            ),
+            _ => String::new(),
+        };
+        writeln!(
+            out,
+            "<tr><td>{}</td><td>{} <code>{}</code> by {}</td><td class=\"num\">{} (max {})</td><td>{}</td><td>{}</td></tr>",
+            status, date(cycle.first_timestamp), short_hash(&cycle.first_commit), escape_xml(&cycle.first_author),
+            last.map_or(0, |point| point.size), cycle.max_size, members.join(", "), ended
+        )?;
+    }
+    writeln!(out, "</table>")
+}
+
+fn write_violations(out: &mut String, data: &ReportData) -> fmt::Result {
+    writeln!(out, "<h2>Rule violations</h2>")?;
+    if !data.has_rules {
+        return writeln!(out, "<p>No architecture rules are configured.</p>");
+    }
+
+    let timestamps: Vec<i64> = data.violation_timeline.iter().map(|c| c.timestamp).collect();
+    let hashes: Vec<String> = data.violation_timeline.iter().map(|c| c.commit_hash.clone()).collect();
+    let counts: Vec<Option<f64>> = data.violation_timeline.iter().map(|c| Some(c.violations as f64)).collect();
+    write_chart(out, "Violations", &timestamps, &hashes, &counts)?;
+
+    if data.violations.is_empty() {
+        return writeln!(out, "<p>The latest commit violates no rules.</p>");
+    }
+    writeln!(out, "<table>\n<tr><th>Level</th><th>Rule</th><th>Location</th><th>Message</th></tr>")?;
+    for issue in &data.violations {
+        let level = format!("{:?}", issue.level);
+        let location = match (&issue.file_path, issue.line_number) {
+            (Some(path), Some(line)) => format!("{}:{}", path.display(), line),
+            (Some(path), None) => path.display().to_string(),
+            _ => String::new(),
+        };
+        writeln!(
+            out,
+            "<tr><td class=\"{}\">{}</td><td>{}</td><td><code>{}</code></td><td>{}</td></tr>",
+            level.to_lowercase(), level, escape_xml(issue.rule.as_deref().unwrap_or("")),
+            escape_xml(&location), escape_xml(&issue.message)
+        )?;
+    }
+    writeln!(out, "</table>")
+}
+
+fn write_periods(out: &mut String, periods: &[PeriodChange]) -> fmt::Result {
+    writeln!(out, "<h2>Dependency changes per period</h2>")?;
+    let changed: Vec<&PeriodChange> = periods.iter().filter(|p| !p.diff.is_empty()).collect();
+    if changed.is_empty() {
+        return writeln!(out, "<p>The dependency graph did not change.</p>");
+    }
+    writeln!(
+        out,
+        "<table>\n<tr><th>Period</th><th>Commits</th><th>Dependencies</th><th>Files</th><th>Cycles</th><th>Largest component dependency changes</th></tr>"
+    )?;
+    for period in changed {
+        let diff = &period.diff;
+        let mut deltas: Vec<_> = diff.node_metric_deltas.iter().filter(|d| d.component_dependency.changed()).collect();
+        deltas.sort_by(|a, b| {
+            b.component_dependency.delta.abs().total_cmp(&a.component_dependency.delta.abs())
+                .then_with(|| a.file_path.cmp(&b.file_path))
+        });
+        let largest: Vec<String> = deltas
+            .iter()
+            .take(PERIOD_CHANGE_LIMIT)
+            .map(|d| {
+                let class = if d.component_dependency.delta > 0.0 { "worse" } else { "better" };
+                format!(
+                    "<code>{}</code> <span class=\"{}\">{:+}</span>",
+                    escape_xml(&d.file_path), class, d.component_dependency.delta
+                )
+            })
+            .collect();
+        writeln!(
+            out,
+            "<tr><td>{} – {}</td><td class=\"num\">{}</td><td>+{} −{}</td><td>+{} −{}</td><td><span class=\"worse\">+{}</span> <span class=\"better\">−{}</span></td><td>{}</td></tr>",
+            date(period.start), date(period.end - 1), period.commits,
+            diff.added_edges.len(), diff.removed_edges.len(),
+            diff.added_nodes.len(), diff.removed_nodes.len(),
+            diff.introduced_cycles.len(), diff.resolved_cycles.len(),
+            largest.join("<br>")
+        )?;
+    }
+    writeln!(out, "</table>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot_diff::diff_snapshots;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_period_changes_and_charts() {
+        let day = 86_400;
+        let snapshots = vec![
+            snapshot("c1", 0, vec![dep("lib/a.dart", "lib/b.dart")]),
+            snapshot("c2", day, vec![dep("lib/a.dart", "lib/b.dart"), dep("lib/b.dart", "lib/c.dart")]),
+            snapshot("c3", 2 * day, vec![dep("lib/b.dart", "lib/c.dart")]),
+            snapshot("c4", 9 * day, vec![dep("lib/b.dart", "lib/c.dart"), dep("lib/c.dart", "lib/b.dart")]),
+        ];
+
+        let periods = period_changes(&snapshots, 7, |from, to| Ok(diff_snapshots(from, to, &[]))).unwrap();
+        let summary: Vec<(i64, usize, usize, usize, usize)> = periods
+            .iter()
+            .map(|p| (p.start / day, p.commits, p.diff.added_edges.len(), p.diff.removed_edges.len(), p.diff.introduced_cycles.len()))
+            .collect();
+        // c1 -> c3 within the first week, c3 -> c4 in the second
+        assert_eq!(summary, vec![(0, 2, 1, 1, 0), (7, 1, 1, 0, 1)]);
+
+        let mut chart = String::new();
+        let hashes: Vec<String> = snapshots.iter().map(|s| s.commit_info.hash.clone()).collect();
+        write_chart(&mut chart, "A & B", &[0, day, 2 * day, 9 * day], &hashes, &[Some(1.0), None, Some(3.0), Some(2.0)]).unwrap();
+        assert!(chart.contains("A &amp; B"));
+        assert_eq!(chart.matches("<circle").count(), 3);
+    }
+}
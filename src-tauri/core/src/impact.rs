@@ -0,0 +1,118 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::DependencyGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// A file affected by a change, reached through reverse dependencies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactedFile {
+    pub path: String,
+    /// Number of import hops from the nearest changed file
+    pub distance: u32,
+    /// The file this one imports on its shortest path to a change
+    pub via: String,
+}
+
+/// Blast radius of changing a set of files or folders at one snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactReport {
+    pub commit_hash: String,
+    /// Graph nodes matched by the queried paths
+    pub changed: Vec<String>,
+    /// Files that directly or transitively import a changed file, nearest first
+    pub impacted: Vec<ImpactedFile>,
+    /// Queried paths that match no file in the snapshot
+    pub unknown: Vec<String>,
+}
+
+/// Files that transitively depend on any of `paths`. A path naming a folder
+/// stands for every file below it.
+pub fn impact(snapshot: &CommitSnapshot, paths: &[String]) -> ImpactReport {
+    let graph = DependencyGraph::from_analysis(&snapshot.analysis_result);
+    let mut distance: Vec<Option<u32>> = vec![None; graph.node_count()];
+    let mut via: Vec<Option<usize>> = vec![None; graph.node_count()];
+    let mut queue = VecDeque::new();
+    let mut unknown = Vec::new();
+
+    for path in paths {
+        let path = normalize_path(path.as_ref()).trim_end_matches('/').to_string();
+        let prefix = format!("{}/", path);
+        let matched: Vec<usize> = (0..graph.node_count())
+            .filter(|&n| graph.nodes[n] == path || graph.nodes[n].starts_with(&prefix))
+            .collect();
+        if matched.is_empty() {
+            unknown.push(path);
+        }
+        for node in matched {
+            if distance[node].is_none() {
+                distance[node] = Some(0);
+                queue.push_back(node);
+            }
+        }
+    }
+
+    let mut changed: Vec<String> = queue.iter().map(|&n| graph.nodes[n].clone()).collect();
+    changed.sort();
+
+    // Breadth-first over importers gives each file its shortest distance
+    let mut impacted = Vec::new();
+    while let Some(node) = queue.pop_front() {
+        let next = distance[node].unwrap_or(0) + 1;
+        for &importer in &graph.predecessors[node] {
+            if distance[importer].is_none() {
+                distance[importer] = Some(next);
+                via[importer] = Some(node);
+                queue.push_back(importer);
+                impacted.push(importer);
+            }
+        }
+    }
+
+    let mut impacted: Vec<ImpactedFile> = impacted
+        .into_iter()
+        .map(|n| ImpactedFile {
+            path: graph.nodes[n].clone(),
+            distance: distance[n].unwrap_or(0),
+            via: via[n].map(|v| graph.nodes[v].clone()).unwrap_or_default(),
+        })
+        .collect();
+    impacted.sort_by(|a, b| a.distance.cmp(&b.distance).then_with(|| a.path.cmp(&b.path)));
+
+    ImpactReport {
+        commit_hash: snapshot.commit_info.hash.clone(),
+        changed,
+        impacted,
+        unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_impact_follows_importers() {
+        let snapshot = snapshot("c1", 100, vec![
+            dep("lib/ui/page.dart", "lib/domain/user.dart"),
+            dep("lib/domain/user.dart", "lib/data/db.dart"),
+            dep("lib/app.dart", "lib/ui/page.dart"),
+            dep("lib/data/db.dart", "lib/util.dart"),
+        ]);
+
+        let report = impact(&snapshot, &["lib/data".to_string(), "lib/missing.dart".to_string()]);
+        assert_eq!(report.changed, vec!["lib/data/db.dart"]);
+        assert_eq!(report.unknown, vec!["lib/missing.dart"]);
+
+        let impacted: Vec<(&str, u32, &str)> = report.impacted
+            .iter()
+            .map(|f| (f.path.as_str(), f.distance, f.via.as_str()))
+            .collect();
+        assert_eq!(impacted, vec![
+            ("lib/domain/user.dart", 1, "lib/data/db.dart"),
+            ("lib/ui/page.dart", 2, "lib/domain/user.dart"),
+            ("lib/app.dart", 3, "lib/ui/page.dart"),
+        ]);
+    }
+}
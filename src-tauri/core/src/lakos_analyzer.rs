@@ -20,7 +20,7 @@ impl LakosAnalyzer {
     
     /// Check if Lakos is installed and available
     pub fn is_available() -> bool {
-        println!("🔍 DEBUG: Checking if Lakos is available");
+        log::debug!("Checking if Lakos is available");
         
         // Use Windows executable directly to bypass shebang issues
         let dart_commands = vec![
@@ -30,56 +30,56 @@ impl LakosAnalyzer {
         ];
         
         for dart_cmd in dart_commands {
-            println!("🔍 DEBUG: Trying dart command for availability check: {}", dart_cmd);
+            log::debug!("Trying dart command for availability check: {}", dart_cmd);
             if let Ok(output) = Command::new(dart_cmd)
                 .args(&["pub", "global", "list"])
                 .output()
             {
                 if output.status.success() {
                     let stdout = String::from_utf8_lossy(&output.stdout);
-                    println!("🔍 DEBUG: dart pub global list output: {}", stdout.trim());
+                    log::debug!("dart pub global list output: {}", stdout.trim());
                     if stdout.contains("lakos") {
-                        println!("✅ DEBUG: Found Lakos using command: {}", dart_cmd);
+                        log::debug!("Found Lakos using command: {}", dart_cmd);
                         return true;
                     }
                 } else {
-                    println!("🔍 DEBUG: Command '{}' failed with status: {}", dart_cmd, output.status);
+                    log::debug!("Command '{}' failed with status: {}", dart_cmd, output.status);
                 }
             } else {
-                println!("🔍 DEBUG: Failed to execute command: {}", dart_cmd);
+                log::debug!("Failed to execute command: {}", dart_cmd);
             }
         }
         
         // If direct commands fail, try with bash wrapper
-        println!("🔍 DEBUG: Trying bash wrapper for dart command");
+        log::debug!("Trying bash wrapper for dart command");
         if let Ok(output) = Command::new("bash")
             .args(&["-c", "dart pub global list 2>/dev/null || echo 'dart not found'"])
             .output()
         {
             if output.status.success() {
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                println!("🔍 DEBUG: bash wrapper output: {}", stdout.trim());
+                log::debug!("bash wrapper output: {}", stdout.trim());
                 let available = stdout.contains("lakos") && !stdout.contains("dart not found");
                 if available {
-                    println!("✅ DEBUG: Found Lakos using bash wrapper");
+                    log::debug!("Found Lakos using bash wrapper");
                 } else {
-                    println!("❌ DEBUG: Lakos not found in bash wrapper output");
+                    log::debug!("Lakos not found in bash wrapper output");
                 }
                 return available;
             } else {
-                println!("🔍 DEBUG: Bash wrapper failed with status: {}", output.status);
+                log::debug!("Bash wrapper failed with status: {}", output.status);
             }
         } else {
-            println!("🔍 DEBUG: Failed to execute bash wrapper");
+            log::debug!("Failed to execute bash wrapper");
         }
         
-        println!("❌ DEBUG: Lakos not available - not found in any dart command output");
+        log::debug!("Lakos not available - not found in any dart command output");
         false
     }
     
     /// Install Lakos globally
     pub fn install() -> Result<()> {
-        println!("Installing Lakos globally...");
+        log::info!("Installing Lakos globally...");
         
         // Use Windows executable directly to bypass shebang issues
         let dart_commands = vec![
@@ -97,7 +97,7 @@ impl LakosAnalyzer {
             {
                 Ok(output) => {
                     if output.status.success() {
-                        println!("Lakos installed successfully using {}", dart_cmd);
+                        log::info!("Lakos installed successfully using {}", dart_cmd);
                         return Ok(());
                     } else {
                         last_error = format!("Command '{}' failed: {}", 
@@ -117,7 +117,7 @@ impl LakosAnalyzer {
         {
             Ok(output) => {
                 if output.status.success() {
-                    println!("Lakos installed successfully using bash wrapper");
+                    log::info!("Lakos installed successfully using bash wrapper");
                     return Ok(());
                 } else {
                     last_error = format!("Bash wrapper failed: {}", 
@@ -134,22 +134,22 @@ impl LakosAnalyzer {
     
     /// Run lakos command and get JSON output
     fn run_lakos(&self, project_path: &Path, config: &AnalysisConfig) -> Result<String> {
-        println!("🔍 DEBUG: Starting Lakos analysis on: {}", project_path.display());
-        println!("🔍 DEBUG: Force recompile trigger");
+        log::debug!("Starting Lakos analysis on: {}", project_path.display());
+        log::debug!("Force recompile trigger");
         
         // Check if project path exists and has necessary files
         if !project_path.exists() {
             return Err(anyhow::anyhow!("Project path does not exist: {}", project_path.display()));
         }
         
-        println!("🔍 DEBUG: Project path exists, checking for pubspec.yaml");
+        log::debug!("Project path exists, checking for pubspec.yaml");
         let pubspec_path = project_path.join("pubspec.yaml");
         if !pubspec_path.exists() {
-            println!("🔍 DEBUG: No pubspec.yaml found at: {}", pubspec_path.display());
+            log::debug!("No pubspec.yaml found at: {}", pubspec_path.display());
             return Err(anyhow::anyhow!("No pubspec.yaml found - not a valid Dart project"));
         }
         
-        println!("🔍 DEBUG: Found pubspec.yaml, checking Lakos availability");
+        log::debug!("Found pubspec.yaml, checking Lakos availability");
         if !Self::is_available() {
             return Err(anyhow::anyhow!("Lakos is not installed or not available"));
         }
@@ -165,7 +165,7 @@ impl LakosAnalyzer {
         let mut last_error = String::new();
         
         for dart_cmd in dart_commands {
-            println!("🔍 DEBUG: Trying dart command: {}", dart_cmd);
+            log::debug!("Trying dart command: {}", dart_cmd);
             
             // Use project path directly for native Windows execution
             let project_path_str = project_path.to_string_lossy().to_string();
@@ -187,7 +187,7 @@ impl LakosAnalyzer {
             cmd.arg(".")
                .current_dir(&project_path);
             
-            println!("🔍 DEBUG: Running command: \"{}\" pub global run lakos --format=json --metrics --node-metrics . in directory: {}", dart_cmd, project_path.display());
+            log::debug!("Running command: \"{}\" pub global run lakos --format=json --metrics --node-metrics . in directory: {}", dart_cmd, project_path.display());
             
             match cmd.output() {
                 Ok(output) => {
@@ -195,15 +195,15 @@ impl LakosAnalyzer {
                     let stdout = String::from_utf8_lossy(&output.stdout);
                     let stderr = String::from_utf8_lossy(&output.stderr);
                     
-                    println!("🔍 DEBUG: Command '{}' completed with exit code: {}", dart_cmd, exit_code);
-                    println!("🔍 DEBUG: stdout length: {} chars", stdout.len());
-                    println!("🔍 DEBUG: stderr length: {} chars", stderr.len());
+                    log::debug!("Command '{}' completed with exit code: {}", dart_cmd, exit_code);
+                    log::debug!("stdout length: {} chars", stdout.len());
+                    log::debug!("stderr length: {} chars", stderr.len());
                     
                     if stdout.len() > 0 {
-                        println!("🔍 DEBUG: stdout content: {}", stdout);
+                        log::debug!("stdout content: {}", stdout);
                     }
                     if stderr.len() > 0 {
-                        println!("🔍 DEBUG: stderr content: {}", stderr);
+                        log::debug!("stderr content: {}", stderr);
                     }
                     
                     // Lakos returns different exit codes:
@@ -211,27 +211,27 @@ impl LakosAnalyzer {
                     // 5 = success, but cycles detected  
                     // Other codes = actual failures
                     if output.status.success() || exit_code == 5 {
-                        println!("✅ Lakos completed successfully with exit code: {} ({})", 
+                        log::debug!("Lakos completed successfully with exit code: {} ({})", 
                                  exit_code, 
                                  if exit_code == 5 { "cycles detected" } else { "success" });
                         
                         let stdout_string = String::from_utf8_lossy(&output.stdout).to_string();
                         if stdout_string.trim().is_empty() {
-                            println!("🔍 DEBUG: WARNING - Lakos output is empty");
+                            log::debug!("WARNING - Lakos output is empty");
                         } else {
-                            println!("🔍 DEBUG: Lakos output first 200 chars: {}", 
+                            log::debug!("Lakos output first 200 chars: {}", 
                                    stdout_string.chars().take(200).collect::<String>());
                         }
                         return Ok(stdout_string);
                     } else {
                         last_error = format!("Command '{}' failed with exit code {}: {}", 
                             dart_cmd, exit_code, stderr);
-                        println!("🔍 DEBUG: Command failed - {}", last_error);
+                        log::debug!("Command failed - {}", last_error);
                     }
                 }
                 Err(e) => {
                     last_error = format!("Failed to run '{}': {}", dart_cmd, e);
-                    println!("🔍 DEBUG: Failed to execute command - {}", last_error);
+                    log::debug!("Failed to execute command - {}", last_error);
                 }
             }
         }
@@ -246,7 +246,7 @@ impl LakosAnalyzer {
             }
         }
         
-        println!("🔍 DEBUG: Trying direct command fallback: {}", direct_cmd);
+        log::debug!("Trying direct command fallback: {}", direct_cmd);
         
         match Command::new("bash")
             .args(&["-c", &direct_cmd])
@@ -257,58 +257,58 @@ impl LakosAnalyzer {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 
-                println!("🔍 DEBUG: Bash fallback exit code: {}", exit_code);
-                println!("🔍 DEBUG: Bash fallback stdout: {} chars", stdout.len());
-                println!("🔍 DEBUG: Bash fallback stderr: {} chars", stderr.len());
+                log::debug!("Bash fallback exit code: {}", exit_code);
+                log::debug!("Bash fallback stdout: {} chars", stdout.len());
+                log::debug!("Bash fallback stderr: {} chars", stderr.len());
                 
                 if stdout.len() > 0 {
-                    println!("🔍 DEBUG: Bash fallback stdout: {}", stdout);
+                    log::debug!("Bash fallback stdout: {}", stdout);
                 }
                 if stderr.len() > 0 {
-                    println!("🔍 DEBUG: Bash fallback stderr: {}", stderr);
+                    log::debug!("Bash fallback stderr: {}", stderr);
                 }
                 
                 if output.status.success() || exit_code == 5 {
                     let stdout_string = String::from_utf8(output.stdout)?;
                     if stdout_string.trim().is_empty() {
-                        println!("🔍 DEBUG: WARNING - Bash fallback output is empty");
+                        log::debug!("WARNING - Bash fallback output is empty");
                     } else {
-                        println!("🔍 DEBUG: Bash fallback output first 200 chars: {}", 
+                        log::debug!("Bash fallback output first 200 chars: {}", 
                                stdout_string.chars().take(200).collect::<String>());
                     }
                     return Ok(stdout_string);
                 } else {
                     last_error = format!("Bash wrapper failed with exit code {}: {}", exit_code, stderr);
-                    println!("🔍 DEBUG: Bash fallback failed - {}", last_error);
+                    log::debug!("Bash fallback failed - {}", last_error);
                 }
             }
             Err(e) => {
                 last_error = format!("Bash wrapper execution failed: {}", e);
-                println!("🔍 DEBUG: Bash fallback execution failed - {}", last_error);
+                log::debug!("Bash fallback execution failed - {}", last_error);
             }
         }
         
-        println!("❌ DEBUG: All Lakos execution attempts failed");
+        log::debug!("All Lakos execution attempts failed");
         anyhow::bail!("Failed to run Lakos analysis. All attempts failed. Last error: {}. \nThis likely means:\n1. Lakos is not installed (run: dart pub global activate lakos)\n2. Dart SDK path issues in WSL environment\n3. Project is not a valid Dart/Flutter project", last_error);
     }
     
     /// Parse lakos JSON output into enhanced analysis data
     fn parse_lakos_json_enhanced(&self, json_str: &str, project_path: &Path) -> Result<(Vec<RawDependency>, Option<GlobalArchitecturalMetrics>, Option<HashMap<String, NodeMetrics>>)> {
-        println!("🔍 DEBUG: Starting JSON parse, input length: {} chars", json_str.len());
-        println!("🔍 DEBUG: JSON first 500 chars: {}", json_str.chars().take(500).collect::<String>());
+        log::debug!("Starting JSON parse, input length: {} chars", json_str.len());
+        log::debug!("JSON first 500 chars: {}", json_str.chars().take(500).collect::<String>());
         
         let json: Value = serde_json::from_str(json_str)
             .with_context(|| {
-                println!("🔍 DEBUG: JSON parsing failed!");
-                println!("🔍 DEBUG: JSON length: {}", json_str.len());
-                println!("🔍 DEBUG: First 200 chars: {}", json_str.chars().take(200).collect::<String>());
-                println!("🔍 DEBUG: Last 200 chars: {}", json_str.chars().rev().take(200).collect::<String>().chars().rev().collect::<String>());
+                log::debug!("JSON parsing failed!");
+                log::debug!("JSON length: {}", json_str.len());
+                log::debug!("First 200 chars: {}", json_str.chars().take(200).collect::<String>());
+                log::debug!("Last 200 chars: {}", json_str.chars().rev().take(200).collect::<String>().chars().rev().collect::<String>());
                 format!("Failed to parse Lakos JSON output. JSON length: {}, starts with: {}", 
                        json_str.len(), 
                        json_str.chars().take(100).collect::<String>())
             })?;
         
-        println!("🔍 DEBUG: JSON parsed successfully, parsing all metrics");
+        log::debug!("JSON parsed successfully, parsing all metrics");
         let mut dependencies = Vec::new();
         let mut global_metrics = GlobalArchitecturalMetrics::default();
         let mut node_metrics = HashMap::new();
@@ -318,42 +318,44 @@ impl LakosAnalyzer {
 
         // Parse node-level metrics from the "nodes" object
         if let Some(nodes) = json.get("nodes").and_then(|n| n.as_object()) {
-            println!("🔍 DEBUG: Found {} nodes in JSON", nodes.len());
+            log::debug!("Found {} nodes in JSON", nodes.len());
             node_metrics = self.parse_node_metrics(nodes, project_path)?;
         } else {
-            println!("🔍 DEBUG: No nodes object found in JSON");
+            log::debug!("No nodes object found in JSON");
         }
 
         // Parse edges (existing logic)
         if let Some(edges) = json.get("edges").and_then(|e| e.as_array()) {
-            println!("🔍 DEBUG: Found {} edges in JSON", edges.len());
+            log::debug!("Found {} edges in JSON", edges.len());
 
             for (i, edge) in edges.iter().enumerate() {
                 match self.parse_lakos_edge(edge, project_path) {
                     Ok(Some(dep)) => {
                         dependencies.push(dep);
                         if i < 5 {
-                            println!("🔍 DEBUG: Successfully processed edge {}", i);
+                            log::debug!("Successfully processed edge {}", i);
                         }
                     }
                     Ok(None) => {
                         if i < 5 {
-                            println!("🔍 DEBUG: Edge {} skipped (returned None)", i);
+                            log::debug!("Edge {} skipped (returned None)", i);
                         }
                     }
                     Err(e) => {
                         if i < 20 {
-                            println!("🔍 DEBUG: Failed to process edge {}: {} - Edge data: {:?}", i, e, edge);
+                            log::debug!("Failed to process edge {}: {} - Edge data: {:?}", i, e, edge);
                         }
                     }
                 }
             }
 
-            println!("Successfully processed {} dependencies from {} edges", dependencies.len(), edges.len());
+            log::debug!("Successfully processed {} dependencies from {} edges", dependencies.len(), edges.len());
         } else {
-            println!("🔍 DEBUG: No edges array found in JSON");
+            log::debug!("No edges array found in JSON");
         }
 
+        Self::locate_imports(&mut dependencies, project_path);
+
         Ok((
             dependencies,
             Some(global_metrics),
@@ -361,6 +363,25 @@ impl LakosAnalyzer {
         ))
     }
     
+    /// Fill in the line and statement of each dependency's import from the
+    /// sources, since Lakos only reports which libraries depend on which
+    fn locate_imports(dependencies: &mut [RawDependency], project_path: &Path) {
+        let relative = |path: &Path| Self::make_path_relative(path, project_path).to_string_lossy().replace('\\', "/");
+        let mut sources: HashMap<PathBuf, Option<String>> = HashMap::new();
+        for dep in dependencies.iter_mut() {
+            let source = sources
+                .entry(dep.source_file.clone())
+                .or_insert_with(|| std::fs::read_to_string(project_path.join(&dep.source_file)).ok());
+            let Some(source) = source else { continue };
+            if let Some((line, statement)) =
+                utils::find_dart_import(source, &relative(&dep.source_file), &relative(&dep.target_file))
+            {
+                dep.line_number = Some(line);
+                dep.import_statement = Some(statement);
+            }
+        }
+    }
+
     /// Parse a single edge from Lakos JSON
     fn parse_lakos_edge(&self, edge: &Value, project_path: &Path) -> Result<Option<RawDependency>> {
         // Parse edge from Lakos JSON
@@ -395,8 +416,8 @@ impl LakosAnalyzer {
             target_file,
             relationship_type,
             weight: DependencyWeight::Binary(true),
-            line_number: None, // Lakos doesn't provide line numbers
-            import_statement: None, // Lakos doesn't provide import statements
+            line_number: None, // Located in the sources by locate_imports
+            import_statement: None,
             symbols: Vec::new(), // Lakos doesn't track individual symbols
             metadata,
         }))
@@ -472,7 +493,7 @@ impl LakosAnalyzer {
             }
         }
 
-        println!("🔍 DEBUG: Parsed global metrics - nodes: {}, edges: {}, acyclic: {}",
+        log::debug!("Parsed global metrics - nodes: {}, edges: {}, acyclic: {}",
                 metrics.num_nodes, metrics.num_edges, metrics.is_acyclic);
 
         Ok(metrics)
@@ -531,7 +552,7 @@ impl LakosAnalyzer {
             node_metrics.insert(file_path, metrics);
         }
 
-        println!("🔍 DEBUG: Parsed {} node metrics", node_metrics.len());
+        log::debug!("Parsed {} node metrics", node_metrics.len());
         Ok(node_metrics)
     }
 
@@ -575,7 +596,7 @@ impl LakosAnalyzer {
         }
         
         // If file doesn't exist, still return the relative path but log warning
-        eprintln!("Warning: File not found for library '{}', using relative path: {}", 
+        log::warn!("File not found for library '{}', using relative path: {}", 
                 library_name, relative_path.display());
         Ok(relative_path)
     }
@@ -600,8 +621,9 @@ impl DependencyAnalyzer for LakosAnalyzer {
         AnalyzerCapabilities {
             supports_weighted_analysis: false,
             supports_symbol_tracking: false,
-            supports_line_numbers: false,
+            supports_line_numbers: true,
             supports_dynamic_imports: false,
+            supports_declarations: true,
             supported_file_extensions: vec!["dart".to_string()],
             performance_tier: PerformanceTier::Fast,
         }
@@ -622,6 +644,9 @@ impl DependencyAnalyzer for LakosAnalyzer {
                 message: "No pubspec.yaml found - may not be a Dart/Flutter project".to_string(),
                 file_path: None,
                 line_number: None,
+                import_statement: None,
+                target_path: None,
+                rule: None,
             });
         }
         
@@ -638,21 +663,31 @@ impl DependencyAnalyzer for LakosAnalyzer {
             
         // Run lakos analysis
         let json_output = self.run_lakos(project_path, config)?;
-        println!("🔍 DEBUG: run_lakos returned successfully, JSON length: {} chars", json_output.len());
-        println!("🔍 DEBUG: About to call enhanced JSON parsing");
+        log::debug!("run_lakos returned successfully, JSON length: {} chars", json_output.len());
+        log::debug!("About to call enhanced JSON parsing");
 
         // Parse enhanced dependencies and metrics
-        let (dependencies, global_metrics, node_metrics) = self.parse_lakos_json_enhanced(&json_output, project_path)
+        let (dependencies, global_metrics, mut node_metrics) = self.parse_lakos_json_enhanced(&json_output, project_path)
             .context("Failed to parse Lakos output")?;
-        println!("🔍 DEBUG: Enhanced parsing completed - found {} dependencies", dependencies.len());
+
+        // Lakos does not report declarations, so count them from the sources
+        if let Some(ref mut nodes) = node_metrics {
+            for (file_path, metrics) in nodes.iter_mut() {
+                if let Ok(source) = std::fs::read_to_string(project_path.join(file_path)) {
+                    let (abstract_types, total_types) = utils::count_dart_type_declarations(&source);
+                    metrics.set_type_declarations(abstract_types, total_types);
+                }
+            }
+        }
+        log::debug!("Enhanced parsing completed - found {} dependencies", dependencies.len());
 
         if let Some(ref global) = global_metrics {
-            println!("🔍 DEBUG: Global metrics - nodes: {}, edges: {}, SLOC: {}",
+            log::debug!("Global metrics - nodes: {}, edges: {}, SLOC: {}",
                     global.num_nodes, global.num_edges, global.total_sloc);
         }
 
         if let Some(ref nodes) = node_metrics {
-            println!("🔍 DEBUG: Node metrics for {} files", nodes.len());
+            log::debug!("Node metrics for {} files", nodes.len());
         }
         
         let analysis_duration = start_time.elapsed();
@@ -674,9 +709,9 @@ impl DependencyAnalyzer for LakosAnalyzer {
             .collect();
 
         // Make all paths relative to the project root for cleaner UI display
-        println!("🔍 DEBUG: Project path for relative conversion: {}", project_path.display());
+        log::debug!("Project path for relative conversion: {}", project_path.display());
         if !dependencies.is_empty() {
-            println!("🔍 DEBUG: Sample dependency before relative: {} -> {}", 
+            log::debug!("Sample dependency before relative: {} -> {}", 
                      dependencies[0].source_file.display(), 
                      dependencies[0].target_file.display());
         }
@@ -686,7 +721,7 @@ impl DependencyAnalyzer for LakosAnalyzer {
         let relative_dart_files = Self::make_paths_relative(&dart_files, project_path);
         
         if !relative_dependencies.is_empty() {
-            println!("🔍 DEBUG: Sample dependency after relative: {} -> {}", 
+            log::debug!("Sample dependency after relative: {} -> {}", 
                      relative_dependencies[0].source_file.display(), 
                      relative_dependencies[0].target_file.display());
         }
@@ -698,6 +733,7 @@ impl DependencyAnalyzer for LakosAnalyzer {
             global_metrics: global_metrics.clone(),
             node_metrics: node_metrics.clone(),
             architecture_quality_score: None,
+            quality_breakdown: None,
             analyzer_name: self.name().to_string(),
             analyzer_version: self.version().to_string(),
             analysis_timestamp: chrono::Utc::now().timestamp(),
@@ -711,7 +747,7 @@ impl DependencyAnalyzer for LakosAnalyzer {
         // Calculate quality score based on metrics
         result.calculate_quality_score();
 
-        println!("🔍 DEBUG: Enhanced analysis result created with quality score: {:?}",
+        log::debug!("Enhanced analysis result created with quality score: {:?}",
                 result.architecture_quality_score);
 
         Ok(result)
@@ -846,6 +882,45 @@ mod tests {
         assert_eq!(result, test_file);
     }
     
+    #[test]
+    fn test_dependencies_carry_import_lines() {
+        let temp_dir = tempdir().unwrap();
+        let project_path = temp_dir.path();
+        fs::create_dir_all(project_path.join("lib/ui")).unwrap();
+        fs::create_dir_all(project_path.join("lib/data")).unwrap();
+        fs::write(project_path.join("lib/ui/page.dart"), "import 'dart:async';\n\nimport '../data/db.dart';\nimport 'package:app/data/api.dart' show Api;\n").unwrap();
+        fs::write(project_path.join("lib/data/db.dart"), "class Db {}\n").unwrap();
+        fs::write(project_path.join("lib/data/api.dart"), "class Api {}\n").unwrap();
+        let json = serde_json::json!({
+            "nodes": { "/lib/ui/page.dart": {}, "/lib/data/db.dart": {}, "/lib/data/api.dart": {} },
+            "edges": [
+                { "from": "/lib/ui/page.dart", "to": "/lib/data/db.dart" },
+                { "from": "/lib/ui/page.dart", "to": "/lib/data/api.dart" }
+            ]
+        });
+
+        let (dependencies, _, _) = LakosAnalyzer::new()
+            .parse_lakos_json_enhanced(&json.to_string(), project_path)
+            .unwrap();
+        let located: Vec<(Option<u32>, Option<&str>)> = dependencies
+            .iter()
+            .map(|d| (d.line_number, d.import_statement.as_deref()))
+            .collect();
+        assert_eq!(located, vec![
+            (Some(3), Some("import '../data/db.dart';")),
+            (Some(4), Some("import 'package:app/data/api.dart' show Api;")),
+        ]);
+
+        let rules: crate::architecture_rules::RuleSet = serde_json::from_value(serde_json::json!({
+            "rules": [{ "type": "forbidden", "from": "lib/ui/**", "to": "lib/data/db.dart" }]
+        }))
+        .unwrap();
+        let issues = rules.evaluate(&dependencies);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line_number, Some(3));
+        assert_eq!(issues[0].import_statement.as_deref(), Some("import '../data/db.dart';"));
+    }
+
     #[test]
     fn test_dart_project_detection() {
         let temp_dir = tempdir().unwrap();
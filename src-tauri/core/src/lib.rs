@@ -0,0 +1,39 @@
+//! ChronoGraph analysis engine: git history navigation, dependency analyzers, the
+//! analysis cache, graph algorithms and everything derived from analyzed snapshots.
+//! Has no UI dependencies; the desktop app and the CLI are thin layers on top.
+
+pub mod models;
+pub mod git_navigator;
+pub mod dependency_analyzer;
+pub mod lakos_analyzer;
+pub mod chronograph_engine;
+pub mod analysis_cache;
+pub mod dependency_view;
+pub mod dependency_timeline;
+pub mod file_history;
+pub mod node_identity;
+pub mod change_coupling;
+pub mod hotspots;
+pub mod impact;
+pub mod cycle_tracker;
+pub mod bisect;
+pub mod metric_series;
+pub mod regressions;
+pub mod quality_score;
+pub mod package_metrics;
+pub mod modularity;
+pub mod ownership;
+pub mod architecture_rules;
+pub mod baseline;
+pub mod analysis_export;
+pub mod graph_export;
+pub mod table_export;
+pub mod diagram_export;
+pub mod html_report;
+pub mod sarif_export;
+pub mod api;
+pub mod graph_algorithms;
+pub mod snapshot_diff;
+
+#[cfg(test)]
+mod test_fixtures;
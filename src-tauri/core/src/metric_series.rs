@@ -0,0 +1,272 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::{global_metrics_or_derived, node_metrics_or_derived, DependencyGraph};
+use crate::node_identity::NodeIdentityMap;
+use crate::package_metrics::FolderPackages;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Columnar time series for charting: every vector in `series` is aligned with
+/// `timestamps`, with `None` where the subject did not exist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricTimeSeries {
+    /// Node path, folder path or "global"
+    pub subject: String,
+    pub timestamps: Vec<i64>,
+    pub commit_hashes: Vec<String>,
+    /// Path of the subject at each commit (differs after renames)
+    pub paths: Vec<Option<String>>,
+    pub series: BTreeMap<String, Vec<Option<f64>>>,
+}
+
+impl MetricTimeSeries {
+    fn new(subject: &str) -> Self {
+        Self {
+            subject: subject.to_string(),
+            timestamps: Vec::new(),
+            commit_hashes: Vec::new(),
+            paths: Vec::new(),
+            series: BTreeMap::new(),
+        }
+    }
+
+    /// Append one snapshot; metrics missing from `values` are recorded as `None`
+    fn push(&mut self, snapshot: &CommitSnapshot, path: Option<String>, values: Option<Vec<(&str, f64)>>) {
+        let index = self.timestamps.len();
+        self.timestamps.push(snapshot.commit_info.timestamp);
+        self.commit_hashes.push(snapshot.commit_info.hash.clone());
+        self.paths.push(path);
+
+        for (name, value) in values.unwrap_or_default() {
+            self.series
+                .entry(name.to_string())
+                .or_insert_with(|| vec![None; index])
+                .push(Some(value));
+        }
+        for values in self.series.values_mut() {
+            values.resize(index + 1, None);
+        }
+    }
+}
+
+/// Graph-derived per-node values of one snapshot
+struct SnapshotGraphMetrics {
+    levels: HashMap<String, u32>,
+    pagerank: HashMap<String, f64>,
+}
+
+impl SnapshotGraphMetrics {
+    fn new(snapshot: &CommitSnapshot) -> Self {
+        let graph = DependencyGraph::from_analysis(&snapshot.analysis_result);
+        let levels = graph.levels();
+        let pagerank = graph.pagerank();
+        Self {
+            levels: graph.nodes.iter().cloned().zip(levels).collect(),
+            pagerank: graph.nodes.iter().cloned().zip(pagerank).collect(),
+        }
+    }
+}
+
+/// Metrics of one file across all snapshots, following it through renames.
+/// Falls back to matching by path when the file has no tracked identity.
+pub fn node_series(
+    snapshots: &[CommitSnapshot],
+    identities: &NodeIdentityMap,
+    node_path: &str,
+) -> MetricTimeSeries {
+    let node_path = normalize_path(Path::new(node_path));
+    let locations: HashMap<&str, &str> = identities
+        .latest_id(&node_path)
+        .map(|id| {
+            identities
+                .locations(id)
+                .iter()
+                .map(|l| (l.commit_hash.as_str(), l.path.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut series = MetricTimeSeries::new(&node_path);
+    for snapshot in snapshots {
+        let path = if locations.is_empty() {
+            Some(node_path.as_str())
+        } else {
+            locations.get(snapshot.commit_info.hash.as_str()).copied()
+        };
+
+        let metrics = node_metrics_or_derived(&snapshot.analysis_result);
+        let Some((path, node)) = path.and_then(|p| Some((p, metrics.get(p)?))) else {
+            series.push(snapshot, None, None);
+            continue;
+        };
+
+        let graph_metrics = SnapshotGraphMetrics::new(snapshot);
+        let mut values = vec![
+            ("in_degree", node.in_degree as f64),
+            ("out_degree", node.out_degree as f64),
+            ("component_dependency", node.component_dependency as f64),
+            ("instability", node.instability),
+            ("sloc", node.sloc as f64),
+            ("in_cycle", if node.in_cycle { 1.0 } else { 0.0 }),
+            ("level", graph_metrics.levels.get(path).copied().unwrap_or(1) as f64),
+            ("pagerank", graph_metrics.pagerank.get(path).copied().unwrap_or(0.0)),
+        ];
+        if let (Some(abstractness), Some(distance)) = (node.abstractness, node.distance) {
+            values.push(("abstractness", abstractness));
+            values.push(("distance", distance));
+        }
+        series.push(snapshot, Some(path.to_string()), Some(values));
+    }
+    series
+}
+
+/// Aggregated metrics of all files below a folder across snapshots. Degrees count
+/// edges crossing the folder boundary; level is the highest member level and
+/// centrality the summed PageRank of the members.
+pub fn folder_series(snapshots: &[CommitSnapshot], folder: &str) -> MetricTimeSeries {
+    let folder = normalize_path(Path::new(folder));
+    let prefix = format!("{}/", folder);
+    let inside = |path: &str| folder.is_empty() || path.starts_with(&prefix);
+
+    let mut series = MetricTimeSeries::new(&folder);
+    for snapshot in snapshots {
+        let metrics = node_metrics_or_derived(&snapshot.analysis_result);
+        let members: Vec<&String> = metrics.keys().filter(|p| inside(p)).collect();
+        if members.is_empty() {
+            series.push(snapshot, None, None);
+            continue;
+        }
+
+        let graph = DependencyGraph::from_analysis(&snapshot.analysis_result);
+        let graph_metrics = SnapshotGraphMetrics::new(snapshot);
+        let mut in_degree = 0;
+        let mut out_degree = 0;
+        for (source, targets) in graph.successors.iter().enumerate() {
+            for &target in targets {
+                match (inside(&graph.nodes[source]), inside(&graph.nodes[target])) {
+                    (true, false) => out_degree += 1,
+                    (false, true) => in_degree += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        let member_metrics: Vec<_> = members.iter().map(|m| &metrics[*m]).collect();
+        let count = member_metrics.len() as f64;
+        let mut values = vec![
+            ("file_count", count),
+            ("in_degree", in_degree as f64),
+            ("out_degree", out_degree as f64),
+            (
+                "component_dependency",
+                member_metrics.iter().map(|m| m.component_dependency as f64).sum::<f64>() / count,
+            ),
+            (
+                "instability",
+                if in_degree + out_degree > 0 {
+                    out_degree as f64 / (in_degree + out_degree) as f64
+                } else {
+                    0.0
+                },
+            ),
+            ("sloc", member_metrics.iter().map(|m| m.sloc as f64).sum()),
+            ("in_cycle", if member_metrics.iter().any(|m| m.in_cycle) { 1.0 } else { 0.0 }),
+            (
+                "level",
+                members
+                    .iter()
+                    .filter_map(|m| graph_metrics.levels.get(*m))
+                    .copied()
+                    .max()
+                    .unwrap_or(1) as f64,
+            ),
+            (
+                "pagerank",
+                members.iter().filter_map(|m| graph_metrics.pagerank.get(*m)).sum(),
+            ),
+        ];
+        if let Some(package) = FolderPackages::new(&metrics, &graph).metrics(&folder) {
+            values.push(("abstractness", package.abstractness));
+            values.push(("distance", package.distance));
+        }
+        series.push(snapshot, Some(folder.clone()), Some(values));
+    }
+    series
+}
+
+/// `GlobalArchitecturalMetrics` across snapshots, derived from the graph when the
+/// analyzer did not report them
+pub fn global_series(snapshots: &[CommitSnapshot]) -> MetricTimeSeries {
+    let mut series = MetricTimeSeries::new("global");
+    for snapshot in snapshots {
+        let global = global_metrics_or_derived(&snapshot.analysis_result);
+        let mut values = vec![
+            ("is_acyclic", if global.is_acyclic { 1.0 } else { 0.0 }),
+            ("num_nodes", global.num_nodes as f64),
+            ("num_edges", global.num_edges as f64),
+            ("avg_degree", global.avg_degree),
+            ("cumulative_component_dependency", global.cumulative_component_dependency as f64),
+            ("average_component_dependency", global.average_component_dependency),
+            ("normalized_ccd", global.normalized_ccd),
+            ("total_sloc", global.total_sloc as f64),
+            ("average_sloc", global.average_sloc),
+            ("cycle_count", global.detected_cycles.len() as f64),
+            ("orphan_count", global.orphan_libraries.len() as f64),
+        ];
+        if let Some(score) = snapshot.analysis_result.architecture_quality_score {
+            values.push(("architecture_quality_score", score));
+        }
+        series.push(snapshot, None, Some(values));
+    }
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FileChangeSet;
+    use crate::test_fixtures::{dep, snapshot};
+    use std::path::PathBuf;
+
+    fn snapshots() -> Vec<CommitSnapshot> {
+        vec![
+            snapshot("c1", 100, vec![dep("lib/a.dart", "lib/util/b.dart")]),
+            snapshot("c2", 200, vec![dep("lib/core/a.dart", "lib/util/b.dart"), dep("lib/util/b.dart", "lib/util/c.dart")]),
+            snapshot("c3", 300, vec![dep("lib/util/b.dart", "lib/util/c.dart")]),
+        ]
+    }
+
+    #[test]
+    fn test_node_series_follows_renames() {
+        let snapshots = snapshots();
+        let changes = vec![
+            FileChangeSet {
+                renamed_files: vec![(PathBuf::from("lib/a.dart"), PathBuf::from("lib/core/a.dart"))],
+                ..FileChangeSet::default()
+            },
+            FileChangeSet::default(),
+        ];
+        let identities = NodeIdentityMap::build(&snapshots, &changes);
+
+        let series = node_series(&snapshots, &identities, "lib/core/a.dart");
+        assert_eq!(series.timestamps, vec![100, 200, 300]);
+        assert_eq!(series.paths, vec![Some("lib/a.dart".to_string()), Some("lib/core/a.dart".to_string()), None]);
+        assert_eq!(series.series["out_degree"], vec![Some(1.0), Some(1.0), None]);
+        assert_eq!(series.series["level"], vec![Some(2.0), Some(3.0), None]);
+    }
+
+    #[test]
+    fn test_folder_and_global_series() {
+        let snapshots = snapshots();
+
+        let util = folder_series(&snapshots, "lib/util");
+        assert_eq!(util.series["file_count"], vec![Some(1.0), Some(2.0), Some(2.0)]);
+        assert_eq!(util.series["in_degree"], vec![Some(1.0), Some(1.0), Some(0.0)]);
+        assert_eq!(util.series["out_degree"], vec![Some(0.0), Some(0.0), Some(0.0)]);
+
+        let global = global_series(&snapshots);
+        assert_eq!(global.series["num_edges"], vec![Some(1.0), Some(2.0), Some(1.0)]);
+        assert_eq!(global.series["cycle_count"], vec![Some(0.0), Some(0.0), Some(0.0)]);
+    }
+}
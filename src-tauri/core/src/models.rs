@@ -32,7 +32,7 @@ pub struct CommitSnapshot {
     pub metrics: CommitMetrics,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FileChangeSet {
     pub added_files: HashSet<PathBuf>,
     pub modified_files: HashSet<PathBuf>,
@@ -58,6 +58,22 @@ pub struct TemporalFileDependency {
     pub authors: HashSet<String>,
     pub stability_score: f64,
     pub strength_over_time: Vec<(String, f64)>, // Simplified timestamp
+    pub introduced_by: String,
+    pub removed_in: Option<String>, // CommitHash, None while still present
+    pub reappeared: bool,
+    pub intervals: Vec<DependencyInterval>,
+}
+
+/// One continuous stretch of snapshots in which a dependency was present
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyInterval {
+    pub introduced_in: String, // CommitHash
+    pub introduced_by: String,
+    pub introduced_at: i64,
+    pub removed_in: Option<String>,
+    pub removed_by: Option<String>,
+    pub removed_at: Option<i64>,
+    pub last_seen: String, // CommitHash
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,12 +82,18 @@ pub struct TemporalMetadata {
     pub modification_commits: Vec<String>,
     pub deletion_commit: Option<String>,
     pub primary_authors: Vec<String>,
-    pub change_frequency: f64,
+    pub change_frequency: f64, // Commits per 30 days of the file's lifetime
+    pub created_at: i64,
+    pub last_modified_at: i64,
+    pub lines_added: usize,
+    pub lines_removed: usize,
 }
 
 /// View layer - computed on-demand structures
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DependencyView {
+    /// Commit the visible dependencies were built from
+    pub commit_hash: Option<String>,
     pub folder_depth: usize,
     pub expanded_folders: HashSet<PathBuf>,
     pub visible_dependencies: Vec<ViewDependency>,
@@ -0,0 +1,206 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::graph_algorithms::DependencyGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Centrality and community of one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeCentrality {
+    pub path: String,
+    pub folder: String,
+    pub pagerank: f64,
+    pub betweenness: f64,
+    pub community: usize,
+}
+
+/// A group of files that depend more on each other than on the rest of the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Community {
+    pub id: usize,
+    pub members: Vec<String>,
+    /// Folder holding most of the members
+    pub dominant_folder: String,
+    /// Fraction of the members inside `dominant_folder`
+    pub dominant_share: f64,
+}
+
+/// A file whose dependencies place it in a community rooted in another folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MisplacedFile {
+    pub path: String,
+    pub folder: String,
+    pub community: usize,
+    pub community_folder: String,
+}
+
+/// How well the folder structure of one snapshot matches its dependency structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModularityReport {
+    pub commit_hash: String,
+    pub timestamp: i64,
+    /// Newman modularity of the detected communities
+    pub community_modularity: f64,
+    /// Newman modularity of the folder partition
+    pub folder_modularity: f64,
+    /// 1 - normalized mutual information between folders and communities:
+    /// 0 when they coincide, approaching 1 when unrelated
+    pub mismatch: f64,
+    pub nodes: Vec<NodeCentrality>,
+    pub communities: Vec<Community>,
+    pub misplaced: Vec<MisplacedFile>,
+}
+
+fn parent_folder(path: &str) -> String {
+    path.rsplit_once('/').map(|(folder, _)| folder.to_string()).unwrap_or_default()
+}
+
+/// Normalized mutual information of two partitions over the same nodes
+fn normalized_mutual_information(a: &[usize], b: &[usize]) -> f64 {
+    let n = a.len() as f64;
+    if a.is_empty() {
+        return 1.0;
+    }
+
+    let mut joint: HashMap<(usize, usize), f64> = HashMap::new();
+    let mut count_a: HashMap<usize, f64> = HashMap::new();
+    let mut count_b: HashMap<usize, f64> = HashMap::new();
+    for (&x, &y) in a.iter().zip(b) {
+        *joint.entry((x, y)).or_insert(0.0) += 1.0;
+        *count_a.entry(x).or_insert(0.0) += 1.0;
+        *count_b.entry(y).or_insert(0.0) += 1.0;
+    }
+
+    let entropy = |counts: &HashMap<usize, f64>| -> f64 {
+        counts.values().map(|c| -(c / n) * (c / n).ln()).sum()
+    };
+    let (h_a, h_b) = (entropy(&count_a), entropy(&count_b));
+    if h_a + h_b == 0.0 {
+        return 1.0;
+    }
+
+    let mutual: f64 = joint
+        .iter()
+        .map(|(&(x, y), &c)| (c / n) * (c * n / (count_a[&x] * count_b[&y])).ln())
+        .sum();
+    2.0 * mutual / (h_a + h_b)
+}
+
+/// Centrality, communities and folder mismatch of one snapshot
+pub fn analyze_modularity(snapshot: &CommitSnapshot) -> ModularityReport {
+    let graph = DependencyGraph::from_analysis(&snapshot.analysis_result);
+    let pagerank = graph.pagerank();
+    let betweenness = graph.betweenness();
+    let communities = graph.louvain();
+
+    let folders: Vec<String> = graph.nodes.iter().map(|p| parent_folder(p)).collect();
+    let mut folder_ids: HashMap<&str, usize> = HashMap::new();
+    let folder_partition: Vec<usize> = folders
+        .iter()
+        .map(|f| {
+            let next = folder_ids.len();
+            *folder_ids.entry(f.as_str()).or_insert(next)
+        })
+        .collect();
+
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (node, &community) in communities.iter().enumerate() {
+        members.entry(community).or_default().push(node);
+    }
+
+    let mut community_list = Vec::new();
+    let mut misplaced = Vec::new();
+    for (&id, nodes) in &members {
+        let mut folder_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for &node in nodes {
+            *folder_counts.entry(folders[node].as_str()).or_insert(0) += 1;
+        }
+        // Most members wins; BTreeMap order breaks ties by folder name
+        let (dominant_folder, dominant_count) = folder_counts
+            .iter()
+            .fold(("", 0), |best, (&folder, &count)| if count > best.1 { (folder, count) } else { best });
+
+        if nodes.len() > 1 {
+            for &node in nodes {
+                if folders[node] != dominant_folder {
+                    misplaced.push(MisplacedFile {
+                        path: graph.nodes[node].clone(),
+                        folder: folders[node].clone(),
+                        community: id,
+                        community_folder: dominant_folder.to_string(),
+                    });
+                }
+            }
+        }
+
+        let mut paths: Vec<String> = nodes.iter().map(|&n| graph.nodes[n].clone()).collect();
+        paths.sort();
+        community_list.push(Community {
+            id,
+            dominant_share: dominant_count as f64 / nodes.len() as f64,
+            dominant_folder: dominant_folder.to_string(),
+            members: paths,
+        });
+    }
+    misplaced.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut nodes: Vec<NodeCentrality> = (0..graph.node_count())
+        .map(|node| NodeCentrality {
+            path: graph.nodes[node].clone(),
+            folder: folders[node].clone(),
+            pagerank: pagerank[node],
+            betweenness: betweenness[node],
+            community: communities[node],
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    ModularityReport {
+        commit_hash: snapshot.commit_info.hash.clone(),
+        timestamp: snapshot.commit_info.timestamp,
+        community_modularity: graph.modularity(&communities),
+        folder_modularity: graph.modularity(&folder_partition),
+        mismatch: 1.0 - normalized_mutual_information(&folder_partition, &communities),
+        nodes,
+        communities: community_list,
+        misplaced,
+    }
+}
+
+/// Modularity reports for every snapshot, oldest first
+pub fn modularity_timeline(snapshots: &[CommitSnapshot]) -> Vec<ModularityReport> {
+    snapshots.iter().map(analyze_modularity).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_file_in_wrong_folder_is_reported() {
+        let snapshot = snapshot("c1", 100, vec![
+            dep("lib/auth/login.dart", "lib/auth/session.dart"),
+            dep("lib/auth/session.dart", "lib/auth/token.dart"),
+            dep("lib/auth/token.dart", "lib/auth/login.dart"),
+            dep("lib/utils/jwt.dart", "lib/auth/token.dart"),
+            dep("lib/auth/session.dart", "lib/utils/jwt.dart"),
+            dep("lib/ui/home.dart", "lib/ui/button.dart"),
+            dep("lib/ui/button.dart", "lib/ui/theme.dart"),
+            dep("lib/ui/theme.dart", "lib/ui/home.dart"),
+            dep("lib/ui/home.dart", "lib/auth/login.dart"),
+        ]);
+
+        let report = analyze_modularity(&snapshot);
+        assert_eq!(report.misplaced.len(), 1);
+        assert_eq!(report.misplaced[0].path, "lib/utils/jwt.dart");
+        assert_eq!(report.misplaced[0].community_folder, "lib/auth");
+        assert!(report.mismatch > 0.0 && report.mismatch < 1.0);
+        assert!(report.community_modularity >= report.folder_modularity);
+    }
+
+    #[test]
+    fn test_identical_partitions_have_no_mismatch() {
+        assert!((normalized_mutual_information(&[0, 0, 1, 1], &[5, 5, 3, 3]) - 1.0).abs() < 1e-9);
+        assert!(normalized_mutual_information(&[0, 0, 1, 1], &[0, 1, 0, 1]).abs() < 1e-9);
+    }
+}
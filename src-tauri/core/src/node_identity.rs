@@ -0,0 +1,286 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::NodeMetrics;
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::node_metrics_or_derived;
+use crate::models::FileChangeSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// The path a tracked file had in one analyzed snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeLocation {
+    pub commit_hash: String,
+    pub timestamp: i64,
+    pub path: String,
+}
+
+/// Metrics of a tracked file in one analyzed snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeMetricPoint {
+    pub commit_hash: String,
+    pub timestamp: i64,
+    pub path: String,
+    pub metrics: NodeMetrics,
+}
+
+/// Stable node ids that follow files through renames and moves.
+///
+/// A file's id is the path it had in the first snapshot it appeared in; when a
+/// path is reused by an unrelated file later on, the new file gets a `#n` suffix.
+#[derive(Debug, Clone, Default)]
+pub struct NodeIdentityMap {
+    /// Per snapshot (in order): project-relative path -> stable id
+    snapshot_ids: Vec<(String, HashMap<String, String>)>,
+    /// Stable id -> locations in chronological order
+    locations: BTreeMap<String, Vec<NodeLocation>>,
+}
+
+impl NodeIdentityMap {
+    /// Track files across chronologically ordered snapshots. `changes[i]` holds the
+    /// project-relative changes between `snapshots[i]` and `snapshots[i + 1]`.
+    pub fn build(snapshots: &[CommitSnapshot], changes: &[FileChangeSet]) -> Self {
+        let mut map = Self::default();
+        let mut current: HashMap<String, String> = HashMap::new();
+        let mut used_ids: HashSet<String> = HashSet::new();
+
+        for (position, snapshot) in snapshots.iter().enumerate() {
+            if position > 0 {
+                if let Some(change_set) = changes.get(position - 1) {
+                    // Vacate every old path before taking the new ones so swaps
+                    // and chains of renames keep their ids
+                    let moved: Vec<(String, String)> = change_set
+                        .renamed_files
+                        .iter()
+                        .filter_map(|(old_path, new_path)| {
+                            let id = current.remove(&normalize_path(old_path))?;
+                            Some((normalize_path(new_path), id))
+                        })
+                        .collect();
+                    current.extend(moved);
+                }
+            }
+
+            let files = snapshot_files(snapshot);
+            current.retain(|path, _| files.binary_search(path).is_ok());
+
+            let mut ids = HashMap::new();
+            for file in files {
+                let id = current
+                    .entry(file.clone())
+                    .or_insert_with(|| unique_id(&file, &mut used_ids))
+                    .clone();
+
+                map.locations.entry(id.clone()).or_default().push(NodeLocation {
+                    commit_hash: snapshot.commit_info.hash.clone(),
+                    timestamp: snapshot.commit_info.timestamp,
+                    path: file.clone(),
+                });
+                ids.insert(file, id);
+            }
+
+            map.snapshot_ids.push((snapshot.commit_info.hash.clone(), ids));
+        }
+
+        map
+    }
+
+    /// Stable id of a file path as seen in the given snapshot
+    pub fn stable_id(&self, commit_hash: &str, path: &str) -> Option<&str> {
+        let path = normalize_path(Path::new(path));
+        self.snapshot_ids
+            .iter()
+            .find(|(hash, _)| hash == commit_hash)
+            .and_then(|(_, ids)| ids.get(&path))
+            .map(String::as_str)
+    }
+
+    /// Stable id of a path in the most recent snapshot containing it
+    pub fn latest_id(&self, path: &str) -> Option<&str> {
+        let path = normalize_path(Path::new(path));
+        self.snapshot_ids
+            .iter()
+            .rev()
+            .find_map(|(_, ids)| ids.get(&path))
+            .map(String::as_str)
+    }
+
+    /// Every path a tracked file had, oldest first
+    pub fn locations(&self, id: &str) -> &[NodeLocation] {
+        self.locations.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &String> {
+        self.locations.keys()
+    }
+
+    /// Metrics of the file currently or last known at `path`, across all of its
+    /// former paths
+    pub fn metric_history(&self, snapshots: &[CommitSnapshot], path: &str) -> Vec<NodeMetricPoint> {
+        let Some(id) = self.latest_id(path) else {
+            return Vec::new();
+        };
+        let locations: HashMap<&str, &NodeLocation> = self
+            .locations(id)
+            .iter()
+            .map(|l| (l.commit_hash.as_str(), l))
+            .collect();
+
+        snapshots
+            .iter()
+            .filter_map(|snapshot| {
+                let location = locations.get(snapshot.commit_info.hash.as_str())?;
+                let metrics = node_metrics_or_derived(&snapshot.analysis_result)
+                    .remove(&location.path)?;
+                Some(NodeMetricPoint {
+                    commit_hash: location.commit_hash.clone(),
+                    timestamp: location.timestamp,
+                    path: location.path.clone(),
+                    metrics,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Restrict a repository-level change set to the analyzed subfolder and make
+/// its paths project-relative. Moves across the subfolder boundary become
+/// additions or deletions.
+pub fn project_relative_changes(changes: &FileChangeSet, subfolder: Option<&str>) -> FileChangeSet {
+    let prefix = subfolder
+        .map(|s| s.replace('\\', "/").trim_matches('/').to_string())
+        .filter(|s| !s.is_empty());
+    let relative = |path: &PathBuf| -> Option<PathBuf> {
+        match prefix {
+            Some(ref prefix) => path.strip_prefix(prefix).ok().map(Path::to_path_buf),
+            None => Some(path.clone()),
+        }
+    };
+    let relative_set = |paths: &HashSet<PathBuf>| -> HashSet<PathBuf> {
+        paths.iter().filter_map(relative).collect()
+    };
+
+    let mut result = FileChangeSet {
+        added_files: relative_set(&changes.added_files),
+        modified_files: relative_set(&changes.modified_files),
+        deleted_files: relative_set(&changes.deleted_files),
+        renamed_files: Vec::new(),
+    };
+
+    for (old_path, new_path) in &changes.renamed_files {
+        match (relative(old_path), relative(new_path)) {
+            (Some(old_path), Some(new_path)) => result.renamed_files.push((old_path, new_path)),
+            (Some(old_path), None) => {
+                result.deleted_files.insert(old_path);
+            }
+            (None, Some(new_path)) => {
+                result.added_files.insert(new_path);
+            }
+            (None, None) => {}
+        }
+    }
+
+    result
+}
+
+/// Files that exist in a snapshot, as normalized project-relative paths
+fn snapshot_files(snapshot: &CommitSnapshot) -> Vec<String> {
+    let result = &snapshot.analysis_result;
+    let mut files: HashSet<String> = result.analyzed_files.iter().map(|p| normalize_path(p)).collect();
+    if let Some(ref node_metrics) = result.node_metrics {
+        files.extend(node_metrics.keys().map(|k| normalize_path(Path::new(k))));
+    }
+
+    let mut files: Vec<String> = files.into_iter().collect();
+    files.sort();
+    files
+}
+
+fn unique_id(path: &str, used_ids: &mut HashSet<String>) -> String {
+    let mut id = path.to_string();
+    let mut suffix = 2;
+    while used_ids.contains(&id) {
+        id = format!("{}#{}", path, suffix);
+        suffix += 1;
+    }
+    used_ids.insert(id.clone());
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+
+    fn renamed(pairs: &[(&str, &str)]) -> FileChangeSet {
+        FileChangeSet {
+            renamed_files: pairs
+                .iter()
+                .map(|(from, to)| (PathBuf::from(from), PathBuf::from(to)))
+                .collect(),
+            ..FileChangeSet::default()
+        }
+    }
+
+    #[test]
+    fn test_identity_follows_moves() {
+        let snapshots = vec![
+            snapshot("c1", 100, vec![dep("lib/a.dart", "lib/b.dart")]),
+            snapshot("c2", 200, vec![dep("lib/core/a.dart", "lib/b.dart")]),
+            snapshot("c3", 300, vec![dep("lib/a.dart", "lib/core/a.dart")]),
+        ];
+        let changes = vec![
+            renamed(&[("lib/a.dart", "lib/core/a.dart")]),
+            FileChangeSet { added_files: HashSet::from([PathBuf::from("lib/a.dart")]), ..FileChangeSet::default() },
+        ];
+
+        let identities = NodeIdentityMap::build(&snapshots, &changes);
+        assert_eq!(identities.stable_id("c2", "lib/core/a.dart"), Some("lib/a.dart"));
+        assert_eq!(identities.stable_id("c3", "lib/core/a.dart"), Some("lib/a.dart"));
+        // A new file at the vacated path is a different node
+        assert_eq!(identities.stable_id("c3", "lib/a.dart"), Some("lib/a.dart#2"));
+
+        let paths: Vec<&str> = identities.locations("lib/a.dart").iter().map(|l| l.path.as_str()).collect();
+        assert_eq!(paths, vec!["lib/a.dart", "lib/core/a.dart", "lib/core/a.dart"]);
+
+        let history = identities.metric_history(&snapshots, "lib/core/a.dart");
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].path, "lib/a.dart");
+        assert_eq!(history[0].metrics.out_degree, 1);
+        assert_eq!(history[2].metrics.in_degree, 1);
+    }
+
+    #[test]
+    fn test_identity_follows_swapped_paths() {
+        let snapshots = vec![
+            snapshot("c1", 100, vec![dep("lib/a.dart", "lib/b.dart")]),
+            snapshot("c2", 200, vec![dep("lib/b.dart", "lib/a.dart")]),
+        ];
+        let changes = vec![renamed(&[("lib/a.dart", "lib/b.dart"), ("lib/b.dart", "lib/a.dart")])];
+
+        let identities = NodeIdentityMap::build(&snapshots, &changes);
+        assert_eq!(identities.stable_id("c2", "lib/b.dart"), Some("lib/a.dart"));
+        assert_eq!(identities.stable_id("c2", "lib/a.dart"), Some("lib/b.dart"));
+        assert_eq!(identities.ids().count(), 2);
+    }
+
+    #[test]
+    fn test_project_relative_changes() {
+        let changes = FileChangeSet {
+            added_files: HashSet::from([PathBuf::from("app/lib/new.dart"), PathBuf::from("docs/a.md")]),
+            ..renamed(&[
+                ("app/lib/a.dart", "app/lib/core/a.dart"),
+                ("app/lib/b.dart", "other/b.dart"),
+                ("tools/c.dart", "app/lib/c.dart"),
+            ])
+        };
+
+        let relative = project_relative_changes(&changes, Some("app/"));
+        assert_eq!(relative.renamed_files, vec![(PathBuf::from("lib/a.dart"), PathBuf::from("lib/core/a.dart"))]);
+        assert_eq!(
+            relative.added_files,
+            HashSet::from([PathBuf::from("lib/new.dart"), PathBuf::from("lib/c.dart")])
+        );
+        assert_eq!(relative.deleted_files, HashSet::from([PathBuf::from("lib/b.dart")]));
+    }
+}
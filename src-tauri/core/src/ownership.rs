@@ -0,0 +1,318 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::file_history::FileHistory;
+use crate::graph_algorithms::DependencyGraph;
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Where GitHub and GitLab look for a CODEOWNERS file, in order
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Share of a file's or folder's change weight that the bus factor authors must cover
+const BUS_FACTOR_COVERAGE: f64 = 0.5;
+
+/// One line of a CODEOWNERS file
+#[derive(Debug, Clone)]
+struct CodeOwnersRule {
+    patterns: Vec<Pattern>,
+    owners: Vec<String>,
+}
+
+/// Parsed CODEOWNERS file; the last matching rule wins
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<CodeOwnersRule>,
+}
+
+impl CodeOwners {
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut tokens = line.split_whitespace().take_while(|t| !t.starts_with('#'));
+                let pattern = tokens.next()?;
+                Some(CodeOwnersRule {
+                    patterns: Self::globs(pattern),
+                    owners: tokens.map(str::to_string).collect(),
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Translate a gitignore-style pattern into globs over repository-relative paths
+    fn globs(pattern: &str) -> Vec<Pattern> {
+        let directory_only = pattern.ends_with('/');
+        let trimmed = pattern.trim_matches('/');
+        let anchored = pattern.starts_with('/') || trimmed.contains('/');
+        let base = if anchored { trimmed.to_string() } else { format!("**/{}", trimmed) };
+
+        let mut globs = Vec::new();
+        // A plain name also covers everything inside a directory of that name
+        if !trimmed.rsplit('/').next().unwrap_or("").contains('*') || directory_only {
+            globs.push(format!("{}/**", base));
+        }
+        if !directory_only {
+            globs.push(base);
+        }
+        globs.iter().filter_map(|g| Pattern::new(g).ok()).collect()
+    }
+
+    /// Owners of a repository-relative path; an empty list means explicitly unowned
+    pub fn owners_of(&self, path: &str) -> Option<&[String]> {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.patterns.iter().any(|p| p.matches_with(path, options)))
+            .map(|rule| rule.owners.as_slice())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorShare {
+    pub author: String,
+    /// Lines changed, with every commit counting at least once
+    pub weight: f64,
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileOwnership {
+    pub path: String,
+    /// Owners from CODEOWNERS, or the top author when there is none
+    pub owners: Vec<String>,
+    pub authors: Vec<AuthorShare>,
+    pub bus_factor: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleOwnership {
+    pub path: String,
+    pub file_count: usize,
+    /// Owners of the member files, most files first
+    pub owners: Vec<String>,
+    pub authors: Vec<AuthorShare>,
+    pub bus_factor: usize,
+}
+
+/// An import from code owned by one team into code owned by another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossTeamEdge {
+    pub source_file: String,
+    pub target_file: String,
+    pub source_owners: Vec<String>,
+    pub target_owners: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OwnerSource {
+    Codeowners,
+    Authors,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipReport {
+    pub commit_hash: String,
+    pub owner_source: OwnerSource,
+    pub files: Vec<FileOwnership>,
+    /// Every folder containing analyzed files
+    pub modules: Vec<ModuleOwnership>,
+    pub cross_team_edges: Vec<CrossTeamEdge>,
+    /// Fraction of edges between owned files that cross an ownership boundary
+    pub cross_team_ratio: f64,
+}
+
+/// Normalize weights into shares, largest first
+fn author_shares(weights: &HashMap<String, f64>) -> Vec<AuthorShare> {
+    let total: f64 = weights.values().sum();
+    let mut shares: Vec<AuthorShare> = weights
+        .iter()
+        .map(|(author, &weight)| AuthorShare {
+            author: author.clone(),
+            weight,
+            share: if total > 0.0 { weight / total } else { 0.0 },
+        })
+        .collect();
+    shares.sort_by(|a, b| b.weight.total_cmp(&a.weight).then_with(|| a.author.cmp(&b.author)));
+    shares
+}
+
+/// Smallest number of authors who together made more than half of the changes
+fn bus_factor(shares: &[AuthorShare]) -> usize {
+    let mut covered = 0.0;
+    for (count, share) in shares.iter().enumerate() {
+        covered += share.share;
+        if covered > BUS_FACTOR_COVERAGE {
+            return count + 1;
+        }
+    }
+    shares.len()
+}
+
+/// Ownership, bus factor and cross-team coupling of one snapshot. `subfolder` maps
+/// project-relative paths back to the repository paths CODEOWNERS refers to.
+pub fn analyze_ownership(
+    snapshot: &CommitSnapshot,
+    history: &FileHistory,
+    codeowners: Option<&CodeOwners>,
+    subfolder: Option<&str>,
+) -> OwnershipReport {
+    let graph = DependencyGraph::from_analysis(&snapshot.analysis_result);
+    let as_of = Some(snapshot.commit_info.timestamp);
+    let repository_path = |path: &str| match subfolder.map(|s| s.trim_matches('/')) {
+        Some(prefix) if !prefix.is_empty() => format!("{}/{}", prefix, path),
+        _ => path.to_string(),
+    };
+
+    let mut files: Vec<FileOwnership> = graph
+        .nodes
+        .iter()
+        .map(|path| {
+            let weights: HashMap<String, f64> = history.author_weights(path, as_of).into_iter().collect();
+            let authors = author_shares(&weights);
+            let owners = match codeowners {
+                Some(codeowners) => codeowners
+                    .owners_of(&repository_path(path))
+                    .map(<[String]>::to_vec)
+                    .unwrap_or_default(),
+                None => authors.first().map(|a| vec![a.author.clone()]).unwrap_or_default(),
+            };
+            FileOwnership {
+                path: path.clone(),
+                owners,
+                bus_factor: bus_factor(&authors),
+                authors,
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut folders: BTreeMap<String, Vec<&FileOwnership>> = BTreeMap::new();
+    for file in &files {
+        let components: Vec<&str> = file.path.split('/').collect();
+        for depth in 1..components.len() {
+            folders.entry(components[..depth].join("/")).or_default().push(file);
+        }
+    }
+    let modules = folders
+        .into_iter()
+        .map(|(path, members)| {
+            let mut weights: HashMap<String, f64> = HashMap::new();
+            let mut owner_counts: HashMap<&str, usize> = HashMap::new();
+            for file in &members {
+                for author in &file.authors {
+                    *weights.entry(author.author.clone()).or_insert(0.0) += author.weight;
+                }
+                for owner in &file.owners {
+                    *owner_counts.entry(owner.as_str()).or_insert(0) += 1;
+                }
+            }
+            let mut owners: Vec<(&str, usize)> = owner_counts.into_iter().collect();
+            owners.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            let authors = author_shares(&weights);
+            ModuleOwnership {
+                path,
+                file_count: members.len(),
+                owners: owners.into_iter().map(|(o, _)| o.to_string()).collect(),
+                bus_factor: bus_factor(&authors),
+                authors,
+            }
+        })
+        .collect();
+
+    let owners_by_path: HashMap<&str, BTreeSet<&str>> = files
+        .iter()
+        .map(|f| (f.path.as_str(), f.owners.iter().map(String::as_str).collect()))
+        .collect();
+    let mut owned_edges = 0;
+    let mut cross_team_edges = Vec::new();
+    for (source, targets) in graph.successors.iter().enumerate() {
+        let source_owners = &owners_by_path[graph.nodes[source].as_str()];
+        for &target in targets {
+            let target_owners = &owners_by_path[graph.nodes[target].as_str()];
+            if source_owners.is_empty() || target_owners.is_empty() {
+                continue;
+            }
+            owned_edges += 1;
+            if source_owners.is_disjoint(target_owners) {
+                cross_team_edges.push(CrossTeamEdge {
+                    source_file: graph.nodes[source].clone(),
+                    target_file: graph.nodes[target].clone(),
+                    source_owners: source_owners.iter().map(|o| o.to_string()).collect(),
+                    target_owners: target_owners.iter().map(|o| o.to_string()).collect(),
+                });
+            }
+        }
+    }
+    cross_team_edges.sort_by(|a, b| (&a.source_file, &a.target_file).cmp(&(&b.source_file, &b.target_file)));
+
+    OwnershipReport {
+        commit_hash: snapshot.commit_info.hash.clone(),
+        owner_source: if codeowners.is_some() { OwnerSource::Codeowners } else { OwnerSource::Authors },
+        files,
+        modules,
+        cross_team_ratio: if owned_edges > 0 {
+            cross_team_edges.len() as f64 / owned_edges as f64
+        } else {
+            0.0
+        },
+        cross_team_edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{commit_changes, dep, modified, snapshot};
+
+    #[test]
+    fn test_codeowners_last_match_wins() {
+        let codeowners = CodeOwners::parse(
+            "# Default owners\n* @org/core\n/app/lib/ui/ @org/frontend # UI\n*.md\ndocs @org/writers\n",
+        );
+        assert_eq!(codeowners.owners_of("app/lib/main.dart").unwrap(), ["@org/core"]);
+        assert_eq!(codeowners.owners_of("app/lib/ui/button.dart").unwrap(), ["@org/frontend"]);
+        assert!(codeowners.owners_of("README.md").unwrap().is_empty());
+        assert_eq!(codeowners.owners_of("app/docs/guide.txt").unwrap(), ["@org/writers"]);
+    }
+
+    #[test]
+    fn test_ownership_bus_factor_and_cross_team_edges() {
+        let history = FileHistory::build(
+            &[
+                commit_changes("c1", "alice", 100, vec![modified("app/lib/ui/home.dart", 90), modified("app/lib/core/api.dart", 10)]),
+                commit_changes("c2", "bob", 200, vec![modified("app/lib/ui/home.dart", 10), modified("app/lib/core/api.dart", 45)]),
+                commit_changes("c3", "carol", 300, vec![modified("app/lib/core/api.dart", 45)]),
+            ],
+            Some("app"),
+        );
+        let snapshot = snapshot("c3", 300, vec![dep("lib/ui/home.dart", "lib/core/api.dart")]);
+        let codeowners = CodeOwners::parse("/app/lib/ui/ @org/frontend\n/app/lib/core/ @org/platform\n");
+
+        let report = analyze_ownership(&snapshot, &history, Some(&codeowners), Some("app"));
+        let home = report.files.iter().find(|f| f.path == "lib/ui/home.dart").unwrap();
+        assert_eq!(home.authors[0].author, "alice");
+        assert_eq!(home.bus_factor, 1);
+        let api = report.files.iter().find(|f| f.path == "lib/core/api.dart").unwrap();
+        assert_eq!(api.bus_factor, 2);
+
+        let lib = report.modules.iter().find(|m| m.path == "lib").unwrap();
+        assert_eq!(lib.file_count, 2);
+        assert_eq!(lib.owners, vec!["@org/frontend", "@org/platform"]);
+
+        assert_eq!(report.cross_team_edges.len(), 1);
+        assert_eq!(report.cross_team_edges[0].target_owners, vec!["@org/platform"]);
+        assert_eq!(report.cross_team_ratio, 1.0);
+
+        let by_author = analyze_ownership(&snapshot, &history, None, Some("app"));
+        assert_eq!(by_author.owner_source, OwnerSource::Authors);
+        assert_eq!(by_author.files[0].owners, vec!["bob".to_string()]);
+    }
+}
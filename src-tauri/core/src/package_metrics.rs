@@ -0,0 +1,216 @@
+use crate::dependency_analyzer::{AnalysisResult, NodeMetrics};
+use crate::graph_algorithms::{node_metrics_or_derived, DependencyGraph};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Distance from the main sequence beyond which a package is considered off it
+const ZONE_DISTANCE: f64 = 0.5;
+
+/// Where a package sits relative to the main sequence A + I = 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MainSequenceZone {
+    /// Concrete and heavily depended upon: rigid and hard to change
+    Pain,
+    /// Abstract and depended upon by nobody
+    Uselessness,
+    MainSequence,
+}
+
+/// Distance from the main sequence, D = |A + I - 1|
+pub fn distance(abstractness: f64, instability: f64) -> f64 {
+    (abstractness + instability - 1.0).abs()
+}
+
+pub fn zone(abstractness: f64, instability: f64) -> MainSequenceZone {
+    if distance(abstractness, instability) < ZONE_DISTANCE {
+        MainSequenceZone::MainSequence
+    } else if abstractness + instability < 1.0 {
+        MainSequenceZone::Pain
+    } else {
+        MainSequenceZone::Uselessness
+    }
+}
+
+/// Martin's abstractness/instability/distance metrics for a file or folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageMetrics {
+    pub path: String,
+    pub is_folder: bool,
+    pub abstract_types: u32,
+    pub total_types: u32,
+    pub abstractness: f64,
+    pub instability: f64,
+    pub distance: f64,
+    pub zone: MainSequenceZone,
+}
+
+impl PackageMetrics {
+    fn new(path: String, is_folder: bool, abstract_types: u32, total_types: u32, instability: f64) -> Self {
+        let abstractness = abstract_types as f64 / total_types as f64;
+        Self {
+            path,
+            is_folder,
+            abstract_types,
+            total_types,
+            abstractness,
+            instability,
+            distance: distance(abstractness, instability),
+            zone: zone(abstractness, instability),
+        }
+    }
+}
+
+/// Package metrics of every file whose declarations the analyzer counted
+pub fn file_package_metrics(result: &AnalysisResult) -> Vec<PackageMetrics> {
+    let mut metrics: Vec<PackageMetrics> = node_metrics_or_derived(result)
+        .into_iter()
+        .filter_map(|(path, m)| match (m.abstract_types, m.total_types) {
+            (Some(abstract_types), Some(total)) if total > 0 => {
+                Some(PackageMetrics::new(path, false, abstract_types, total, m.instability))
+            }
+            _ => None,
+        })
+        .collect();
+    metrics.sort_by(|a, b| a.path.cmp(&b.path));
+    metrics
+}
+
+/// Folders containing a path, outermost first, starting with the root ""
+fn enclosing_folders(path: &str) -> impl Iterator<Item = &str> {
+    std::iter::once("").chain(path.match_indices('/').map(move |(i, _)| &path[..i]))
+}
+
+/// Declarations and boundary-crossing dependencies of every folder of a snapshot,
+/// aggregated in one pass over its nodes and edges
+pub struct FolderPackages {
+    /// Abstract and total types declared below each folder
+    declarations: BTreeMap<String, (u32, u32)>,
+    /// Dependencies entering and leaving each folder
+    coupling: HashMap<String, (u32, u32)>,
+}
+
+impl FolderPackages {
+    pub fn new(metrics: &HashMap<String, NodeMetrics>, graph: &DependencyGraph) -> Self {
+        let mut declarations: BTreeMap<String, (u32, u32)> = BTreeMap::new();
+        for (path, m) in metrics {
+            let (Some(abstract_types), Some(total_types)) = (m.abstract_types, m.total_types) else {
+                continue;
+            };
+            for folder in enclosing_folders(path) {
+                let entry = declarations.entry(folder.to_string()).or_default();
+                entry.0 += abstract_types;
+                entry.1 += total_types;
+            }
+        }
+
+        let mut coupling: HashMap<String, (u32, u32)> = HashMap::new();
+        for (source, targets) in graph.successors.iter().enumerate() {
+            let source_folders: Vec<&str> = enclosing_folders(&graph.nodes[source]).collect();
+            for &target in targets {
+                let target_folders: Vec<&str> = enclosing_folders(&graph.nodes[target]).collect();
+                let shared = source_folders
+                    .iter()
+                    .zip(&target_folders)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                for folder in &source_folders[shared..] {
+                    coupling.entry(folder.to_string()).or_default().1 += 1;
+                }
+                for folder in &target_folders[shared..] {
+                    coupling.entry(folder.to_string()).or_default().0 += 1;
+                }
+            }
+        }
+
+        Self { declarations, coupling }
+    }
+
+    /// Package metrics of a folder: abstractness over all types declared below it
+    /// and instability from the dependencies crossing its boundary
+    pub fn metrics(&self, folder: &str) -> Option<PackageMetrics> {
+        let &(abstract_types, total_types) = self.declarations.get(folder)?;
+        if total_types == 0 {
+            return None;
+        }
+        let (afferent, efferent) = self.coupling.get(folder).copied().unwrap_or_default();
+        let instability = if afferent + efferent > 0 {
+            efferent as f64 / (afferent + efferent) as f64
+        } else {
+            0.0
+        };
+        Some(PackageMetrics::new(folder.to_string(), true, abstract_types, total_types, instability))
+    }
+
+    /// Package metrics of every folder containing at least one counted file
+    pub fn all(&self) -> Vec<PackageMetrics> {
+        self.declarations
+            .keys()
+            .filter(|folder| !folder.is_empty())
+            .filter_map(|folder| self.metrics(folder))
+            .collect()
+    }
+}
+
+/// Package metrics of a folder, or of the whole project for ""
+pub fn folder_package_metrics(result: &AnalysisResult, folder: &str) -> Option<PackageMetrics> {
+    FolderPackages::new(&node_metrics_or_derived(result), &DependencyGraph::from_analysis(result)).metrics(folder)
+}
+
+/// Package metrics of every folder containing at least one counted file
+pub fn all_folder_package_metrics(result: &AnalysisResult) -> Vec<PackageMetrics> {
+    FolderPackages::new(&node_metrics_or_derived(result), &DependencyGraph::from_analysis(result)).all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{analysis_result, dep};
+
+    fn with_declarations(deps: Vec<crate::dependency_analyzer::RawDependency>, types: &[(&str, u32, u32)]) -> AnalysisResult {
+        let mut result = analysis_result(deps);
+        let mut metrics: HashMap<String, NodeMetrics> = node_metrics_or_derived(&result);
+        for (path, abstract_types, total_types) in types {
+            metrics.get_mut(*path).unwrap().set_type_declarations(*abstract_types, *total_types);
+        }
+        result.node_metrics = Some(metrics);
+        result
+    }
+
+    #[test]
+    fn test_distance_and_zones() {
+        assert_eq!(distance(1.0, 0.0), 0.0);
+        assert_eq!(zone(0.0, 0.0), MainSequenceZone::Pain);
+        assert_eq!(zone(1.0, 1.0), MainSequenceZone::Uselessness);
+        assert_eq!(zone(0.5, 0.5), MainSequenceZone::MainSequence);
+    }
+
+    #[test]
+    fn test_file_and_folder_metrics() {
+        let result = with_declarations(
+            vec![dep("lib/app/main.dart", "lib/core/repo.dart"), dep("lib/core/repo.dart", "lib/core/model.dart")],
+            &[("lib/app/main.dart", 0, 1), ("lib/core/repo.dart", 1, 1), ("lib/core/model.dart", 0, 2)],
+        );
+
+        let files = file_package_metrics(&result);
+        let model = files.iter().find(|m| m.path == "lib/core/model.dart").unwrap();
+        assert_eq!(model.abstractness, 0.0);
+        assert_eq!(model.instability, 0.0);
+        assert_eq!(model.zone, MainSequenceZone::Pain);
+
+        let core = folder_package_metrics(&result, "lib/core").unwrap();
+        assert_eq!((core.abstract_types, core.total_types), (1, 3));
+        assert_eq!(core.instability, 0.0);
+        assert!((core.distance - 2.0 / 3.0).abs() < 1e-9);
+
+        let folders = all_folder_package_metrics(&result);
+        let paths: Vec<&str> = folders.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, vec!["lib", "lib/app", "lib/core"]);
+        assert_eq!(folders[1].instability, 1.0);
+        assert_eq!(folders[2].instability, core.instability);
+
+        let project = folder_package_metrics(&result, "").unwrap();
+        assert_eq!((project.abstract_types, project.total_types), (1, 4));
+        assert_eq!(project.instability, 0.0);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::dependency_analyzer::AnalysisResult;
+use crate::graph_algorithms::{global_metrics_or_derived, node_metrics_or_derived};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Quality model file read from the head of the analyzed branch when the
+/// configuration sets none
+pub const QUALITY_MODEL_FILE: &str = ".chronograph/quality.json";
+
+/// Starting point of every score before factors are applied
+const BASE_SCORE: f64 = 100.0;
+
+/// Named starting points for a scoring model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityPreset {
+    /// Leaf files are entry points; moderate coupling is acceptable
+    Application,
+    /// Public API files are often not imported internally, so orphans are not
+    /// penalized; cycles and coupling are held to a stricter standard
+    Library,
+}
+
+/// Penalty for dependency cycles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleFactor {
+    pub enabled: bool,
+    /// Deducted once when the graph has any cycle
+    pub base_penalty: f64,
+    /// Deducted for every detected cycle
+    pub per_cycle_penalty: f64,
+}
+
+/// Penalty for NCCD above a threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CouplingFactor {
+    pub enabled: bool,
+    pub nccd_threshold: f64,
+    /// Points per unit of NCCD above the threshold
+    pub penalty_per_unit: f64,
+}
+
+/// Penalty for files with no dependencies in either direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanFactor {
+    pub enabled: bool,
+    pub per_orphan_penalty: f64,
+    /// Upper bound on the total orphan penalty
+    pub max_penalty: Option<f64>,
+}
+
+/// Bonus for an average instability close to a target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstabilityFactor {
+    pub enabled: bool,
+    pub target: f64,
+    /// Deviation from the target at which the bonus reaches zero
+    pub tolerance: f64,
+    pub max_bonus: f64,
+}
+
+/// Weights and thresholds of the architecture quality score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityScoreModel {
+    pub preset: QualityPreset,
+    pub cycles: CycleFactor,
+    pub coupling: CouplingFactor,
+    pub orphans: OrphanFactor,
+    pub instability: InstabilityFactor,
+}
+
+impl Default for QualityScoreModel {
+    fn default() -> Self {
+        Self::preset(QualityPreset::Application)
+    }
+}
+
+impl QualityScoreModel {
+    pub fn preset(preset: QualityPreset) -> Self {
+        match preset {
+            QualityPreset::Application => Self {
+                preset,
+                cycles: CycleFactor { enabled: true, base_penalty: 30.0, per_cycle_penalty: 5.0 },
+                coupling: CouplingFactor { enabled: true, nccd_threshold: 0.5, penalty_per_unit: 40.0 },
+                orphans: OrphanFactor { enabled: true, per_orphan_penalty: 2.0, max_penalty: None },
+                instability: InstabilityFactor { enabled: true, target: 0.5, tolerance: 0.2, max_bonus: 5.0 },
+            },
+            QualityPreset::Library => Self {
+                preset,
+                cycles: CycleFactor { enabled: true, base_penalty: 40.0, per_cycle_penalty: 8.0 },
+                coupling: CouplingFactor { enabled: true, nccd_threshold: 0.3, penalty_per_unit: 50.0 },
+                orphans: OrphanFactor { enabled: false, per_orphan_penalty: 0.0, max_penalty: None },
+                instability: InstabilityFactor { enabled: true, target: 0.4, tolerance: 0.2, max_bonus: 5.0 },
+            },
+        }
+    }
+
+    /// Build a model from project configuration: the optional `preset` field picks
+    /// the starting point and any other fields override it, e.g.
+    /// `{"preset": "library", "coupling": {"nccd_threshold": 0.4}}`
+    pub fn from_config(config: &serde_json::Value) -> Result<Self> {
+        let preset = match config.get("preset") {
+            Some(preset) => serde_json::from_value(preset.clone())
+                .map_err(|e| anyhow!("Unknown quality preset {}: {}", preset, e))?,
+            None => QualityPreset::Application,
+        };
+
+        let mut merged = serde_json::to_value(Self::preset(preset))?;
+        merge_json(&mut merged, config);
+        Ok(serde_json::from_value(merged)?)
+    }
+}
+
+/// Recursively overlay `overrides` onto `base`
+fn merge_json(base: &mut serde_json::Value, overrides: &serde_json::Value) {
+    match (base, overrides) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityFactor {
+    Cycles,
+    Coupling,
+    Orphans,
+    Instability,
+}
+
+/// Points one factor added to (positive) or removed from (negative) the score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactorContribution {
+    pub factor: QualityFactor,
+    /// The measured input, e.g. number of cycles or NCCD
+    pub measured: f64,
+    pub points: f64,
+    pub explanation: String,
+}
+
+/// How a quality score was reached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityScoreBreakdown {
+    pub preset: QualityPreset,
+    pub base: f64,
+    pub factors: Vec<FactorContribution>,
+    /// Base plus all contributions, clamped to 0..=100
+    pub score: f64,
+}
+
+/// Score an analysis result. Metrics are derived from the dependency graph when the
+/// analyzer did not report them, so every analyzer is scored the same way.
+pub fn score(result: &AnalysisResult, model: &QualityScoreModel) -> QualityScoreBreakdown {
+    let global = global_metrics_or_derived(result);
+    let mut factors = Vec::new();
+
+    if model.cycles.enabled {
+        let cycles = global.detected_cycles.len();
+        let points = if global.is_acyclic {
+            0.0
+        } else {
+            -(model.cycles.base_penalty + cycles as f64 * model.cycles.per_cycle_penalty)
+        };
+        factors.push(FactorContribution {
+            factor: QualityFactor::Cycles,
+            measured: cycles as f64,
+            points,
+            explanation: if global.is_acyclic {
+                "No dependency cycles".to_string()
+            } else {
+                format!(
+                    "{} cycle(s): {} for having cycles plus {} each",
+                    cycles, model.cycles.base_penalty, model.cycles.per_cycle_penalty
+                )
+            },
+        });
+    }
+
+    if model.coupling.enabled {
+        let excess = (global.normalized_ccd - model.coupling.nccd_threshold).max(0.0);
+        factors.push(FactorContribution {
+            factor: QualityFactor::Coupling,
+            measured: global.normalized_ccd,
+            points: -excess * model.coupling.penalty_per_unit,
+            explanation: format!(
+                "NCCD {:.2} is {:.2} above the {:.2} threshold",
+                global.normalized_ccd, excess, model.coupling.nccd_threshold
+            ),
+        });
+    }
+
+    if model.orphans.enabled {
+        let orphans = global.orphan_libraries.len();
+        let mut penalty = orphans as f64 * model.orphans.per_orphan_penalty;
+        if let Some(max) = model.orphans.max_penalty {
+            penalty = penalty.min(max);
+        }
+        factors.push(FactorContribution {
+            factor: QualityFactor::Orphans,
+            measured: orphans as f64,
+            points: -penalty,
+            explanation: format!("{} orphan file(s), {} each", orphans, model.orphans.per_orphan_penalty),
+        });
+    }
+
+    if model.instability.enabled {
+        // Files with zero instability are excluded, matching the original scoring
+        let instabilities: Vec<f64> = node_metrics_or_derived(result)
+            .values()
+            .map(|m| m.instability)
+            .filter(|i| *i > 0.0)
+            .collect();
+        if !instabilities.is_empty() {
+            let average = instabilities.iter().sum::<f64>() / instabilities.len() as f64;
+            let deviation = (average - model.instability.target).abs();
+            let tolerance = model.instability.tolerance;
+            let points = if tolerance > 0.0 {
+                (tolerance - deviation.min(tolerance)) / tolerance * model.instability.max_bonus
+            } else {
+                0.0
+            };
+            factors.push(FactorContribution {
+                factor: QualityFactor::Instability,
+                measured: average,
+                points,
+                explanation: format!(
+                    "Average instability {:.2}, {:.2} from the {:.2} target",
+                    average, deviation, model.instability.target
+                ),
+            });
+        }
+    }
+
+    let raw = BASE_SCORE + factors.iter().map(|f| f.points).sum::<f64>();
+    QualityScoreBreakdown {
+        preset: model.preset,
+        base: BASE_SCORE,
+        factors,
+        score: raw.clamp(0.0, 100.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{analysis_result, dep};
+
+    fn points(breakdown: &QualityScoreBreakdown, factor: QualityFactor) -> f64 {
+        breakdown.factors.iter().find(|f| f.factor == factor).map(|f| f.points).unwrap()
+    }
+
+    #[test]
+    fn test_breakdown_sums_to_score() {
+        let result = analysis_result(vec![
+            dep("a.dart", "b.dart"),
+            dep("b.dart", "a.dart"),
+            dep("c.dart", "d.dart"),
+        ]);
+
+        let breakdown = score(&result, &QualityScoreModel::default());
+        assert_eq!(points(&breakdown, QualityFactor::Cycles), -35.0);
+        let total: f64 = breakdown.factors.iter().map(|f| f.points).sum();
+        assert!((breakdown.score - (100.0 + total)).abs() < 1e-9);
+
+        let library = score(&result, &QualityScoreModel::preset(QualityPreset::Library));
+        assert_eq!(points(&library, QualityFactor::Cycles), -48.0);
+        assert!(library.factors.iter().all(|f| f.factor != QualityFactor::Orphans));
+    }
+
+    #[test]
+    fn test_config_overrides_preset() {
+        let model = QualityScoreModel::from_config(&serde_json::json!({
+            "preset": "library",
+            "cycles": { "per_cycle_penalty": 1.0 }
+        }))
+        .unwrap();
+
+        assert_eq!(model.preset, QualityPreset::Library);
+        assert_eq!(model.cycles.per_cycle_penalty, 1.0);
+        assert_eq!(model.cycles.base_penalty, 40.0);
+        assert!(QualityScoreModel::from_config(&serde_json::json!({ "preset": "plugin" })).is_err());
+    }
+}
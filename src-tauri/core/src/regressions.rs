@@ -0,0 +1,210 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::snapshot_diff::{MetricDelta, SnapshotDiff};
+use serde::{Deserialize, Serialize};
+
+/// Thresholds file read from the head of the analyzed branch when the
+/// configuration sets none
+pub const THRESHOLDS_FILE: &str = ".chronograph/thresholds.json";
+
+/// Per-project limits beyond which a change between snapshots is reported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegressionThresholds {
+    /// Report every newly introduced dependency cycle
+    pub new_cycles: bool,
+    /// Absolute NCCD increase between two snapshots
+    pub nccd_increase: f64,
+    /// Factor by which a file's component dependency must grow (2.0 = doubling)
+    pub component_dependency_growth: f64,
+    /// Ignore CD growth on files whose new CD is below this
+    pub min_component_dependency: u32,
+    /// Instability at or below this counts as stable
+    pub stable_instability: f64,
+    /// Instability at or above this counts as unstable
+    pub unstable_instability: f64,
+    /// Points the architecture quality score may drop before it is reported
+    pub quality_score_drop: f64,
+}
+
+impl Default for RegressionThresholds {
+    fn default() -> Self {
+        Self {
+            new_cycles: true,
+            nccd_increase: 0.1,
+            component_dependency_growth: 2.0,
+            min_component_dependency: 4,
+            stable_instability: 0.3,
+            unstable_instability: 0.7,
+            quality_score_drop: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegressionKind {
+    NewCycle,
+    NccdIncrease,
+    ComponentDependencyGrowth,
+    InstabilityFlip,
+    QualityScoreDrop,
+}
+
+/// An architectural regression introduced between two consecutive snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionEvent {
+    pub kind: RegressionKind,
+    pub commit_hash: String,
+    pub author: String,
+    pub timestamp: i64,
+    /// Snapshot the change is measured against
+    pub previous_commit: String,
+    /// Affected file for node-level regressions
+    pub file_path: Option<String>,
+    /// Members of a new cycle
+    pub cycle_members: Vec<String>,
+    pub metric: Option<MetricDelta>,
+    pub message: String,
+}
+
+/// Compare a snapshot with its predecessor and list the regressions it introduced
+pub fn detect_regressions(
+    previous: &CommitSnapshot,
+    current: &CommitSnapshot,
+    diff: &SnapshotDiff,
+    thresholds: &RegressionThresholds,
+) -> Vec<RegressionEvent> {
+    let commit = &current.commit_info;
+    let event = |kind, file_path: Option<String>, metric: Option<MetricDelta>, message: String| RegressionEvent {
+        kind,
+        commit_hash: commit.hash.clone(),
+        author: commit.author_name.clone(),
+        timestamp: commit.timestamp,
+        previous_commit: previous.commit_info.hash.clone(),
+        file_path,
+        cycle_members: Vec::new(),
+        metric,
+        message,
+    };
+    let mut events = Vec::new();
+
+    if thresholds.new_cycles {
+        for members in &diff.introduced_cycles {
+            events.push(RegressionEvent {
+                cycle_members: members.clone(),
+                ..event(
+                    RegressionKind::NewCycle,
+                    None,
+                    None,
+                    format!("New dependency cycle between {} files: {}", members.len(), members.join(" -> ")),
+                )
+            });
+        }
+    }
+
+    let nccd = diff.global_metric_deltas.normalized_ccd;
+    if nccd.delta > thresholds.nccd_increase {
+        events.push(event(
+            RegressionKind::NccdIncrease,
+            None,
+            Some(nccd),
+            format!("NCCD rose from {:.2} to {:.2}", nccd.before, nccd.after),
+        ));
+    }
+
+    if let Some(quality) = diff.global_metric_deltas.architecture_quality_score {
+        if -quality.delta > thresholds.quality_score_drop {
+            events.push(event(
+                RegressionKind::QualityScoreDrop,
+                None,
+                Some(quality),
+                format!("Architecture quality score dropped from {:.1} to {:.1}", quality.before, quality.after),
+            ));
+        }
+    }
+
+    for node in &diff.node_metric_deltas {
+        let cd = node.component_dependency;
+        if cd.after >= thresholds.min_component_dependency as f64
+            && cd.before > 0.0
+            && cd.after >= cd.before * thresholds.component_dependency_growth
+        {
+            events.push(event(
+                RegressionKind::ComponentDependencyGrowth,
+                Some(node.file_path.clone()),
+                Some(cd),
+                format!("{} now depends on {} files (was {})", node.file_path, cd.after, cd.before),
+            ));
+        }
+
+        let instability = node.instability;
+        let became_unstable = instability.before <= thresholds.stable_instability
+            && instability.after >= thresholds.unstable_instability;
+        let became_stable = instability.before >= thresholds.unstable_instability
+            && instability.after <= thresholds.stable_instability;
+        if became_unstable || became_stable {
+            events.push(event(
+                RegressionKind::InstabilityFlip,
+                Some(node.file_path.clone()),
+                Some(instability),
+                format!(
+                    "{} flipped from {} to {} (instability {:.2} -> {:.2})",
+                    node.file_path,
+                    if became_unstable { "stable" } else { "unstable" },
+                    if became_unstable { "unstable" } else { "stable" },
+                    instability.before,
+                    instability.after,
+                ),
+            ));
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot_diff::diff_snapshots;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_detects_cycle_cd_growth_and_instability_flip() {
+        let previous = snapshot("c1", 100, vec![
+            dep("lib/a.dart", "lib/b.dart"),
+            dep("lib/b.dart", "lib/c.dart"),
+            dep("lib/d.dart", "lib/c.dart"),
+            dep("lib/e.dart", "lib/c.dart"),
+        ]);
+        let current = snapshot("c2", 200, vec![
+            dep("lib/a.dart", "lib/b.dart"),
+            dep("lib/b.dart", "lib/c.dart"),
+            dep("lib/c.dart", "lib/b.dart"),
+            dep("lib/c.dart", "lib/d.dart"),
+            dep("lib/c.dart", "lib/e.dart"),
+            dep("lib/c.dart", "lib/f.dart"),
+        ]);
+
+        let diff = diff_snapshots(&previous, &current, &[]);
+        let events = detect_regressions(&previous, &current, &diff, &RegressionThresholds::default());
+        let kinds: Vec<(RegressionKind, Option<&str>)> = events
+            .iter()
+            .map(|e| (e.kind, e.file_path.as_deref()))
+            .collect();
+
+        assert!(kinds.contains(&(RegressionKind::NewCycle, None)));
+        assert!(kinds.contains(&(RegressionKind::NccdIncrease, None)));
+        assert!(kinds.contains(&(RegressionKind::ComponentDependencyGrowth, Some("lib/c.dart"))));
+        assert!(kinds.contains(&(RegressionKind::InstabilityFlip, Some("lib/c.dart"))));
+        assert!(events.iter().all(|e| e.commit_hash == "c2" && e.previous_commit == "c1"));
+
+        let quiet = RegressionThresholds {
+            new_cycles: false,
+            nccd_increase: 1.0,
+            component_dependency_growth: 10.0,
+            unstable_instability: 1.1,
+            ..RegressionThresholds::default()
+        };
+        assert!(detect_regressions(&previous, &current, &diff, &quiet).is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+//! SARIF 2.1.0 log of the architectural findings at one commit, for code
+//! scanning dashboards. Rule violations keep the level of their rule, dependency
+//! cycles are warnings and regressions introduced by the commit are errors; a
+//! cycle the commit introduced is only reported as that regression.
+//! Results point at the offending import lines; project-wide regressions such as
+//! an NCCD increase have no location.
+
+use crate::architecture_rules::rule_issues;
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::IssueLevel;
+use crate::dependency_view::normalize_path;
+use crate::git_navigator::RepoCloneInfo;
+use crate::graph_algorithms::global_metrics_or_derived;
+use crate::regressions::{RegressionEvent, RegressionKind};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const CYCLE_RULE: &str = "chronograph/dependency-cycle";
+
+/// A source line, relative to the analyzed project
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Location {
+    path: String,
+    line: Option<u32>,
+}
+
+struct Finding {
+    rule_id: String,
+    level: IssueLevel,
+    message: String,
+    /// The first location is the primary one
+    locations: Vec<Location>,
+}
+
+struct RuleMetadata {
+    name: String,
+    description: String,
+    level: IssueLevel,
+}
+
+fn sarif_level(level: &IssueLevel) -> &'static str {
+    match level {
+        IssueLevel::Error => "error",
+        IssueLevel::Warning => "warning",
+        IssueLevel::Info => "note",
+    }
+}
+
+/// Rule id for a rule name: lowercase with dashes for anything but letters and digits
+fn rule_slug(name: &str) -> String {
+    let slug: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    slug.join("-")
+}
+
+/// Imports between members of a cycle, which are where it can be broken
+fn cycle_imports(snapshot: &CommitSnapshot, members: &[String]) -> Vec<Location> {
+    let members: BTreeSet<&str> = members.iter().map(String::as_str).collect();
+    let locations: BTreeSet<Location> = snapshot
+        .analysis_result
+        .dependencies
+        .iter()
+        .filter_map(|dep| {
+            let source = normalize_path(&dep.source_file);
+            let target = normalize_path(&dep.target_file);
+            (source != target && members.contains(source.as_str()) && members.contains(target.as_str()))
+                .then_some(Location { path: source, line: dep.line_number })
+        })
+        .collect();
+    locations.into_iter().collect()
+}
+
+fn regression_rule(regression: &RegressionEvent) -> (String, &'static str) {
+    let kind = serde_json::to_value(regression.kind)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let description = match regression.kind {
+        RegressionKind::NewCycle => "A dependency cycle was introduced",
+        RegressionKind::NccdIncrease => "Normalized cumulative component dependency rose",
+        RegressionKind::ComponentDependencyGrowth => "A file's component dependency grew sharply",
+        RegressionKind::InstabilityFlip => "A stable file became unstable",
+        RegressionKind::QualityScoreDrop => "The architecture quality score dropped",
+    };
+    (format!("chronograph/regression/{}", kind.replace('_', "-")), description)
+}
+
+fn findings(snapshot: &CommitSnapshot) -> (Vec<Finding>, BTreeMap<String, RuleMetadata>) {
+    let mut findings = Vec::new();
+    let mut rules: BTreeMap<String, RuleMetadata> = BTreeMap::new();
+
+    for issue in rule_issues(snapshot) {
+        let name = issue.rule.clone().unwrap_or_default();
+        let rule_id = format!("chronograph/rule/{}", rule_slug(&name));
+        rules.entry(rule_id.clone()).or_insert_with(|| RuleMetadata {
+            description: format!("Architecture rule: {}", name),
+            name,
+            level: issue.level.clone(),
+        });
+        findings.push(Finding {
+            rule_id,
+            level: issue.level.clone(),
+            message: issue.message.clone(),
+            locations: issue
+                .file_path
+                .as_deref()
+                .map(|path| Location { path: normalize_path(path), line: issue.line_number })
+                .into_iter()
+                .collect(),
+        });
+    }
+
+    let new_cycles: BTreeSet<BTreeSet<&str>> = snapshot
+        .regressions
+        .iter()
+        .filter(|r| r.kind == RegressionKind::NewCycle)
+        .map(|r| r.cycle_members.iter().map(String::as_str).collect())
+        .collect();
+    for cycle in global_metrics_or_derived(&snapshot.analysis_result).detected_cycles {
+        if new_cycles.contains(&cycle.iter().map(String::as_str).collect::<BTreeSet<_>>()) {
+            continue;
+        }
+        rules.entry(CYCLE_RULE.to_string()).or_insert_with(|| RuleMetadata {
+            name: "Dependency cycle".to_string(),
+            description: "Files that depend on each other through a cycle of imports".to_string(),
+            level: IssueLevel::Warning,
+        });
+        findings.push(Finding {
+            rule_id: CYCLE_RULE.to_string(),
+            level: IssueLevel::Warning,
+            message: format!("Dependency cycle between {} files: {}", cycle.len(), cycle.join(", ")),
+            locations: cycle_imports(snapshot, &cycle),
+        });
+    }
+
+    for regression in &snapshot.regressions {
+        let (rule_id, description) = regression_rule(regression);
+        rules.entry(rule_id.clone()).or_insert_with(|| RuleMetadata {
+            name: description.to_string(),
+            description: format!("{} in this commit", description),
+            level: IssueLevel::Error,
+        });
+        let locations = if !regression.cycle_members.is_empty() {
+            cycle_imports(snapshot, &regression.cycle_members)
+        } else {
+            regression.file_path.iter().map(|path| Location { path: path.clone(), line: None }).collect()
+        };
+        findings.push(Finding {
+            rule_id,
+            level: IssueLevel::Error,
+            message: regression.message.clone(),
+            locations,
+        });
+    }
+
+    (findings, rules)
+}
+
+/// SARIF log for a snapshot. `subfolder` is the analyzed project's folder within
+/// the repository, so that locations resolve against the repository root.
+pub fn sarif(snapshot: &CommitSnapshot, repository: Option<&RepoCloneInfo>, subfolder: Option<&str>) -> Result<String> {
+    let (findings, rules) = findings(snapshot);
+    let prefix = subfolder.map(|s| s.trim_matches('/')).filter(|s| !s.is_empty());
+    let physical_location = |location: &Location| {
+        let uri = match prefix {
+            Some(prefix) => format!("{}/{}", prefix, location.path),
+            None => location.path.clone(),
+        };
+        let mut physical = json!({ "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" } });
+        if let Some(line) = location.line {
+            physical["region"] = json!({ "startLine": line });
+        }
+        json!({ "physicalLocation": physical })
+    };
+
+    let rule_index: BTreeMap<&str, usize> = rules.keys().enumerate().map(|(i, id)| (id.as_str(), i)).collect();
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let mut result = json!({
+                "ruleId": finding.rule_id,
+                "ruleIndex": rule_index[finding.rule_id.as_str()],
+                "level": sarif_level(&finding.level),
+                "message": { "text": finding.message },
+                "locations": finding.locations.first().map(physical_location).into_iter().collect::<Vec<_>>(),
+            });
+            if finding.locations.len() > 1 {
+                result["relatedLocations"] = finding.locations[1..]
+                    .iter()
+                    .enumerate()
+                    .map(|(id, location)| {
+                        let mut related = physical_location(location);
+                        related["id"] = json!(id + 1);
+                        related["message"] = json!({ "text": "Import in the same cycle" });
+                        related
+                    })
+                    .collect();
+            }
+            result
+        })
+        .collect();
+
+    let rules: Vec<Value> = rules
+        .iter()
+        .map(|(id, rule)| json!({
+            "id": id,
+            "name": rule.name,
+            "shortDescription": { "text": rule.description },
+            "defaultConfiguration": { "level": sarif_level(&rule.level) },
+            "properties": { "tags": ["architecture"] },
+        }))
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "ChronoGraph",
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            }
+        },
+        "originalUriBaseIds": { "%SRCROOT%": { "description": { "text": "Repository root" } } },
+        "results": results,
+    });
+    if let Some(repository) = repository {
+        run["versionControlProvenance"] = json!([{
+            "repositoryUri": repository.original_url,
+            "revisionId": snapshot.commit_info.hash,
+            "branch": repository.default_branch,
+        }]);
+    }
+
+    Ok(serde_json::to_string_pretty(&json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    }))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependency_analyzer::AnalysisIssue;
+    use crate::test_fixtures::{dep, snapshot};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_sarif_results() {
+        let mut snapshot = snapshot("c2", 200, vec![
+            dep("lib/a.dart", "lib/b.dart"),
+            dep("lib/b.dart", "lib/a.dart"),
+            dep("lib/ui/page.dart", "lib/data/db.dart"),
+            dep("lib/x.dart", "lib/y.dart"),
+            dep("lib/y.dart", "lib/x.dart"),
+        ]);
+        snapshot.analysis_result.dependencies[0].line_number = Some(3);
+        snapshot.analysis_result.dependencies[1].line_number = Some(5);
+        snapshot.analysis_result.dependencies[3].line_number = Some(9);
+        snapshot.analysis_result.issues.push(AnalysisIssue {
+            level: IssueLevel::Warning,
+            message: "lib/ui/page.dart imports lib/data/db.dart".to_string(),
+            file_path: Some(PathBuf::from("lib/ui/page.dart")),
+            line_number: Some(7),
+            import_statement: None,
+            target_path: Some(PathBuf::from("lib/data/db.dart")),
+            rule: Some("UI must not import data".to_string()),
+        });
+        snapshot.regressions.push(RegressionEvent {
+            kind: RegressionKind::NewCycle,
+            commit_hash: "c2".to_string(),
+            author: "alice".to_string(),
+            timestamp: 200,
+            previous_commit: "c1".to_string(),
+            file_path: None,
+            cycle_members: vec!["lib/a.dart".to_string(), "lib/b.dart".to_string()],
+            metric: None,
+            message: "New cycle".to_string(),
+        });
+
+        let log: Value = serde_json::from_str(&sarif(&snapshot, None, Some("app")).unwrap()).unwrap();
+        let run = &log["runs"][0];
+        let rule_ids: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rule_ids, vec![
+            "chronograph/dependency-cycle",
+            "chronograph/regression/new-cycle",
+            "chronograph/rule/ui-must-not-import-data",
+        ]);
+
+        // The cycle introduced in c2 is only reported as the regression
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[0]["ruleIndex"], 2);
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "app/lib/ui/page.dart");
+        assert_eq!(location["region"]["startLine"], 7);
+
+        assert_eq!(results[1]["ruleId"], CYCLE_RULE);
+        assert_eq!(results[1]["locations"][0]["physicalLocation"]["region"]["startLine"], 9);
+
+        assert_eq!(results[2]["ruleId"], "chronograph/regression/new-cycle");
+        assert_eq!(results[2]["level"], "error");
+        assert_eq!(results[2]["locations"][0]["physicalLocation"]["region"]["startLine"], 3);
+        assert_eq!(results[2]["relatedLocations"][0]["physicalLocation"]["region"]["startLine"], 5);
+    }
+}
@@ -0,0 +1,435 @@
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::{AnalysisResult, NodeMetrics, RelationshipType};
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::{dependency_cycles, global_metrics_or_derived, node_metrics_or_derived};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Structural difference between two snapshots (not necessarily adjacent)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub from_commit: String,
+    pub to_commit: String,
+    pub added_edges: Vec<EdgeChange>,
+    pub removed_edges: Vec<EdgeChange>,
+    /// Edges present in both snapshots whose relationship type changed
+    pub retyped_edges: Vec<RetypedEdge>,
+    pub added_nodes: Vec<String>,
+    pub removed_nodes: Vec<String>,
+    /// Files moved between the two commits as (old path, new path)
+    pub renamed_nodes: Vec<(String, String)>,
+    /// Metric changes for nodes present in both snapshots (only changed nodes)
+    pub node_metric_deltas: Vec<NodeMetricDelta>,
+    pub introduced_cycles: Vec<Vec<String>>,
+    pub resolved_cycles: Vec<Vec<String>>,
+    pub global_metric_deltas: GlobalMetricDeltas,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeChange {
+    pub source_file: String,
+    pub target_file: String,
+    pub relationship_type: RelationshipType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetypedEdge {
+    pub source_file: String,
+    pub target_file: String,
+    pub from_type: RelationshipType,
+    pub to_type: RelationshipType,
+}
+
+/// A value before and after, with the difference precomputed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+}
+
+impl MetricDelta {
+    pub fn new(before: f64, after: f64) -> Self {
+        Self {
+            before,
+            after,
+            delta: after - before,
+        }
+    }
+
+    pub fn changed(&self) -> bool {
+        self.delta.abs() > f64::EPSILON
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeMetricDelta {
+    /// Path in the `to` snapshot
+    pub file_path: String,
+    /// Path in the `from` snapshot when the file was renamed
+    pub previous_path: Option<String>,
+    pub in_degree: MetricDelta,
+    pub out_degree: MetricDelta,
+    pub component_dependency: MetricDelta,
+    pub instability: MetricDelta,
+    pub sloc: MetricDelta,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GlobalMetricDeltas {
+    pub num_nodes: MetricDelta,
+    pub num_edges: MetricDelta,
+    pub avg_degree: MetricDelta,
+    pub cumulative_component_dependency: MetricDelta,
+    pub average_component_dependency: MetricDelta,
+    pub normalized_ccd: MetricDelta,
+    pub total_sloc: MetricDelta,
+    pub cycle_count: MetricDelta,
+    /// Only present when both snapshots have a quality score
+    pub architecture_quality_score: Option<MetricDelta>,
+}
+
+/// Compare two snapshots.
+///
+/// `renames` maps paths in `from` to paths in `to` (project-relative), so a
+/// moved file keeps its edges and metrics instead of showing up as removed + added.
+pub fn diff_snapshots(
+    from: &CommitSnapshot,
+    to: &CommitSnapshot,
+    renames: &[(PathBuf, PathBuf)],
+) -> SnapshotDiff {
+    let rename_map: HashMap<String, String> = renames
+        .iter()
+        .map(|(old, new)| (normalize_path(old), normalize_path(new)))
+        .collect();
+    let follow = |path: &str| rename_map.get(path).cloned().unwrap_or_else(|| path.to_string());
+
+    let from_result = &from.analysis_result;
+    let to_result = &to.analysis_result;
+
+    // Relationship types per endpoint pair, with `from` endpoints translated through renames
+    let mut from_edges: BTreeMap<(String, String), BTreeSet<RelationshipType>> = BTreeMap::new();
+    for ((s, t), types) in edge_map(from_result) {
+        from_edges.entry((follow(&s), follow(&t))).or_default().extend(types);
+    }
+    let to_edges = edge_map(to_result);
+    let no_types = BTreeSet::new();
+
+    let mut added_edges = Vec::new();
+    let mut removed_edges = Vec::new();
+    let mut retyped_edges = Vec::new();
+    let pairs: BTreeSet<&(String, String)> = from_edges.keys().chain(to_edges.keys()).collect();
+    for pair in pairs {
+        let (source, target) = pair;
+        let from_types = from_edges.get(pair).unwrap_or(&no_types);
+        let to_types = to_edges.get(pair).unwrap_or(&no_types);
+        let mut dropped = from_types.difference(to_types);
+        let mut gained = to_types.difference(from_types);
+        let edge = |ty: &RelationshipType| EdgeChange {
+            source_file: source.clone(),
+            target_file: target.clone(),
+            relationship_type: ty.clone(),
+        };
+
+        // A type that disappeared while another appeared on an existing edge is a retype
+        if !from_types.is_empty() && !to_types.is_empty() {
+            for (from_type, to_type) in dropped.by_ref().zip(gained.by_ref()) {
+                retyped_edges.push(RetypedEdge {
+                    source_file: source.clone(),
+                    target_file: target.clone(),
+                    from_type: from_type.clone(),
+                    to_type: to_type.clone(),
+                });
+            }
+        }
+        removed_edges.extend(dropped.map(edge));
+        added_edges.extend(gained.map(edge));
+    }
+
+    // Nodes
+    let from_nodes = node_set(from_result);
+    let to_nodes = node_set(to_result);
+    let renamed_nodes: Vec<(String, String)> = rename_map
+        .iter()
+        .filter(|(old, new)| from_nodes.contains(*old) && to_nodes.contains(*new))
+        .map(|(old, new)| (old.clone(), new.clone()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let carried: BTreeSet<String> = from_nodes.iter().map(|n| follow(n)).collect();
+    let added_nodes = to_nodes.difference(&carried).cloned().collect();
+    let removed_nodes = from_nodes
+        .iter()
+        .filter(|n| !to_nodes.contains(&follow(n)))
+        .cloned()
+        .collect();
+
+    // Per-node metric deltas
+    let from_metrics = node_metrics_or_derived(from_result);
+    let to_metrics = node_metrics_or_derived(to_result);
+    let mut node_metric_deltas: Vec<NodeMetricDelta> = from_metrics
+        .iter()
+        .filter_map(|(path, before)| {
+            let new_path = follow(path);
+            let after = to_metrics.get(&new_path)?;
+            let delta = node_delta(&new_path, path, before, after);
+            delta.has_changes().then_some(delta)
+        })
+        .collect();
+    node_metric_deltas.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+    // Cycles, matched by member set after renames
+    let from_cycles: BTreeSet<Vec<String>> = dependency_cycles(from_result)
+        .into_iter()
+        .map(|cycle| {
+            let members: BTreeSet<String> = cycle.iter().map(|m| follow(m)).collect();
+            members.into_iter().collect()
+        })
+        .collect();
+    let to_cycles: BTreeSet<Vec<String>> = dependency_cycles(to_result).into_iter().collect();
+
+    SnapshotDiff {
+        from_commit: from.commit_info.hash.clone(),
+        to_commit: to.commit_info.hash.clone(),
+        added_edges,
+        removed_edges,
+        retyped_edges,
+        added_nodes,
+        removed_nodes,
+        renamed_nodes,
+        node_metric_deltas,
+        introduced_cycles: to_cycles.difference(&from_cycles).cloned().collect(),
+        resolved_cycles: from_cycles.difference(&to_cycles).cloned().collect(),
+        global_metric_deltas: global_deltas(from_result, to_result),
+    }
+}
+
+/// Translate repository-relative rename pairs into project-relative ones,
+/// dropping pairs that fall outside the analyzed subfolder
+pub fn project_relative_renames(
+    renames: &[(PathBuf, PathBuf)],
+    subfolder: Option<&str>,
+) -> Vec<(PathBuf, PathBuf)> {
+    let Some(subfolder) = subfolder else {
+        return renames.to_vec();
+    };
+    let prefix = Path::new(subfolder.trim_matches('/'));
+
+    renames
+        .iter()
+        .filter_map(|(old, new)| {
+            let old = old.strip_prefix(prefix).ok()?;
+            let new = new.strip_prefix(prefix).ok()?;
+            Some((old.to_path_buf(), new.to_path_buf()))
+        })
+        .collect()
+}
+
+impl SnapshotDiff {
+    /// True when nothing structural changed between the snapshots
+    pub fn is_empty(&self) -> bool {
+        self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.retyped_edges.is_empty()
+            && self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.renamed_nodes.is_empty()
+            && self.introduced_cycles.is_empty()
+            && self.resolved_cycles.is_empty()
+    }
+}
+
+impl NodeMetricDelta {
+    fn has_changes(&self) -> bool {
+        self.previous_path.is_some()
+            || self.in_degree.changed()
+            || self.out_degree.changed()
+            || self.component_dependency.changed()
+            || self.instability.changed()
+            || self.sloc.changed()
+    }
+}
+
+/// Relationship types of each edge, keyed by endpoints
+fn edge_map(result: &AnalysisResult) -> BTreeMap<(String, String), BTreeSet<RelationshipType>> {
+    let mut edges: BTreeMap<(String, String), BTreeSet<RelationshipType>> = BTreeMap::new();
+    for dep in &result.dependencies {
+        edges
+            .entry((normalize_path(&dep.source_file), normalize_path(&dep.target_file)))
+            .or_default()
+            .insert(dep.relationship_type.clone());
+    }
+    edges
+}
+
+fn node_set(result: &AnalysisResult) -> BTreeSet<String> {
+    let mut nodes: BTreeSet<String> = result.analyzed_files.iter().map(|p| normalize_path(p)).collect();
+    for dep in &result.dependencies {
+        nodes.insert(normalize_path(&dep.source_file));
+        nodes.insert(normalize_path(&dep.target_file));
+    }
+    nodes
+}
+
+fn node_delta(path: &str, previous_path: &str, before: &NodeMetrics, after: &NodeMetrics) -> NodeMetricDelta {
+    NodeMetricDelta {
+        file_path: path.to_string(),
+        previous_path: (path != previous_path).then(|| previous_path.to_string()),
+        in_degree: MetricDelta::new(before.in_degree as f64, after.in_degree as f64),
+        out_degree: MetricDelta::new(before.out_degree as f64, after.out_degree as f64),
+        component_dependency: MetricDelta::new(
+            before.component_dependency as f64,
+            after.component_dependency as f64,
+        ),
+        instability: MetricDelta::new(before.instability, after.instability),
+        sloc: MetricDelta::new(before.sloc as f64, after.sloc as f64),
+    }
+}
+
+fn global_deltas(from: &AnalysisResult, to: &AnalysisResult) -> GlobalMetricDeltas {
+    let from_global = global_metrics_or_derived(from);
+    let to_global = global_metrics_or_derived(to);
+
+    GlobalMetricDeltas {
+        num_nodes: MetricDelta::new(from_global.num_nodes as f64, to_global.num_nodes as f64),
+        num_edges: MetricDelta::new(from_global.num_edges as f64, to_global.num_edges as f64),
+        avg_degree: MetricDelta::new(from_global.avg_degree, to_global.avg_degree),
+        cumulative_component_dependency: MetricDelta::new(
+            from_global.cumulative_component_dependency as f64,
+            to_global.cumulative_component_dependency as f64,
+        ),
+        average_component_dependency: MetricDelta::new(
+            from_global.average_component_dependency,
+            to_global.average_component_dependency,
+        ),
+        normalized_ccd: MetricDelta::new(from_global.normalized_ccd, to_global.normalized_ccd),
+        total_sloc: MetricDelta::new(from_global.total_sloc as f64, to_global.total_sloc as f64),
+        cycle_count: MetricDelta::new(
+            dependency_cycles(from).len() as f64,
+            dependency_cycles(to).len() as f64,
+        ),
+        architecture_quality_score: from
+            .architecture_quality_score
+            .zip(to.architecture_quality_score)
+            .map(|(before, after)| MetricDelta::new(before, after)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_edge_and_node_changes() {
+        let from = snapshot("a", 1, vec![
+            dep("lib/a.dart", "lib/b.dart"),
+            dep("lib/b.dart", "lib/c.dart"),
+        ]);
+        let mut exported = dep("lib/a.dart", "lib/b.dart");
+        exported.relationship_type = RelationshipType::Export;
+        let to = snapshot("b", 2, vec![
+            exported,
+            dep("lib/a.dart", "lib/d.dart"),
+        ]);
+
+        let diff = diff_snapshots(&from, &to, &[]);
+        assert_eq!(diff.added_edges.len(), 1);
+        assert_eq!(diff.added_edges[0].target_file, "lib/d.dart");
+        assert_eq!(diff.removed_edges.len(), 1);
+        assert_eq!(diff.removed_edges[0].source_file, "lib/b.dart");
+        assert_eq!(diff.retyped_edges.len(), 1);
+        assert_eq!(diff.added_nodes, vec!["lib/d.dart".to_string()]);
+        assert_eq!(diff.removed_nodes, vec!["lib/c.dart".to_string()]);
+        assert_eq!(diff.global_metric_deltas.num_nodes.delta, 0.0);
+    }
+
+    #[test]
+    fn test_edges_with_several_types() {
+        let typed = |source: &str, target: &str, ty: RelationshipType| {
+            let mut dep = dep(source, target);
+            dep.relationship_type = ty;
+            dep
+        };
+        let from = snapshot("a", 1, vec![
+            typed("lib/a.dart", "lib/b.dart", RelationshipType::Import),
+            typed("lib/a.dart", "lib/b.dart", RelationshipType::Export),
+            dep("lib/b.dart", "lib/c.dart"),
+        ]);
+        let to = snapshot("b", 2, vec![
+            typed("lib/a.dart", "lib/b.dart", RelationshipType::Export),
+            typed("lib/a.dart", "lib/b.dart", RelationshipType::Import),
+            dep("lib/b.dart", "lib/c.dart"),
+            typed("lib/b.dart", "lib/c.dart", RelationshipType::Part),
+        ]);
+
+        // Reordered types are no change; an extra type on an edge is an added edge
+        let diff = diff_snapshots(&from, &to, &[]);
+        assert!(diff.retyped_edges.is_empty());
+        assert!(diff.removed_edges.is_empty());
+        assert_eq!(diff.added_edges.len(), 1);
+        assert_eq!(diff.added_edges[0].relationship_type, RelationshipType::Part);
+        // Neither snapshot has a quality score
+        assert!(diff.global_metric_deltas.architecture_quality_score.is_none());
+    }
+
+    #[test]
+    fn test_renames_keep_identity() {
+        let from = snapshot("a", 1, vec![dep("lib/a.dart", "lib/old.dart")]);
+        let to = snapshot("b", 2, vec![dep("lib/a.dart", "lib/core/new.dart")]);
+        let renames = vec![(PathBuf::from("lib/old.dart"), PathBuf::from("lib/core/new.dart"))];
+
+        let diff = diff_snapshots(&from, &to, &renames);
+        assert!(diff.added_edges.is_empty());
+        assert!(diff.removed_edges.is_empty());
+        assert!(diff.added_nodes.is_empty());
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.renamed_nodes.len(), 1);
+        assert_eq!(diff.node_metric_deltas.len(), 1);
+        assert_eq!(diff.node_metric_deltas[0].previous_path.as_deref(), Some("lib/old.dart"));
+    }
+
+    #[test]
+    fn test_cycle_changes() {
+        let from = snapshot("a", 1, vec![
+            dep("a.dart", "b.dart"),
+            dep("b.dart", "a.dart"),
+        ]);
+        let to = snapshot("b", 2, vec![
+            dep("a.dart", "b.dart"),
+            dep("b.dart", "c.dart"),
+            dep("c.dart", "b.dart"),
+        ]);
+
+        let diff = diff_snapshots(&from, &to, &[]);
+        assert_eq!(diff.introduced_cycles, vec![vec!["b.dart".to_string(), "c.dart".to_string()]]);
+        assert_eq!(diff.resolved_cycles, vec![vec!["a.dart".to_string(), "b.dart".to_string()]]);
+        assert_eq!(diff.global_metric_deltas.cycle_count.delta, 0.0);
+    }
+
+    #[test]
+    fn test_derived_global_deltas() {
+        let from = snapshot("a", 1, vec![dep("a.dart", "b.dart")]);
+        let to = snapshot("b", 2, vec![
+            dep("a.dart", "b.dart"),
+            dep("b.dart", "c.dart"),
+        ]);
+
+        let deltas = diff_snapshots(&from, &to, &[]).global_metric_deltas;
+        assert_eq!(deltas.num_nodes.delta, 1.0);
+        assert_eq!(deltas.num_edges.delta, 1.0);
+        assert!(deltas.normalized_ccd.changed());
+        assert!(deltas.avg_degree.changed());
+    }
+
+    #[test]
+    fn test_project_relative_renames() {
+        let renames = vec![
+            (PathBuf::from("app/lib/a.dart"), PathBuf::from("app/lib/b.dart")),
+            (PathBuf::from("docs/a.md"), PathBuf::from("docs/b.md")),
+        ];
+        let relative = project_relative_renames(&renames, Some("app"));
+        assert_eq!(relative, vec![(PathBuf::from("lib/a.dart"), PathBuf::from("lib/b.dart"))]);
+    }
+}
@@ -0,0 +1,333 @@
+//! Flat tables of the timeline for notebooks: edges, nodes, commits and issues,
+//! one row per item per commit, written as CSV, Parquet or Arrow IPC files.
+//! Parquet and Arrow need the `parquet` feature.
+
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_view::normalize_path;
+use crate::graph_algorithms::{global_metrics_or_derived, node_metrics_or_derived};
+use anyhow::{anyhow, Context, Result};
+#[cfg(feature = "parquet")]
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray};
+#[cfg(feature = "parquet")]
+use arrow_schema::{DataType, Field, Schema};
+#[cfg(feature = "parquet")]
+use parquet::arrow::ArrowWriter;
+#[cfg(feature = "parquet")]
+use parquet::basic::Compression;
+#[cfg(feature = "parquet")]
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "parquet")]
+use std::fs::File;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "parquet")]
+use std::sync::Arc;
+
+/// The tables an export can contain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Table {
+    /// One row per dependency per commit
+    Edges,
+    /// One row per file per commit with its node metrics
+    Nodes,
+    /// One row per commit with its global metrics
+    Commits,
+    /// One row per analysis issue per commit
+    Issues,
+}
+
+impl Table {
+    pub const ALL: [Table; 4] = [Table::Edges, Table::Nodes, Table::Commits, Table::Issues];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Table::Edges => "edges",
+            Table::Nodes => "nodes",
+            Table::Commits => "commits",
+            Table::Issues => "issues",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Table> {
+        Table::ALL
+            .into_iter()
+            .find(|table| table.name() == name)
+            .ok_or_else(|| anyhow!("Unknown table: {} (expected edges, nodes, commits or issues)", name))
+    }
+}
+
+/// Values of one column; None is written as an empty CSV field or a null
+#[derive(Debug, Clone, PartialEq)]
+enum Values {
+    Int(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+    Bool(Vec<Option<bool>>),
+    Text(Vec<Option<String>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Column {
+    name: &'static str,
+    values: Values,
+}
+
+impl Column {
+    fn int<T>(name: &'static str, rows: &[T], value: impl Fn(&T) -> Option<i64>) -> Self {
+        Self { name, values: Values::Int(rows.iter().map(value).collect()) }
+    }
+
+    fn float<T>(name: &'static str, rows: &[T], value: impl Fn(&T) -> Option<f64>) -> Self {
+        Self { name, values: Values::Float(rows.iter().map(value).collect()) }
+    }
+
+    fn bool<T>(name: &'static str, rows: &[T], value: impl Fn(&T) -> Option<bool>) -> Self {
+        Self { name, values: Values::Bool(rows.iter().map(value).collect()) }
+    }
+
+    fn text<T>(name: &'static str, rows: &[T], value: impl Fn(&T) -> Option<String>) -> Self {
+        Self { name, values: Values::Text(rows.iter().map(value).collect()) }
+    }
+
+    fn len(&self) -> usize {
+        match &self.values {
+            Values::Int(v) => v.len(),
+            Values::Float(v) => v.len(),
+            Values::Bool(v) => v.len(),
+            Values::Text(v) => v.len(),
+        }
+    }
+
+    fn cell(&self, row: usize) -> String {
+        match &self.values {
+            Values::Int(v) => v[row].map(|x| x.to_string()),
+            Values::Float(v) => v[row].map(|x| x.to_string()),
+            Values::Bool(v) => v[row].map(|x| x.to_string()),
+            Values::Text(v) => v[row].clone(),
+        }
+        .unwrap_or_default()
+    }
+
+    #[cfg(feature = "parquet")]
+    fn field(&self) -> Field {
+        let data_type = match self.values {
+            Values::Int(_) => DataType::Int64,
+            Values::Float(_) => DataType::Float64,
+            Values::Bool(_) => DataType::Boolean,
+            Values::Text(_) => DataType::Utf8,
+        };
+        Field::new(self.name, data_type, true)
+    }
+
+    #[cfg(feature = "parquet")]
+    fn array(&self) -> ArrayRef {
+        match &self.values {
+            Values::Int(v) => Arc::new(Int64Array::from(v.clone())),
+            Values::Float(v) => Arc::new(Float64Array::from(v.clone())),
+            Values::Bool(v) => Arc::new(BooleanArray::from(v.clone())),
+            Values::Text(v) => Arc::new(StringArray::from(v.clone())),
+        }
+    }
+}
+
+fn hash(snapshot: &CommitSnapshot) -> Option<String> {
+    Some(snapshot.commit_info.hash.clone())
+}
+
+fn edges(snapshots: &[CommitSnapshot]) -> Vec<Column> {
+    let rows: Vec<_> = snapshots
+        .iter()
+        .flat_map(|s| s.analysis_result.dependencies.iter().map(move |d| (s, d)))
+        .collect();
+    vec![
+        Column::text("commit_hash", &rows, |(s, _)| hash(s)),
+        Column::text("source", &rows, |(_, d)| Some(normalize_path(&d.source_file))),
+        Column::text("target", &rows, |(_, d)| Some(normalize_path(&d.target_file))),
+        Column::text("relationship_type", &rows, |(_, d)| Some(d.relationship_type.name().to_string())),
+        Column::float("weight", &rows, |(_, d)| Some(d.weight.as_normalized_float())),
+        Column::int("line_number", &rows, |(_, d)| d.line_number.map(i64::from)),
+    ]
+}
+
+fn nodes(snapshots: &[CommitSnapshot]) -> Vec<Column> {
+    let rows: Vec<_> = snapshots
+        .iter()
+        .flat_map(|s| {
+            let mut metrics: Vec<_> = node_metrics_or_derived(&s.analysis_result).into_iter().collect();
+            metrics.sort_by(|a, b| a.0.cmp(&b.0));
+            metrics.into_iter().map(move |(path, m)| (s, path, m))
+        })
+        .collect();
+    vec![
+        Column::text("commit_hash", &rows, |(s, _, _)| hash(s)),
+        Column::text("path", &rows, |(_, path, _)| Some(path.clone())),
+        Column::int("component_dependency", &rows, |(_, _, m)| Some(m.component_dependency.into())),
+        Column::int("in_degree", &rows, |(_, _, m)| Some(m.in_degree.into())),
+        Column::int("out_degree", &rows, |(_, _, m)| Some(m.out_degree.into())),
+        Column::float("instability", &rows, |(_, _, m)| Some(m.instability)),
+        Column::int("sloc", &rows, |(_, _, m)| Some(m.sloc.into())),
+        Column::bool("is_orphan", &rows, |(_, _, m)| Some(m.is_orphan)),
+        Column::bool("in_cycle", &rows, |(_, _, m)| Some(m.in_cycle)),
+        Column::int("cycle_id", &rows, |(_, _, m)| m.cycle_id.map(i64::from)),
+        Column::int("abstract_types", &rows, |(_, _, m)| m.abstract_types.map(i64::from)),
+        Column::int("total_types", &rows, |(_, _, m)| m.total_types.map(i64::from)),
+        Column::float("abstractness", &rows, |(_, _, m)| m.abstractness),
+        Column::float("distance", &rows, |(_, _, m)| m.distance),
+    ]
+}
+
+fn commits(snapshots: &[CommitSnapshot]) -> Vec<Column> {
+    let rows: Vec<_> = snapshots
+        .iter()
+        .map(|s| (s, global_metrics_or_derived(&s.analysis_result)))
+        .collect();
+    vec![
+        Column::text("commit_hash", &rows, |(s, _)| hash(s)),
+        Column::text("author_name", &rows, |(s, _)| Some(s.commit_info.author_name.clone())),
+        Column::text("author_email", &rows, |(s, _)| Some(s.commit_info.author_email.clone())),
+        Column::text("message", &rows, |(s, _)| Some(s.commit_info.message.clone())),
+        Column::int("timestamp", &rows, |(s, _)| Some(s.commit_info.timestamp)),
+        Column::text("merge_parent_hash", &rows, |(s, _)| s.commit_info.merge_parent_hash.clone()),
+        Column::bool("is_acyclic", &rows, |(_, g)| Some(g.is_acyclic)),
+        Column::int("num_nodes", &rows, |(_, g)| Some(g.num_nodes.into())),
+        Column::int("num_edges", &rows, |(_, g)| Some(g.num_edges.into())),
+        Column::float("avg_degree", &rows, |(_, g)| Some(g.avg_degree)),
+        Column::int("cumulative_component_dependency", &rows, |(_, g)| Some(g.cumulative_component_dependency.into())),
+        Column::float("average_component_dependency", &rows, |(_, g)| Some(g.average_component_dependency)),
+        Column::float("normalized_ccd", &rows, |(_, g)| Some(g.normalized_ccd)),
+        Column::int("total_sloc", &rows, |(_, g)| Some(g.total_sloc.into())),
+        Column::float("average_sloc", &rows, |(_, g)| Some(g.average_sloc)),
+        Column::int("cycle_count", &rows, |(_, g)| Some(g.detected_cycles.len() as i64)),
+        Column::int("orphan_count", &rows, |(_, g)| Some(g.orphan_libraries.len() as i64)),
+        Column::float("architecture_quality_score", &rows, |(s, _)| s.analysis_result.architecture_quality_score),
+    ]
+}
+
+fn issues(snapshots: &[CommitSnapshot]) -> Vec<Column> {
+    let rows: Vec<_> = snapshots
+        .iter()
+        .flat_map(|s| s.analysis_result.issues.iter().map(move |i| (s, i)))
+        .collect();
+    vec![
+        Column::text("commit_hash", &rows, |(s, _)| hash(s)),
+        Column::text("level", &rows, |(_, i)| Some(format!("{:?}", i.level))),
+        Column::text("message", &rows, |(_, i)| Some(i.message.clone())),
+        Column::text("file_path", &rows, |(_, i)| i.file_path.as_deref().map(normalize_path)),
+        Column::int("line_number", &rows, |(_, i)| i.line_number.map(i64::from)),
+        Column::text("import_statement", &rows, |(_, i)| i.import_statement.clone()),
+        Column::text("rule", &rows, |(_, i)| i.rule.clone()),
+    ]
+}
+
+fn columns(table: Table, snapshots: &[CommitSnapshot]) -> Vec<Column> {
+    match table {
+        Table::Edges => edges(snapshots),
+        Table::Nodes => nodes(snapshots),
+        Table::Commits => commits(snapshots),
+        Table::Issues => issues(snapshots),
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn record_batch(columns: &[Column]) -> Result<RecordBatch> {
+    let schema = Schema::new(columns.iter().map(Column::field).collect::<Vec<_>>());
+    Ok(RecordBatch::try_new(Arc::new(schema), columns.iter().map(Column::array).collect())?)
+}
+
+/// One table of the snapshots as CSV with a header row
+pub fn to_csv(table: Table, snapshots: &[CommitSnapshot]) -> Result<String> {
+    let columns = columns(table, snapshots);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(columns.iter().map(|c| c.name))?;
+    for row in 0..columns.first().map_or(0, Column::len) {
+        writer.write_record(columns.iter().map(|c| c.cell(row)))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// Write `tables` into `dir` as `<table>.csv`, `<table>.parquet` or `<table>.arrow`
+/// depending on `format`, returning the written files
+pub fn write_tables(
+    snapshots: &[CommitSnapshot],
+    format: &str,
+    tables: &[Table],
+    dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut written = Vec::new();
+    for &table in tables {
+        let path = dir.join(format!("{}.{}", table.name(), format));
+        match format {
+            "csv" => std::fs::write(&path, to_csv(table, snapshots)?)?,
+            #[cfg(feature = "parquet")]
+            "parquet" => {
+                let batch = record_batch(&columns(table, snapshots))?;
+                let properties = WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+                let mut writer = ArrowWriter::try_new(File::create(&path)?, batch.schema(), Some(properties))?;
+                writer.write(&batch)?;
+                writer.close()?;
+            }
+            #[cfg(feature = "parquet")]
+            "arrow" => {
+                let batch = record_batch(&columns(table, snapshots))?;
+                let mut writer = arrow_ipc::writer::FileWriter::try_new(File::create(&path)?, &batch.schema())?;
+                writer.write(&batch)?;
+                writer.finish()?;
+            }
+            #[cfg(not(feature = "parquet"))]
+            "parquet" | "arrow" => {
+                return Err(anyhow!("{} export needs chronograph-core built with the parquet feature", format))
+            }
+            _ => return Err(anyhow!("Unsupported table format: {}", format)),
+        }
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{dep, snapshot};
+
+    #[test]
+    fn test_table_exports() {
+        let snapshots = vec![
+            snapshot("c1", 100, vec![dep("lib/a.dart", "lib/b.dart")]),
+            snapshot("c2", 200, vec![dep("lib/a.dart", "lib/b.dart"), dep("lib/b.dart", "lib/c.dart")]),
+        ];
+
+        let csv = to_csv(Table::Edges, &snapshots).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "commit_hash,source,target,relationship_type,weight,line_number");
+        assert_eq!(lines[3], "c2,lib/b.dart,lib/c.dart,import,1,");
+        assert_eq!(lines.len(), 4);
+
+        let nodes = to_csv(Table::Nodes, &snapshots).unwrap();
+        assert!(nodes.lines().any(|l| l.starts_with("c2,lib/a.dart,3,0,1,1,")));
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_tables(&snapshots, "csv", &Table::ALL, dir.path()).unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(dir.path().join("issues.csv").exists());
+
+        assert!(Table::parse("files").is_err());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_export() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let snapshots = vec![
+            snapshot("c1", 100, vec![dep("lib/a.dart", "lib/b.dart")]),
+            snapshot("c2", 200, vec![dep("lib/b.dart", "lib/c.dart")]),
+        ];
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_tables(&snapshots, "parquet", &Table::ALL, dir.path()).unwrap();
+        assert_eq!(written.len(), 4);
+        let reader = SerializedFileReader::new(File::open(dir.path().join("commits.parquet")).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+    }
+}
@@ -0,0 +1,166 @@
+//! Builders for snapshots and commit histories used across unit tests
+
+use crate::chronograph_engine::CommitSnapshot;
+use crate::dependency_analyzer::*;
+use crate::git_navigator::{CommitChanges, CommitInfo, FileChange, FileChangeKind};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Binary import dependency between two files
+pub fn dep(source: &str, target: &str) -> RawDependency {
+    RawDependency {
+        source_file: PathBuf::from(source),
+        target_file: PathBuf::from(target),
+        relationship_type: RelationshipType::Import,
+        weight: DependencyWeight::Binary(true),
+        line_number: None,
+        import_statement: None,
+        symbols: Vec::new(),
+        metadata: HashMap::new(),
+    }
+}
+
+/// Commit metadata authored by `author` at `timestamp`
+pub fn commit(hash: &str, author: &str, timestamp: i64) -> CommitInfo {
+    CommitInfo {
+        hash: hash.to_string(),
+        author_name: author.to_string(),
+        author_email: format!("{}@example.com", author),
+        message: format!("Commit {}", hash),
+        timestamp,
+        merge_parent_hash: None,
+    }
+}
+
+/// Change of one file with `lines` added
+pub fn file_change(path: &str, kind: FileChangeKind, lines: usize) -> FileChange {
+    FileChange {
+        path: PathBuf::from(path),
+        old_path: None,
+        kind,
+        lines_added: lines,
+        lines_removed: 0,
+    }
+}
+
+/// Modification of one file with `lines` added
+pub fn modified(path: &str, lines: usize) -> FileChange {
+    file_change(path, FileChangeKind::Modified, lines)
+}
+
+/// Pure rename of a file
+pub fn renamed(from: &str, to: &str) -> FileChange {
+    FileChange {
+        old_path: Some(PathBuf::from(from)),
+        ..file_change(to, FileChangeKind::Renamed, 0)
+    }
+}
+
+/// Commit by `author` at `timestamp` with the given file changes
+pub fn commit_changes(hash: &str, author: &str, timestamp: i64, changes: Vec<FileChange>) -> CommitChanges {
+    CommitChanges {
+        commit: commit(hash, author, timestamp),
+        changes,
+    }
+}
+
+/// Analysis result whose analyzed files are the endpoints of `deps`
+pub fn analysis_result(deps: Vec<RawDependency>) -> AnalysisResult {
+    let mut files: Vec<PathBuf> = deps
+        .iter()
+        .flat_map(|d| vec![d.source_file.clone(), d.target_file.clone()])
+        .collect();
+    files.sort();
+    files.dedup();
+
+    AnalysisResult {
+        dependencies: deps,
+        enhanced_dependencies: None,
+        global_metrics: None,
+        node_metrics: None,
+        architecture_quality_score: None,
+        quality_breakdown: None,
+        analyzer_name: "test".to_string(),
+        analyzer_version: "1.0.0".to_string(),
+        analysis_timestamp: 0,
+        project_path: PathBuf::from("/project"),
+        analyzed_files: files,
+        skipped_files: Vec::new(),
+        metrics: AnalysisMetrics {
+            total_files_found: 0,
+            files_analyzed: 0,
+            files_skipped: 0,
+            dependencies_found: 0,
+            analysis_duration_ms: 0,
+        },
+        issues: Vec::new(),
+    }
+}
+
+/// Snapshot at `timestamp` by "alice" containing `deps`
+pub fn snapshot(hash: &str, timestamp: i64, deps: Vec<RawDependency>) -> CommitSnapshot {
+    CommitSnapshot {
+        commit_info: commit(hash, "alice", timestamp),
+        analysis_result: analysis_result(deps),
+        project_path: PathBuf::from("/project"),
+        regressions: Vec::new(),
+    }
+}
+
+/// Scratch git repository for navigator tests
+pub struct TestRepo {
+    pub dir: tempfile::TempDir,
+    pub repo: git2::Repository,
+}
+
+impl TestRepo {
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let repo = git2::Repository::init(dir.path()).expect("init repo");
+        Self { dir, repo }
+    }
+
+    /// Commit a set of file writes (`Some(content)`) and deletions (`None`)
+    /// on top of HEAD and return the new commit hash
+    pub fn commit(&self, changes: &[(&str, Option<&str>)], author: &str, time: i64) -> String {
+        let mut index = self.repo.index().expect("index");
+        for (path, content) in changes {
+            let full_path = self.dir.path().join(path);
+            match content {
+                Some(content) => {
+                    std::fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+                    std::fs::write(&full_path, content).unwrap();
+                    index.add_path(std::path::Path::new(path)).unwrap();
+                }
+                None => {
+                    let _ = std::fs::remove_file(&full_path);
+                    index.remove_path(std::path::Path::new(path)).unwrap();
+                }
+            }
+        }
+        index.write().unwrap();
+
+        let tree_id = index.write_tree().unwrap();
+        let tree = self.repo.find_tree(tree_id).unwrap();
+        let signature = git2::Signature::new(
+            author,
+            &format!("{}@example.com", author),
+            &git2::Time::new(time, 0),
+        ).unwrap();
+        let parent = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+        self.repo
+            .commit(Some("HEAD"), &signature, &signature, &format!("change at {}", time), &tree, &parents)
+            .unwrap()
+            .to_string()
+    }
+
+    /// Clone the repository through the navigator like a local analysis would
+    pub fn navigator(&self, base_dir: &std::path::Path) -> crate::git_navigator::GitTemporalNavigator {
+        crate::git_navigator::GitTemporalNavigator::clone_local_repository(
+            self.dir.path().to_str().unwrap(),
+            base_dir,
+        ).expect("clone test repository")
+    }
+}
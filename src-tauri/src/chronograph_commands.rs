@@ -1,16 +1,14 @@
-use crate::chronograph_engine::{ChronoGraphEngine, ChronoGraphConfig, AnalysisProgress, CommitSnapshot};
-use crate::lakos_analyzer::LakosAnalyzer;
-use crate::analysis_cache::CacheStatistics;
+use chronograph_core::chronograph_engine::{ChronoGraphEngine, ChronoGraphConfig, AnalysisProgress, CommitSnapshot};
+use chronograph_core::lakos_analyzer::LakosAnalyzer;
+use chronograph_core::analysis_cache::CacheStatistics;
+use chronograph_core::analysis_export::ExportOptions;
+use chronograph_core::api;
 // Removed unused PathBuf import
 use tauri::State;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use anyhow::Result;
 
-/// Shared state for ChronoGraph engine
-pub type ChronoGraphState = Arc<Mutex<Option<ChronoGraphEngine>>>;
-
-/// Progress callback state for analysis updates
-pub type ProgressState = Arc<Mutex<Option<AnalysisProgress>>>;
+pub use chronograph_core::api::{ChronoGraphState, ProgressState};
 
 /// Initialize ChronoGraph analysis
 #[tauri::command]
@@ -19,37 +17,10 @@ pub async fn initialize_analysis(
     config_options: Option<serde_json::Value>,
     state: State<'_, ChronoGraphState>,
 ) -> Result<String, String> {
-    println!("Initializing ChronoGraph analysis for: {}", github_url);
+    log::info!("Initializing ChronoGraph analysis for: {}", github_url);
     
     // Create configuration
-    let mut config = ChronoGraphConfig::default();
-    config.github_url = github_url.clone();
-    
-    // Apply custom configuration if provided
-    if let Some(options) = config_options {
-        if let Some(sampling) = options.get("commit_sampling").and_then(|v| v.as_u64()) {
-            config.commit_sampling = sampling as usize;
-        }
-        if let Some(max_commits) = options.get("max_commits").and_then(|v| v.as_u64()) {
-            config.max_commits = Some(max_commits as usize);
-        }
-        if let Some(analyzer) = options.get("analyzer").and_then(|v| v.as_str()) {
-            config.analyzer_name = analyzer.to_string();
-        }
-        if let Some(subfolder) = options.get("subfolder").and_then(|v| v.as_str()) {
-            // Normalize path separators - convert backslashes to forward slashes
-            let normalized_subfolder = subfolder.replace('\\', "/");
-            config.subfolder = Some(normalized_subfolder);
-        }
-        if let Some(is_local) = options.get("is_local_path").and_then(|v| v.as_bool()) {
-            config.is_local_repository = is_local;
-        }
-    }
-    
-    // Check if Lakos is available
-    if config.analyzer_name == "lakos" && !LakosAnalyzer::is_available() {
-        return Err("Lakos analyzer is not installed. Please run: dart pub global activate lakos".to_string());
-    }
+    let config = api::config_from_options(&github_url, config_options.as_ref())?;
     
     // Create engine
     let engine = ChronoGraphEngine::new(config);
@@ -67,43 +38,14 @@ pub async fn start_analysis(
     state: State<'_, ChronoGraphState>,
     progress_state: State<'_, ProgressState>,
 ) -> Result<Vec<CommitSnapshot>, String> {
-    println!("Starting ChronoGraph analysis...");
-    
-    // Extract engine from state
-    let mut engine = {
-        let mut state_guard = state.lock().map_err(|e| e.to_string())?;
-        state_guard.take().ok_or("No analysis initialized")?
-    }; // MutexGuard is dropped here
+    log::info!("Starting ChronoGraph analysis...");
     
     // Run analysis with progress callback in a blocking task
-    let progress_state_clone = Arc::clone(&progress_state);
-    let result = tokio::task::spawn_blocking(move || {
-        let snapshots = engine.analyze_repository(|progress| {
-            // Update progress state
-            if let Ok(mut progress_guard) = progress_state_clone.lock() {
-                *progress_guard = Some(progress);
-            }
-        });
-        (engine, snapshots)
-    }).await.map_err(|e| e.to_string())?;
-    
-    // Handle result and store engine back
-    let snapshots = match result {
-        (engine_back, Ok(snapshots)) => {
-            // Store engine back for future queries
-            let mut state_guard = state.lock().map_err(|e| e.to_string())?;
-            *state_guard = Some(engine_back);
-            snapshots
-        }
-        (engine_back, Err(e)) => {
-            // Still store engine back even if analysis failed
-            let mut state_guard = state.lock().map_err(|e| e.to_string())?;
-            *state_guard = Some(engine_back);
-            return Err(e.to_string());
-        }
-    };
-    
-    Ok(snapshots)
+    let state = Arc::clone(&state);
+    let progress_state = Arc::clone(&progress_state);
+    tokio::task::spawn_blocking(move || api::run_analysis(&state, &progress_state))
+        .await
+        .map_err(|e| e.to_string())?
 }
 
 /// Get current analysis progress
@@ -132,7 +74,7 @@ pub async fn get_analysis_snapshots(
 #[tauri::command]
 pub async fn get_repository_info(
     state: State<'_, ChronoGraphState>,
-) -> Result<Option<crate::git_navigator::RepoCloneInfo>, String> {
+) -> Result<Option<chronograph_core::git_navigator::RepoCloneInfo>, String> {
     let state_guard = state.lock().map_err(|e| e.to_string())?;
     
     match state_guard.as_ref() {
@@ -145,7 +87,7 @@ pub async fn get_repository_info(
 #[tauri::command]
 pub async fn get_analysis_statistics(
     state: State<'_, ChronoGraphState>,
-) -> Result<crate::chronograph_engine::AnalysisStatistics, String> {
+) -> Result<chronograph_core::chronograph_engine::AnalysisStatistics, String> {
     let state_guard = state.lock().map_err(|e| e.to_string())?;
     
     match state_guard.as_ref() {
@@ -158,14 +100,14 @@ pub async fn get_analysis_statistics(
 #[tauri::command]
 pub async fn list_analyzers(
     state: State<'_, ChronoGraphState>,
-) -> Result<Vec<crate::dependency_analyzer::AnalyzerInfo>, String> {
+) -> Result<Vec<chronograph_core::dependency_analyzer::AnalyzerInfo>, String> {
     let state_guard = state.lock().map_err(|e| e.to_string())?;
     
     match state_guard.as_ref() {
         Some(engine) => Ok(engine.list_analyzers()),
         None => {
             // Return default analyzer list if no engine is initialized
-            let mut registry = crate::dependency_analyzer::AnalyzerRegistry::new();
+            let mut registry = chronograph_core::dependency_analyzer::AnalyzerRegistry::new();
             registry.register(Box::new(LakosAnalyzer::new()));
             Ok(registry.list_analyzers())
         }
@@ -175,7 +117,7 @@ pub async fn list_analyzers(
 /// Install Lakos analyzer
 #[tauri::command]
 pub async fn install_lakos() -> Result<String, String> {
-    println!("Installing Lakos analyzer...");
+    log::info!("Installing Lakos analyzer...");
     
     LakosAnalyzer::install()
         .map_err(|e| e.to_string())?;
@@ -194,21 +136,12 @@ pub async fn check_lakos_availability() -> Result<bool, String> {
 pub async fn get_commit_dependencies(
     commit_hash: String,
     state: State<'_, ChronoGraphState>,
-) -> Result<Option<Vec<crate::dependency_analyzer::RawDependency>>, String> {
+) -> Result<Option<Vec<chronograph_core::dependency_analyzer::RawDependency>>, String> {
     let state_guard = state.lock().map_err(|e| e.to_string())?;
     
-    if let Some(engine) = state_guard.as_ref() {
-        let snapshot = engine.get_snapshots()
-            .iter()
-            .find(|s| s.commit_info.hash == commit_hash);
-            
-        if let Some(snapshot) = snapshot {
-            Ok(Some(snapshot.analysis_result.dependencies.clone()))
-        } else {
-            Ok(None)
-        }
-    } else {
-        Err("No analysis available".to_string())
+    match state_guard.as_ref() {
+        Some(engine) => Ok(api::commit_dependencies(engine, &commit_hash)),
+        None => Err("No analysis available".to_string()),
     }
 }
 
@@ -217,7 +150,7 @@ pub async fn get_commit_dependencies(
 pub async fn get_commit_info(
     commit_hash: String,
     state: State<'_, ChronoGraphState>,
-) -> Result<Option<crate::git_navigator::CommitInfo>, String> {
+) -> Result<Option<chronograph_core::git_navigator::CommitInfo>, String> {
     let state_guard = state.lock().map_err(|e| e.to_string())?;
     
     if let Some(engine) = state_guard.as_ref() {
@@ -235,6 +168,455 @@ pub async fn get_commit_info(
     }
 }
 
+/// Diff two analyzed snapshots (they need not be adjacent)
+#[tauri::command]
+pub async fn diff_snapshots(
+    from_commit: String,
+    to_commit: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::snapshot_diff::SnapshotDiff, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.diff_snapshots(&from_commit, &to_commit).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Files that directly or transitively import the given files or folders at a
+/// commit (latest by default)
+#[tauri::command]
+pub async fn get_impact(
+    paths: Vec<String>,
+    commit_hash: Option<String>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::impact::ImpactReport, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.impact(commit_hash.as_deref(), &paths).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Lifecycle of analyzed dependencies, optionally narrowed to a source and/or target file
+#[tauri::command]
+pub async fn get_dependency_lifecycles(
+    source_file: Option<String>,
+    target_file: Option<String>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::models::TemporalFileDependency>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => {
+            let source = source_file.map(|f| chronograph_core::dependency_view::normalize_path(f.as_ref()));
+            let target = target_file.map(|f| chronograph_core::dependency_view::normalize_path(f.as_ref()));
+            let lifecycles = engine
+                .get_dependency_timeline()
+                .entries()
+                .iter()
+                .filter(|e| {
+                    source.as_ref().is_none_or(|s| *s == chronograph_core::dependency_view::normalize_path(&e.dependency.source_file))
+                        && target.as_ref().is_none_or(|t| *t == chronograph_core::dependency_view::normalize_path(&e.dependency.target_file))
+                })
+                .cloned()
+                .collect();
+            Ok(lifecycles)
+        }
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Metrics and file lifecycle (age, churn, primary authors) of a file or folder node
+#[tauri::command]
+pub async fn get_node_details(
+    node_path: String,
+    commit_hash: Option<String>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::chronograph_engine::NodeDetails, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine
+            .node_details(&node_path, commit_hash.as_deref())
+            .map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Files added, modified, deleted and renamed since the previous analyzed snapshot
+#[tauri::command]
+pub async fn get_snapshot_file_changes(
+    commit_hash: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Option<chronograph_core::models::FileChangeSet>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => {
+            let snapshot = engine.find_snapshot(&commit_hash)
+                .ok_or_else(|| format!("No analyzed snapshot for commit {}", commit_hash))?;
+            Ok(engine.get_snapshot_changes(&snapshot.commit_info.hash).cloned())
+        }
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Metric history of a file across snapshots, continuous through renames and moves
+#[tauri::command]
+pub async fn get_node_metric_history(
+    node_path: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::node_identity::NodeMetricPoint>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => Ok(engine.node_metric_history(&node_path)),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Files that change together in git history, with hidden and dead coupling
+/// relative to the static import graph. Unset thresholds use the defaults.
+#[tauri::command]
+pub async fn get_change_coupling(
+    min_support: Option<usize>,
+    min_confidence: Option<f64>,
+    max_files_per_commit: Option<usize>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::change_coupling::CouplingReport, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => {
+            let defaults = chronograph_core::change_coupling::CouplingConfig::default();
+            let config = chronograph_core::change_coupling::CouplingConfig {
+                min_support: min_support.unwrap_or(defaults.min_support),
+                min_confidence: min_confidence.unwrap_or(defaults.min_confidence),
+                max_files_per_commit: max_files_per_commit.unwrap_or(defaults.max_files_per_commit),
+                ..defaults
+            };
+            Ok(engine.change_coupling(&config))
+        }
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Files where change pressure overlaps with size and centrality, highest score first
+#[tauri::command]
+pub async fn get_hotspots(
+    commit_hash: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::hotspots::HotspotReport, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.hotspots(commit_hash.as_deref(), limit).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Hotspot rankings for every analyzed commit
+#[tauri::command]
+pub async fn get_hotspot_timeline(
+    limit: Option<usize>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::hotspots::HotspotReport>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => Ok(engine.hotspot_timeline(limit)),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// PageRank, betweenness and community of every file in a snapshot, with the files
+/// whose community is rooted in another folder
+#[tauri::command]
+pub async fn get_modularity(
+    commit_hash: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::modularity::ModularityReport, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.modularity(&commit_hash).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Modularity reports for every analyzed commit, oldest first
+#[tauri::command]
+pub async fn get_modularity_timeline(
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::modularity::ModularityReport>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => Ok(engine.modularity_timeline()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Ownership and bus factor per file and folder, plus imports that cross team
+/// boundaries (from CODEOWNERS, or the top author per file without one)
+#[tauri::command]
+pub async fn get_ownership(
+    commit_hash: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::ownership::OwnershipReport, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.ownership(&commit_hash).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Lifecycle of every dependency cycle: when it appeared, how it grew and what broke it
+#[tauri::command]
+pub async fn get_cycle_history(
+    active_only: Option<bool>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::cycle_tracker::TrackedCycle>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => Ok(engine
+            .get_cycle_history()
+            .iter()
+            .filter(|c| !active_only.unwrap_or(false) || c.is_active())
+            .cloned()
+            .collect()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Find the exact commit between two analyzed snapshots that introduced (or removed)
+/// an edge or a cycle. Pass `source_file` and `target_file` for an edge, or
+/// `cycle_members` for a cycle.
+#[tauri::command]
+pub async fn bisect_dependency(
+    from_commit: String,
+    to_commit: String,
+    source_file: Option<String>,
+    target_file: Option<String>,
+    cycle_members: Option<Vec<String>>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::bisect::BisectResult, String> {
+    let target = match (source_file, target_file, cycle_members) {
+        (Some(source), Some(target), None) => chronograph_core::bisect::BisectTarget::Edge { source, target },
+        (None, None, Some(members)) if !members.is_empty() => chronograph_core::bisect::BisectTarget::Cycle { members },
+        _ => return Err("Specify either source_file and target_file, or cycle_members".to_string()),
+    };
+
+    // Bisecting checks out and analyzes commits in its own clone, so the analysis
+    // stays available to other commands and other bisects while it runs
+    let job = {
+        let state_guard = state.lock().map_err(|e| e.to_string())?;
+        let engine = state_guard.as_ref().ok_or("No analysis available")?;
+        engine.prepare_bisect(target, &from_commit, &to_commit).map_err(|e| e.to_string())?
+    };
+
+    tokio::task::spawn_blocking(move || job.run())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Time series of node metrics for a file or folder across all snapshots
+#[tauri::command]
+pub async fn get_metric_series(
+    node_path: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::metric_series::MetricTimeSeries, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => Ok(engine.metric_series(&node_path)),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Abstractness, instability and distance from the main sequence per file, or per
+/// folder when `folders` is set
+#[tauri::command]
+pub async fn get_package_metrics(
+    commit_hash: String,
+    folders: Option<bool>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::package_metrics::PackageMetrics>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine
+            .package_metrics(&commit_hash, folders.unwrap_or(false))
+            .map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Time series of the global architectural metrics across all snapshots
+#[tauri::command]
+pub async fn get_global_metric_series(
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::metric_series::MetricTimeSeries, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => Ok(engine.global_metric_series()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Architecture regressions of one snapshot, or of every snapshot when no commit is given
+#[tauri::command]
+pub async fn get_regressions(
+    commit_hash: Option<String>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::regressions::RegressionEvent>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.get_regressions(commit_hash.as_deref()).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Change the regression alert thresholds and re-evaluate the analyzed snapshots
+#[tauri::command]
+pub async fn update_regression_thresholds(
+    thresholds: chronograph_core::regressions::RegressionThresholds,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::regressions::RegressionEvent>, String> {
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_mut() {
+        Some(engine) => {
+            engine.set_regression_thresholds(thresholds);
+            engine.get_regressions(None).map_err(|e| e.to_string())
+        }
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Per-factor breakdown of the architecture quality score of a snapshot
+#[tauri::command]
+pub async fn get_quality_breakdown(
+    commit_hash: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::quality_score::QualityScoreBreakdown, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.quality_breakdown(&commit_hash).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Change the quality scoring model (a preset plus overrides) and rescore all snapshots
+#[tauri::command]
+pub async fn update_quality_model(
+    model: serde_json::Value,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::quality_score::QualityScoreModel, String> {
+    let model = chronograph_core::quality_score::QualityScoreModel::from_config(&model).map_err(|e| e.to_string())?;
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_mut() {
+        Some(engine) => {
+            engine.set_quality_model(model.clone());
+            Ok(model)
+        }
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Architecture rule violations of a snapshot, with file, line and import statement
+#[tauri::command]
+pub async fn get_rule_violations(
+    commit_hash: String,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::dependency_analyzer::AnalysisIssue>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => engine.rule_violations(&commit_hash).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Rule violation count per analyzed commit, to see when rules started being broken
+#[tauri::command]
+pub async fn get_rule_violation_timeline(
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::architecture_rules::RuleViolationCount>, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_ref() {
+        Some(engine) => Ok(engine.rule_violation_timeline()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Replace the architecture rules and re-evaluate every snapshot
+#[tauri::command]
+pub async fn update_architecture_rules(
+    rules: chronograph_core::architecture_rules::RuleSet,
+    state: State<'_, ChronoGraphState>,
+) -> Result<Vec<chronograph_core::architecture_rules::RuleViolationCount>, String> {
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_mut() {
+        Some(engine) => {
+            engine.set_architecture_rules(rules).map_err(|e| e.to_string())?;
+            Ok(engine.rule_violation_timeline())
+        }
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// Accept the current violations, cycles and metrics of a commit (latest by
+/// default) as the baseline and write it to the repository
+#[tauri::command]
+pub async fn record_baseline(
+    commit_hash: Option<String>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::baseline::Baseline, String> {
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_mut() {
+        Some(engine) => engine.record_baseline(commit_hash.as_deref()).map_err(|e| e.to_string()),
+        None => Err("No analysis available".to_string()),
+    }
+}
+
+/// What got worse (and better) in a commit (latest by default) relative to the
+/// baseline. With `tighten` (or the ratchet option) improvements are accepted into
+/// the baseline.
+#[tauri::command]
+pub async fn get_baseline_comparison(
+    commit_hash: Option<String>,
+    tighten: Option<bool>,
+    state: State<'_, ChronoGraphState>,
+) -> Result<chronograph_core::baseline::BaselineComparison, String> {
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    match state_guard.as_mut() {
+        Some(engine) => {
+            let comparison = engine.compare_with_baseline(commit_hash.as_deref())
+                .map_err(|e| e.to_string())?;
+            if tighten.unwrap_or(engine.get_config().ratchet_baseline) && comparison.improved() {
+                engine.tighten_baseline(commit_hash.as_deref()).map_err(|e| e.to_string())?;
+            }
+            Ok(comparison)
+        }
+        None => Err("No analysis available".to_string()),
+    }
+}
+
 /// Cleanup analysis resources
 #[tauri::command]
 pub async fn cleanup_analysis(
@@ -264,23 +646,18 @@ pub async fn get_analysis_config(
     }
 }
 
-/// Export analysis results to JSON
+/// Export analysis results as JSON, graph files, diagrams, an HTML report, SARIF or CSV/Parquet/Arrow tables
 #[tauri::command]
 pub async fn export_analysis_results(
-    format: String, // "json", "csv", etc.
+    format: String, // "json", "graphml", "gexf", "dot", "mermaid", "plantuml", "structurizr", "html", "sarif", "csv", "parquet" or "arrow"
+    options: Option<ExportOptions>,
     state: State<'_, ChronoGraphState>,
 ) -> Result<String, String> {
     let state_guard = state.lock().map_err(|e| e.to_string())?;
     
     if let Some(engine) = state_guard.as_ref() {
-        match format.as_str() {
-            "json" => {
-                let snapshots = engine.get_snapshots();
-                serde_json::to_string_pretty(snapshots)
-                    .map_err(|e| e.to_string())
-            }
-            _ => Err(format!("Unsupported export format: {}", format))
-        }
+        let options = options.unwrap_or_default();
+        chronograph_core::analysis_export::export(engine, &format, &options).map_err(|e| e.to_string())
     } else {
         Err("No analysis available to export".to_string())
     }
@@ -340,7 +717,7 @@ pub async fn get_cached_repositories() -> Result<Vec<CachedRepository>, String>
 
 /// Analyze a cached repository to extract information
 async fn analyze_cached_repo(repo_path: &std::path::Path) -> Result<CachedRepository, String> {
-    use crate::git_navigator::GitTemporalNavigator;
+    use chronograph_core::git_navigator::GitTemporalNavigator;
     use std::fs;
     
     // Extract repository name and reconstruct URL
@@ -433,7 +810,7 @@ pub async fn cleanup_cached_repository(repo_name: String) -> Result<(), String>
 /// Clean up all cached repositories
 #[tauri::command]
 pub async fn cleanup_all_cached_repositories() -> Result<(), String> {
-    use crate::git_navigator::GitTemporalNavigator;
+    use chronograph_core::git_navigator::GitTemporalNavigator;
     use std::path::PathBuf;
     
     let cache_dir = PathBuf::from("/tmp/chronograph");
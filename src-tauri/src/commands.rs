@@ -1,5 +1,30 @@
-use crate::models::*;
+use chronograph_core::models::*;
+use crate::chronograph_commands::ChronoGraphState;
+use chronograph_core::dependency_view;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+/// Per-project dependency view and the commit it is pinned to
+#[derive(Debug, Clone)]
+pub struct DependencyViewSession {
+    pub view: DependencyView,
+    /// Snapshot the view is built from; `None` follows the latest snapshot
+    pub commit_hash: Option<String>,
+}
+
+impl Default for DependencyViewSession {
+    fn default() -> Self {
+        Self {
+            view: DependencyView::new(2),
+            commit_hash: None,
+        }
+    }
+}
+
+/// Dependency view sessions keyed by project path
+pub type DependencyViewState = Arc<Mutex<HashMap<String, DependencyViewSession>>>;
 
 /// Command to initialize analysis of a project repository
 #[tauri::command]
@@ -61,34 +86,17 @@ pub async fn get_project_config(
 pub async fn get_dependency_view(
     project_path: String,
     folder_depth: Option<usize>,
+    state: State<'_, ChronoGraphState>,
+    view_state: State<'_, DependencyViewState>,
 ) -> Result<DependencyView, String> {
-    let _path = PathBuf::from(project_path);
-    
-    // Create placeholder dependency view
-    let view = DependencyView {
-        folder_depth: folder_depth.unwrap_or(2),
-        expanded_folders: std::collections::HashSet::new(),
-        visible_dependencies: Vec::new(),
-        layout_state: LayoutState {
-            algorithm: LayoutAlgorithm::Hybrid,
-            viewport: Viewport {
-                x: 0.0,
-                y: 0.0,
-                width: 1200.0,
-                height: 800.0,
-            },
-            zoom_level: 1.0,
-        },
-        filter_criteria: FilterCriteria {
-            min_dependency_strength: 0.1,
-            show_external_deps: false,
-            author_filter: None,
-            time_range: None,
-            node_types: std::collections::HashSet::new(),
-        },
-    };
-    
-    Ok(view)
+    let mut sessions = view_state.lock().map_err(|e| e.to_string())?;
+    let session = sessions.entry(project_path).or_default();
+
+    if let Some(depth) = folder_depth {
+        session.view.folder_depth = depth;
+    }
+
+    refresh_view(session, &state)
 }
 
 /// Command to get temporal snapshots for timeline navigation
@@ -106,13 +114,29 @@ pub async fn get_temporal_snapshots(
 #[tauri::command]
 pub async fn navigate_to_timestamp(
     project_path: String,
-    timestamp: String, // Changed to String for now
+    timestamp: String, // Unix seconds or RFC 3339
+    state: State<'_, ChronoGraphState>,
+    view_state: State<'_, DependencyViewState>,
 ) -> Result<DependencyView, String> {
-    let _path = PathBuf::from(project_path.clone());
-    let _target_time = timestamp;
-    
-    // TODO: Implement temporal navigation
-    get_dependency_view(project_path, None).await
+    let filters = {
+        let mut sessions = view_state.lock().map_err(|e| e.to_string())?;
+        sessions.entry(project_path.clone()).or_default().view.filter_criteria.clone()
+    };
+
+    let target_hash = {
+        let state_guard = state.lock().map_err(|e| e.to_string())?;
+        let engine = state_guard.as_ref().ok_or("No analysis available")?;
+
+        dependency_view::snapshot_at_timestamp(engine.get_snapshots(), &timestamp, &filters)
+            .map(|s| s.commit_info.hash.clone())
+            .ok_or_else(|| format!("No analyzed commit at or before {}", timestamp))?
+    };
+
+    let mut sessions = view_state.lock().map_err(|e| e.to_string())?;
+    let session = sessions.entry(project_path).or_default();
+    session.commit_hash = Some(target_hash);
+
+    refresh_view(session, &state)
 }
 
 /// Command to expand/collapse folder in view
@@ -121,13 +145,14 @@ pub async fn toggle_folder_expansion(
     project_path: String,
     folder_path: String,
     expand: bool,
+    state: State<'_, ChronoGraphState>,
+    view_state: State<'_, DependencyViewState>,
 ) -> Result<DependencyView, String> {
-    let _path = PathBuf::from(project_path.clone());
-    let _folder = PathBuf::from(folder_path);
-    let _should_expand = expand;
-    
-    // TODO: Implement folder expansion logic
-    get_dependency_view(project_path, None).await
+    let mut sessions = view_state.lock().map_err(|e| e.to_string())?;
+    let session = sessions.entry(project_path).or_default();
+    session.view.set_folder_expanded(&PathBuf::from(folder_path), expand);
+
+    refresh_view(session, &state)
 }
 
 /// Command to update filter criteria
@@ -135,10 +160,36 @@ pub async fn toggle_folder_expansion(
 pub async fn update_filters(
     project_path: String,
     filters: FilterCriteria,
+    state: State<'_, ChronoGraphState>,
+    view_state: State<'_, DependencyViewState>,
 ) -> Result<DependencyView, String> {
-    let _path = PathBuf::from(project_path.clone());
-    let _filter_criteria = filters;
-    
-    // TODO: Implement filtering logic
-    get_dependency_view(project_path, None).await
-}
\ No newline at end of file
+    let mut sessions = view_state.lock().map_err(|e| e.to_string())?;
+    let session = sessions.entry(project_path).or_default();
+    session.view.filter_criteria = filters;
+
+    refresh_view(session, &state)
+}
+
+/// Rebuild a session's view from the snapshot it currently points at. When the
+/// filters exclude the pinned commit the session unpins and follows the latest
+/// matching snapshot; the view's `commit_hash` tells which one is shown.
+fn refresh_view(
+    session: &mut DependencyViewSession,
+    state: &State<'_, ChronoGraphState>,
+) -> Result<DependencyView, String> {
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    let engine = state_guard.as_ref().ok_or("No analysis available")?;
+    let snapshots = engine.get_snapshots();
+    let filters = &session.view.filter_criteria;
+
+    let pinned = dependency_view::select_snapshot(snapshots, session.commit_hash.as_deref(), filters);
+    if pinned.is_none() {
+        session.commit_hash = None;
+    }
+    let snapshot = pinned
+        .or_else(|| dependency_view::select_snapshot(snapshots, None, filters))
+        .ok_or("No analyzed commit matches the current filters")?;
+
+    session.view.rebuild(snapshot, Some(engine.get_file_history()));
+    Ok(session.view.clone())
+}
@@ -1,11 +1,5 @@
-pub mod models;
 pub mod commands;
-pub mod git_navigator;
-pub mod dependency_analyzer;
-pub mod lakos_analyzer;
-pub mod chronograph_engine;
 pub mod chronograph_commands;
-pub mod analysis_cache;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -15,14 +9,19 @@ fn greet(name: &str) -> String {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // Arc and Mutex are already imported in chronograph_commands
-    
+    // Warnings from the engine by default; RUST_LOG=debug shows its diagnostics
+    env_logger::Builder::new()
+        .filter_level(log::LevelFilter::Warn)
+        .parse_default_env()
+        .init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(chronograph_commands::ChronoGraphState::default())
         .manage(chronograph_commands::ProgressState::default())
+        .manage(commands::DependencyViewState::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             // Legacy commands (for backward compatibility)
@@ -45,6 +44,32 @@ pub fn run() {
             chronograph_commands::check_lakos_availability,
             chronograph_commands::get_commit_dependencies,
             chronograph_commands::get_commit_info,
+            chronograph_commands::diff_snapshots,
+            chronograph_commands::get_impact,
+            chronograph_commands::get_dependency_lifecycles,
+            chronograph_commands::get_node_details,
+            chronograph_commands::get_snapshot_file_changes,
+            chronograph_commands::get_node_metric_history,
+            chronograph_commands::get_change_coupling,
+            chronograph_commands::get_hotspots,
+            chronograph_commands::get_hotspot_timeline,
+            chronograph_commands::get_modularity,
+            chronograph_commands::get_modularity_timeline,
+            chronograph_commands::get_ownership,
+            chronograph_commands::get_cycle_history,
+            chronograph_commands::bisect_dependency,
+            chronograph_commands::get_metric_series,
+            chronograph_commands::get_package_metrics,
+            chronograph_commands::get_global_metric_series,
+            chronograph_commands::get_regressions,
+            chronograph_commands::update_regression_thresholds,
+            chronograph_commands::get_quality_breakdown,
+            chronograph_commands::update_quality_model,
+            chronograph_commands::get_rule_violations,
+            chronograph_commands::get_rule_violation_timeline,
+            chronograph_commands::update_architecture_rules,
+            chronograph_commands::record_baseline,
+            chronograph_commands::get_baseline_comparison,
             chronograph_commands::cleanup_analysis,
             chronograph_commands::get_analysis_config,
             chronograph_commands::export_analysis_results,